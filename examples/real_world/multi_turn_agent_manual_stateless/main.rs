@@ -6,6 +6,10 @@
 //! - You CANNOT use `previous_interaction_id`
 //! - You MUST manually build conversation history
 //!
+//! This example runs the function calling loop by hand to show each step.
+//! `create_with_auto_functions()` does the same with `with_store_disabled()`
+//! and returns the conversation in `AutoFunctionResult::transcript`.
+//!
 //! ## Key Differences from Stateful (store: true)
 //!
 //! | Aspect | Stateful | Stateless |
@@ -13,7 +17,7 @@
 //! | Server state | Yes | No |
 //! | `previous_interaction_id` | Yes | No |
 //! | Manual history | No | Yes |
-//! | Auto functions | Available | Blocked at runtime |
+//! | Auto functions | Available | Available (history resent each round) |
 //!
 //! ## When to Use Stateless
 //!
@@ -415,7 +419,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     println!("1. store: false means NO server-side state");
     println!("2. Must manually build conversation history");
     println!("3. Cannot use previous_interaction_id");
-    println!("4. create_with_auto_functions() returns a runtime error");
+    println!("4. create_with_auto_functions() can run this loop and return the transcript");
     println!("\nFinal history size: {} items", session.history_size());
 
     Ok(())
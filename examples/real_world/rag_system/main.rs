@@ -83,7 +83,7 @@ impl DocumentStore {
             .collect();
 
         // Sort by score descending
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
 
         // Return top-k results
         scored
@@ -4,13 +4,63 @@
 //! and auto-registration code for functions annotated with `#[tool]`.
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{ItemFn, Pat};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{ItemFn, Pat, Type};
 use utoipa::openapi::RefOr;
 use utoipa::openapi::schema::Schema;
 
+use crate::parsing::ParamConfig;
 use crate::schema::get_type_info;
 
+/// A parameter whose schema comes from `genai_rs::ToolSchema` at runtime.
+pub struct DerivedParam {
+    /// The parameter name.
+    pub name: String,
+    /// The parameter type, with any `Option` removed.
+    pub ty: Type,
+    /// Description and enum values from the macro attribute.
+    pub config: ParamConfig,
+}
+
+/// Generates a statement inserting a derived parameter's schema into `map`.
+fn derived_param_tokens(param: &DerivedParam) -> TokenStream {
+    let name = &param.name;
+    let ty = &param.ty;
+
+    let description = param
+        .config
+        .description
+        .as_ref()
+        .filter(|desc| !desc.is_empty())
+        .map(|desc| {
+            quote! {
+                schema.insert(
+                    "description".to_string(),
+                    ::serde_json::Value::String(#desc.to_string()),
+                );
+            }
+        });
+    let enum_values = param.config.enum_values.as_ref().map(|enums| {
+        let enums = json_value_to_tokens(&serde_json::Value::Array(enums.clone()));
+        quote! {
+            schema.insert("enum".to_string(), #enums);
+        }
+    });
+
+    let schema = quote_spanned! {ty.span()=>
+        <#ty as ::genai_rs::ToolSchema>::tool_schema()
+    };
+    quote! {{
+        let mut schema = #schema;
+        if let ::serde_json::Value::Object(schema) = &mut schema {
+            #description
+            #enum_values
+        }
+        map.insert(#name.to_string(), schema);
+    }}
+}
+
 /// Converts a `serde_json::Value` to a `TokenStream` that constructs an equivalent
 /// `Value` at runtime.
 ///
@@ -62,17 +112,23 @@ fn json_value_to_tokens(value: &serde_json::Value) -> TokenStream {
 ///
 /// This includes:
 /// - The original function (unchanged)
-/// - A `{FuncName}Callable` struct implementing `CallableFunction`
+/// - A `{FuncName}Callable` struct implementing `CallableFunction` (with a
+///   `timeout()` override when `timeout_secs` is set)
 /// - A `{func_name}_declaration()` function returning the `FunctionDeclaration`
 /// - A `{func_name}_callable_factory()` function for the registry
 /// - Automatic registration via `inventory::submit!`
+///
+/// Parameters in `derived_params` are added to the properties at runtime from
+/// their `ToolSchema` implementations.
 #[allow(clippy::too_many_lines)]
 pub fn generate_declaration_function(
     func: &ItemFn,
     func_name: &str,
     func_description: &str,
     parameters_schema_ref: &RefOr<Schema>,
+    derived_params: &[DerivedParam],
     required_params_for_struct_field: &[String],
+    timeout_secs: Option<u64>,
 ) -> TokenStream {
     let generated_fn_name =
         syn::Ident::new(&format!("{func_name}_declaration"), func.sig.ident.span());
@@ -123,8 +179,11 @@ pub fn generate_declaration_function(
     let properties_value = match parameters_schema_value.get("properties") {
         Some(props) => props.clone(),
         None => {
-            // Functions with no parameters will have no properties - that's fine
-            if !required_params_for_struct_field.is_empty() {
+            // Functions with no builtin-schema parameters will have no properties - that's fine
+            if required_params_for_struct_field
+                .iter()
+                .any(|name| !derived_params.iter().any(|param| &param.name == name))
+            {
                 return syn::Error::new(
                     func.sig.ident.span(),
                     format!(
@@ -139,6 +198,18 @@ pub fn generate_declaration_function(
     };
 
     let properties_tokens = json_value_to_tokens(&properties_value);
+    let properties_tokens = if derived_params.is_empty() {
+        properties_tokens
+    } else {
+        let inserts = derived_params.iter().map(derived_param_tokens);
+        quote! {{
+            let mut properties = #properties_tokens;
+            if let ::serde_json::Value::Object(map) = &mut properties {
+                #(#inserts)*
+            }
+            properties
+        }}
+    };
 
     let mut arg_names = Vec::new();
     let mut arg_extraction_tokens = Vec::new();
@@ -191,6 +262,14 @@ pub fn generate_declaration_function(
         quote! { #original_fn_ident(#fn_call_args) }
     };
 
+    let timeout_fn = timeout_secs.map(|secs| {
+        quote! {
+            fn timeout(&self) -> ::std::option::Option<::std::time::Duration> {
+                ::std::option::Option::Some(::std::time::Duration::from_secs(#secs))
+            }
+        }
+    });
+
     let output = quote! {
         #func
 
@@ -235,6 +314,8 @@ pub fn generate_declaration_function(
                     Err(e) => Err(::genai_rs::function_calling::FunctionError::ExecutionError(Box::new(e)))
                 }
             }
+
+            #timeout_fn
         }
 
         pub fn #generated_fn_name() -> ::genai_rs::FunctionDeclaration {
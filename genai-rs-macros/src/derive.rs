@@ -0,0 +1,334 @@
+//! Code generation for `#[derive(ToolSchema)]`.
+//!
+//! Implements `genai_rs::ToolSchema` for structs with named fields (as JSON
+//! objects) and unit-only enums (as string enums), following the `serde`
+//! attributes that change what gets deserialized.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue, Token,
+    parse_quote,
+};
+
+use crate::parsing::extract_doc_comments;
+use crate::schema::get_type_info;
+
+/// A `serde` `rename_all` rule.
+#[derive(Debug, Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            other => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    format!("Unknown rename_all rule '{other}'"),
+                ));
+            }
+        })
+    }
+
+    /// Renames a `PascalCase` enum variant, as `serde` does.
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => {
+                let mut chars = variant.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_ascii_lowercase().to_string() + chars.as_str()
+                })
+            }
+            Self::Snake | Self::ScreamingSnake | Self::Kebab | Self::ScreamingKebab => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                self.apply_to_field(&snake)
+            }
+        }
+    }
+
+    /// Renames a `snake_case` struct field, as `serde` does.
+    fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal | Self::Camel => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                if matches!(self, Self::Camel) {
+                    Self::Camel.apply_to_variant(&pascal)
+                } else {
+                    pascal
+                }
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+/// The `serde` attributes that affect a type's schema.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<RenameRule>,
+    default: bool,
+    skip: bool,
+    flatten: bool,
+}
+
+/// Returns the deserialization name from `rename = "..."` or
+/// `rename(deserialize = "...")`.
+fn deserialize_name(meta: &Meta) -> syn::Result<Option<syn::LitStr>> {
+    match meta {
+        Meta::NameValue(MetaNameValue {
+            value: Expr::Lit(ExprLit {
+                lit: Lit::Str(s), ..
+            }),
+            ..
+        }) => Ok(Some(s.clone())),
+        Meta::List(list) => {
+            let nested =
+                list.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+            for nv in nested {
+                if nv.path.is_ident("deserialize")
+                    && let Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) = nv.value
+                {
+                    return Ok(Some(s));
+                }
+            }
+            Ok(None)
+        }
+        Meta::Path(_) => Ok(None),
+        Meta::NameValue(nv) => Err(syn::Error::new_spanned(
+            &nv.value,
+            "Expected string literal",
+        )),
+    }
+}
+
+fn parse_serde_attrs(attrs: &[Attribute]) -> syn::Result<SerdeAttrs> {
+    let mut serde = SerdeAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        for meta in metas {
+            let path = meta.path();
+            if path.is_ident("rename") {
+                serde.rename = deserialize_name(&meta)?.map(|s| s.value());
+            } else if path.is_ident("rename_all") {
+                if let Some(rule) = deserialize_name(&meta)? {
+                    serde.rename_all = Some(RenameRule::parse(&rule)?);
+                }
+            } else if path.is_ident("default") {
+                serde.default = true;
+            } else if path.is_ident("skip") || path.is_ident("skip_deserializing") {
+                serde.skip = true;
+            } else if path.is_ident("flatten") {
+                serde.flatten = true;
+            }
+        }
+    }
+    Ok(serde)
+}
+
+/// Adds `"description": doc` to a schema expression when `doc` is non-empty.
+fn with_description(schema: TokenStream, doc: &str) -> TokenStream {
+    if doc.is_empty() {
+        return schema;
+    }
+    quote! {{
+        let mut schema = #schema;
+        if let ::serde_json::Value::Object(map) = &mut schema {
+            map.insert(
+                "description".to_string(),
+                ::serde_json::Value::String(#doc.to_string()),
+            );
+        }
+        schema
+    }}
+}
+
+/// Generates the `ToolSchema` implementation for a derive input.
+pub fn derive_tool_schema(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    let container = parse_serde_attrs(&input.attrs)?;
+    let description = extract_doc_comments(&input.attrs);
+
+    let schema = match &input.data {
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "ToolSchema can only be derived for structs with named fields",
+                ));
+            };
+
+            let mut inserts = Vec::new();
+            for field in &fields.named {
+                let serde = parse_serde_attrs(&field.attrs)?;
+                if serde.skip {
+                    continue;
+                }
+
+                let ty = &field.ty;
+                let field_schema = quote_spanned! {ty.span()=>
+                    <#ty as ::genai_rs::ToolSchema>::tool_schema()
+                };
+                let (is_option, _) = get_type_info(ty);
+                let is_required = !is_option && !serde.default && !container.default;
+
+                if serde.flatten {
+                    // The flattened type's fields sit directly in this object
+                    let merge_required = is_required.then(|| {
+                        quote! {
+                            if let Some(::serde_json::Value::Array(names)) = flattened.get("required") {
+                                required.extend(names.iter().cloned());
+                            }
+                        }
+                    });
+                    inserts.push(quote! {{
+                        let flattened = #field_schema;
+                        if let Some(::serde_json::Value::Object(fields)) = flattened.get("properties") {
+                            properties.extend(fields.clone());
+                        }
+                        if let Some(additional) = flattened.get("additionalProperties") {
+                            schema.insert("additionalProperties".to_string(), additional.clone());
+                        }
+                        #merge_required
+                    }});
+                    continue;
+                }
+
+                let ident = field.ident.as_ref().expect("named field");
+                let ident = ident.to_string();
+                let ident = ident.trim_start_matches("r#");
+                let name = serde.rename.unwrap_or_else(|| {
+                    container
+                        .rename_all
+                        .map_or_else(|| ident.to_string(), |rule| rule.apply_to_field(ident))
+                });
+
+                let field_schema =
+                    with_description(field_schema, &extract_doc_comments(&field.attrs));
+                inserts.push(quote! {
+                    properties.insert(#name.to_string(), #field_schema);
+                });
+                if is_required {
+                    inserts.push(quote! {
+                        required.push(::serde_json::Value::String(#name.to_string()));
+                    });
+                }
+            }
+
+            // Self-referential types describe their nested occurrences as
+            // plain objects instead of recursing forever
+            quote! {
+                ::genai_rs::tool_schema::guard_recursion(::std::any::type_name::<Self>(), || {
+                    let mut schema = ::serde_json::Map::new();
+                    #[allow(unused_mut)]
+                    let mut properties = ::serde_json::Map::new();
+                    #[allow(unused_mut)]
+                    let mut required = ::std::vec::Vec::<::serde_json::Value>::new();
+                    #(#inserts)*
+                    schema.insert("type".to_string(), "object".into());
+                    schema.insert("properties".to_string(), ::serde_json::Value::Object(properties));
+                    if !required.is_empty() {
+                        schema.insert("required".to_string(), ::serde_json::Value::Array(required));
+                    }
+                    ::serde_json::Value::Object(schema)
+                })
+            }
+        }
+        Data::Enum(data) => {
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "ToolSchema can only be derived for enums whose variants have no fields",
+                    ));
+                }
+                let serde = parse_serde_attrs(&variant.attrs)?;
+                if serde.skip {
+                    continue;
+                }
+                let ident = variant.ident.to_string();
+                values.push(serde.rename.unwrap_or_else(|| {
+                    container
+                        .rename_all
+                        .map_or_else(|| ident.clone(), |rule| rule.apply_to_variant(&ident))
+                }));
+            }
+            quote! {
+                ::serde_json::json!({"type": "string", "enum": [#(#values),*]})
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "ToolSchema cannot be derived for unions",
+            ));
+        }
+    };
+    let schema = with_description(schema, &description);
+
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::genai_rs::ToolSchema));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::genai_rs::ToolSchema for #ident #ty_generics #where_clause {
+            fn tool_schema() -> ::serde_json::Value {
+                #schema
+            }
+        }
+    })
+}
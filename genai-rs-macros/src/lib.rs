@@ -6,11 +6,13 @@ use utoipa::openapi::RefOr;
 use utoipa::openapi::schema::{ObjectBuilder, Schema};
 
 mod codegen;
+mod derive;
 mod parsing;
 mod schema;
 
+use codegen::DerivedParam;
 use parsing::parse_input;
-use schema::{build_param_schema, get_type_info};
+use schema::{build_param_schema, get_type_info, has_builtin_schema};
 
 /// Generates a function that returns a `FunctionDeclaration` for the annotated function.
 ///
@@ -29,6 +31,20 @@ use schema::{build_param_schema, get_type_info};
 /// // The macro generates:
 /// // pub fn get_weather_declaration() -> genai_rs::FunctionDeclaration { ... }
 /// ```
+///
+/// `timeout_secs = N` limits each call during automatic function calling,
+/// overriding the builder's `with_function_timeout()`:
+///
+/// ```ignore
+/// #[tool(url(description = "URL to fetch"), timeout_secs = 10)]
+/// async fn fetch_url(url: String) -> String {
+///     // ...
+/// }
+/// ```
+///
+/// Parameters may be `String`, numbers, `bool`, `serde_json::Value`, `Vec`s
+/// of those, or any type implementing `genai_rs::ToolSchema`, such as a struct
+/// with `#[derive(ToolSchema)]`. `Option<T>` parameters are not required.
 #[proc_macro_attribute]
 pub fn tool(attr_input: TokenStream, item: TokenStream) -> TokenStream {
     let input = match parse_input(attr_input, item) {
@@ -70,6 +86,7 @@ pub fn tool(attr_input: TokenStream, item: TokenStream) -> TokenStream {
 
     let func_description = parsing::extract_doc_comments(&func.attrs);
     let mut object_builder = ObjectBuilder::new();
+    let mut derived_params = Vec::new();
     let mut required_params_for_struct_field = Vec::new();
 
     for fn_arg in &func.sig.inputs {
@@ -78,11 +95,19 @@ pub fn tool(attr_input: TokenStream, item: TokenStream) -> TokenStream {
         {
             let param_name = pat_ident.ident.to_string();
             let config = config_map.get(&param_name);
-            let param_schema = build_param_schema(pat_type, config);
+            let (is_option, inner_type) = get_type_info(&pat_type.ty);
 
-            object_builder = object_builder.property(param_name.clone(), param_schema);
+            if has_builtin_schema(&inner_type) {
+                let param_schema = build_param_schema(pat_type, config);
+                object_builder = object_builder.property(param_name.clone(), param_schema);
+            } else {
+                derived_params.push(DerivedParam {
+                    name: param_name.clone(),
+                    ty: inner_type,
+                    config: config.cloned().unwrap_or_default(),
+                });
+            }
 
-            let (is_option, _) = get_type_info(&pat_type.ty);
             if !is_option {
                 required_params_for_struct_field.push(param_name.clone());
             }
@@ -97,7 +122,55 @@ pub fn tool(attr_input: TokenStream, item: TokenStream) -> TokenStream {
         &func_name,
         &func_description,
         &parameters_schema_ref_or,
+        &derived_params,
         &required_params_for_struct_field,
+        input.timeout_secs,
     )
     .into()
 }
+
+/// Implements `genai_rs::ToolSchema` so the type can be a `#[tool]` parameter.
+///
+/// Structs with named fields become JSON objects: each field is a property,
+/// described by its doc comment, and fields are required unless they are
+/// `Option`s or have `#[serde(default)]`. Enums whose variants have no fields
+/// become strings restricted to the variant names. The type's own doc comment
+/// is its description. `#[serde(rename)]`, `#[serde(rename_all)]`,
+/// `#[serde(skip)]`, and `#[serde(flatten)]` are applied, so the schema matches
+/// what `serde` deserializes. A struct that contains itself (for example through
+/// `Vec<Self>` or `Option<Box<Self>>`) describes the nested occurrence as a
+/// plain `object`.
+///
+/// # Example
+/// ```ignore
+/// use genai_rs_macros::{ToolSchema, tool};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, ToolSchema)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Unit {
+///     Celsius,
+///     Fahrenheit,
+/// }
+///
+/// #[derive(Deserialize, ToolSchema)]
+/// struct Forecast {
+///     /// The city and state
+///     location: String,
+///     unit: Option<Unit>,
+///     /// How many days ahead to forecast
+///     days: u8,
+/// }
+///
+/// #[tool(request(description = "What to forecast"))]
+/// fn get_forecast(request: Forecast) -> String {
+///     format!("Forecast for {}", request.location)
+/// }
+/// ```
+#[proc_macro_derive(ToolSchema)]
+pub fn derive_tool_schema(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    derive::derive_tool_schema(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,7 +1,8 @@
 //! Parsing utilities for the `#[tool]` procedural macro.
 //!
 //! This module handles parsing of:
-//! - Macro attribute arguments (parameter descriptions and enum values)
+//! - Macro attribute arguments (parameter descriptions and enum values, plus
+//!   function-level options like `timeout_secs`)
 //! - Function signatures and their doc comments
 
 use proc_macro::TokenStream;
@@ -9,8 +10,8 @@ use quote::ToTokens;
 use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
 use syn::{
-    Attribute, Expr, ExprArray, ExprLit, Ident, ItemFn, Lit, LitStr, Meta, Token, parenthesized,
-    punctuated::Punctuated,
+    Attribute, Expr, ExprArray, ExprLit, Ident, ItemFn, Lit, LitStr, Meta, MetaNameValue, Token,
+    parenthesized, punctuated::Punctuated,
 };
 
 /// Configuration for a single function parameter extracted from the macro attribute.
@@ -85,10 +86,27 @@ impl Parse for SingleParamConfigInput {
     }
 }
 
-/// Parses the complete macro attribute: `param1(...), param2(...)`
+/// One entry of the macro attribute: `param(...)` or a function-level `option = value`.
+#[derive(Debug)]
+enum ToolAttrEntry {
+    Param(SingleParamConfigInput),
+    Option(MetaNameValue),
+}
+
+impl Parse for ToolAttrEntry {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        if input.peek2(Token![=]) {
+            Ok(Self::Option(input.parse()?))
+        } else {
+            Ok(Self::Param(input.parse()?))
+        }
+    }
+}
+
+/// Parses the complete macro attribute: `param1(...), param2(...), timeout_secs = 10`
 #[derive(Debug)]
 struct AllParamsConfigInput {
-    configs: Punctuated<SingleParamConfigInput, Token![,]>,
+    configs: Punctuated<ToolAttrEntry, Token![,]>,
 }
 
 impl Parse for AllParamsConfigInput {
@@ -98,7 +116,7 @@ impl Parse for AllParamsConfigInput {
                 configs: Punctuated::new(),
             })
         } else {
-            let configs = input.parse_terminated(SingleParamConfigInput::parse, Token![,])?;
+            let configs = input.parse_terminated(ToolAttrEntry::parse, Token![,])?;
             Ok(Self { configs })
         }
     }
@@ -110,6 +128,25 @@ pub struct MacroInput {
     pub func: ItemFn,
     /// Map of parameter name to its configuration (description, enum_values).
     pub param_configs: HashMap<String, ParamConfig>,
+    /// Per-call execution timeout from `timeout_secs = N`.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Parses `timeout_secs = N`, which must be a positive integer literal.
+fn parse_timeout_secs(value: &Expr) -> syn::Result<u64> {
+    if let Expr::Lit(ExprLit {
+        lit: Lit::Int(int), ..
+    }) = value
+    {
+        let secs = int.base10_parse::<u64>()?;
+        if secs > 0 {
+            return Ok(secs);
+        }
+    }
+    Err(syn::Error::new_spanned(
+        value,
+        "Expected a positive integer literal for timeout_secs",
+    ))
 }
 
 /// Parses both the macro attribute and the function item.
@@ -120,7 +157,24 @@ pub fn parse_input(attr_input: TokenStream, item: TokenStream) -> syn::Result<Ma
     let func = syn::parse::<ItemFn>(item)?;
 
     let mut param_configs = HashMap::new();
-    for config in all_params_config.configs {
+    let mut timeout_secs = None;
+    for entry in all_params_config.configs {
+        let config = match entry {
+            ToolAttrEntry::Param(config) => config,
+            ToolAttrEntry::Option(nv) => {
+                if nv.path.is_ident("timeout_secs") {
+                    timeout_secs = Some(parse_timeout_secs(&nv.value)?);
+                    continue;
+                }
+                return Err(syn::Error::new_spanned(
+                    &nv.path,
+                    format!(
+                        "Unknown option '{}'. Valid options are: timeout_secs",
+                        nv.path.to_token_stream()
+                    ),
+                ));
+            }
+        };
         let name = config.name.to_string();
         let mut details = ParamConfig::default();
 
@@ -154,6 +208,7 @@ pub fn parse_input(attr_input: TokenStream, item: TokenStream) -> syn::Result<Ma
     Ok(MacroInput {
         func,
         param_configs,
+        timeout_secs,
     })
 }
 
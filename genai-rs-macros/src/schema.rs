@@ -37,41 +37,64 @@ pub fn get_type_info(ty: &Type) -> (bool, Type) {
 /// - `bool` -> `Boolean`
 /// - `Vec<T>` -> `Array`
 /// - `serde_json::Value` -> `Object`
-/// - Other types default to `Object`
-fn map_rust_type_to_openapi_type(rust_type_path: &Type) -> OpenApiType {
+/// - Other types return `None`; their schema comes from `genai_rs::ToolSchema`
+fn map_rust_type_to_openapi_type(rust_type_path: &Type) -> Option<OpenApiType> {
     let type_str = rust_type_path
         .to_token_stream()
         .to_string()
         .replace(' ', "");
     match type_str.as_str() {
-        "String" => OpenApiType::String,
-        "i32" | "i64" | "isize" | "u32" | "u64" | "usize" => OpenApiType::Integer,
-        "f32" | "f64" => OpenApiType::Number,
-        "bool" => OpenApiType::Boolean,
-        s if s.starts_with("Vec<") && s.ends_with('>') => OpenApiType::Array,
-        s if s == "Value" || s == "serde_json::Value" => OpenApiType::Object,
-        _ => OpenApiType::Object,
+        "String" => Some(OpenApiType::String),
+        "i32" | "i64" | "isize" | "u32" | "u64" | "usize" => Some(OpenApiType::Integer),
+        "f32" | "f64" => Some(OpenApiType::Number),
+        "bool" => Some(OpenApiType::Boolean),
+        s if s.starts_with("Vec<") && s.ends_with('>') => Some(OpenApiType::Array),
+        s if s == "Value" || s == "serde_json::Value" => Some(OpenApiType::Object),
+        _ => None,
     }
 }
 
-/// Builds an OpenAPI schema for a function parameter.
+/// Returns `T` if the type is `Vec<T>`.
+fn vec_item_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.first()
+        && segment.ident == "Vec"
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return Some(inner_ty);
+    }
+    None
+}
+
+/// Returns `true` if [`build_param_schema`] can describe the type on its own.
+///
+/// That is the primitive types above and `Vec`s of them. Parameters of any
+/// other type (including nested `Vec`s) get their schema at runtime from
+/// `genai_rs::ToolSchema`.
+pub fn has_builtin_schema(ty: &Type) -> bool {
+    match vec_item_type(ty) {
+        Some(item_ty) => map_rust_type_to_openapi_type(item_ty)
+            .is_some_and(|item_type| item_type != OpenApiType::Array),
+        None => map_rust_type_to_openapi_type(ty).is_some(),
+    }
+}
+
+/// Builds an OpenAPI schema for a function parameter whose type has a
+/// [builtin schema](has_builtin_schema).
 ///
 /// Uses the Rust type to determine the schema type, and applies any
 /// configuration (description, enum_values) from the macro attribute.
 pub fn build_param_schema(pat_type: &syn::PatType, config: Option<&ParamConfig>) -> RefOr<Schema> {
     let (_, inner_type) = get_type_info(&pat_type.ty);
-    let api_type = map_rust_type_to_openapi_type(&inner_type);
+    let api_type = map_rust_type_to_openapi_type(&inner_type).unwrap_or(OpenApiType::Object);
 
     if api_type == OpenApiType::Array {
         let mut array_builder = ArrayBuilder::new();
 
-        if let Type::Path(type_path) = &inner_type
-            && let Some(segment) = type_path.path.segments.first()
-            && segment.ident == "Vec"
-            && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
-            && let Some(syn::GenericArgument::Type(inner_ty_of_vec)) = args.args.first()
-        {
-            let inner_api_type = map_rust_type_to_openapi_type(inner_ty_of_vec);
+        if let Some(inner_ty_of_vec) = vec_item_type(&inner_type) {
+            let inner_api_type =
+                map_rust_type_to_openapi_type(inner_ty_of_vec).unwrap_or(OpenApiType::Object);
             let items_schema = ObjectBuilder::new().schema_type(inner_api_type).build();
             array_builder = array_builder.items(RefOr::T(Schema::Object(items_schema)));
         }
@@ -0,0 +1,86 @@
+//! Human-in-the-loop approval for auto-executed function calls.
+//!
+//! By default the auto-function loops run every function the model asks for
+//! as soon as the call arrives. An [`ApprovalPolicy`] registered with
+//! [`InteractionBuilder::with_approval_policy()`](crate::InteractionBuilder::with_approval_policy)
+//! reviews each call first and decides whether it runs:
+//!
+//! - [`ApprovalDecision::Approve`] - execute the call as requested
+//! - [`ApprovalDecision::Deny`] - skip it and send the reason to the model as
+//!   a function error, so it can explain or try something else
+//! - [`ApprovalDecision::ModifyArgs`] - execute it with replacement arguments
+//!
+//! Calls from one model turn are reviewed one at a time, in the order the
+//! model requested them, before any of them execute. In
+//! `create_stream_with_auto_functions()`, each review is preceded by an
+//! [`AwaitingApproval`](crate::AutoFunctionStreamChunk::AwaitingApproval)
+//! event so a UI can prompt the user; the stream waits on the policy, so keep
+//! polling it while the prompt is open.
+//!
+//! # Example
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use genai_rs::{ApprovalDecision, ApprovalPolicy, PendingFunctionCall};
+//!
+//! /// Lets read-only tools through and blocks everything else.
+//! #[derive(Debug)]
+//! struct ReadOnly;
+//!
+//! #[async_trait]
+//! impl ApprovalPolicy for ReadOnly {
+//!     async fn review(&self, call: &PendingFunctionCall) -> ApprovalDecision {
+//!         if call.name.starts_with("get_") {
+//!             ApprovalDecision::Approve
+//!         } else {
+//!             ApprovalDecision::deny(format!("{} is not allowed in read-only mode", call.name))
+//!         }
+//!     }
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::PendingFunctionCall;
+
+/// The outcome of reviewing a function call with an [`ApprovalPolicy`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ApprovalDecision {
+    /// Execute the call with the arguments the model sent.
+    Approve,
+    /// Skip the call. The model receives `reason` as the function's error.
+    Deny {
+        /// Why the call was not executed
+        reason: String,
+    },
+    /// Execute the call with these arguments instead.
+    ///
+    /// The replacement arguments are what the function receives and what
+    /// [`FunctionExecutionResult::args`](crate::FunctionExecutionResult::args)
+    /// records.
+    ModifyArgs(Value),
+}
+
+impl ApprovalDecision {
+    /// Creates a [`Deny`](Self::Deny) decision with the given reason.
+    #[must_use]
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self::Deny {
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Reviews function calls before the auto-function loop executes them.
+///
+/// See the [module documentation](self) for how decisions are applied.
+#[async_trait]
+pub trait ApprovalPolicy: Send + Sync + std::fmt::Debug {
+    /// Decides whether `call` is executed, skipped, or executed with other arguments.
+    ///
+    /// This may wait as long as it needs to, for example on a user's answer
+    /// to a prompt. Function execution timeouts do not include this time.
+    async fn review(&self, call: &PendingFunctionCall) -> ApprovalDecision;
+}
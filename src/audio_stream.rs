@@ -0,0 +1,471 @@
+//! Incremental audio output from interaction streams.
+//!
+//! [`AudioStreamWriter`] decodes the base64 audio deltas of a streaming
+//! response and writes them to any [`AsyncWrite`] sink as they arrive, so
+//! playback can start before the interaction completes. Output is either raw
+//! PCM or a WAV file whose header sizes are patched when the writer finishes.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use genai_rs::{AudioStreamWriter, Client};
+//!
+//! # async fn example() -> Result<(), genai_rs::GenaiError> {
+//! let client = Client::new("your-api-key".to_string());
+//!
+//! let mut stream = client
+//!     .interaction()
+//!     .with_model("gemini-2.5-flash-preview-tts")
+//!     .with_text("Say hello")
+//!     .with_audio_output()
+//!     .create_stream();
+//!
+//! let file = tokio::fs::File::create("hello.wav").await?;
+//! let mut audio = AudioStreamWriter::wav(file);
+//! while let Some(event) = stream.next().await {
+//!     audio.write_event(&event?).await?;
+//! }
+//! audio.finish().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::SeekFrom;
+
+use base64::Engine;
+use futures_util::future::BoxFuture;
+use tokio::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+use crate::stream_ext::DeltaEvent;
+use crate::{Content, GenaiError};
+
+/// Sample rate assumed when an audio delta has no MIME type.
+const DEFAULT_SAMPLE_RATE: u32 = 24_000;
+
+/// Size of the canonical 44-byte WAV header.
+const WAV_HEADER_LEN: u32 = 44;
+
+/// Layout of 16-bit PCM audio, as announced by an `audio/L16` MIME type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PcmFormat {
+    /// Samples per second
+    pub sample_rate: u32,
+    /// Interleaved channels
+    pub channels: u16,
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self {
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            channels: 1,
+        }
+    }
+}
+
+impl PcmFormat {
+    /// Parses an `audio/L16` MIME type such as `audio/L16;codec=pcm;rate=24000`.
+    ///
+    /// Returns `None` for other audio types. Missing `rate` and `channels`
+    /// parameters default to 24 kHz mono.
+    #[must_use]
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let mut params = mime_type.split(';').map(str::trim);
+        if !params.next()?.eq_ignore_ascii_case("audio/L16") {
+            return None;
+        }
+        let mut format = Self::default();
+        for param in params {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            match key.trim().to_ascii_lowercase().as_str() {
+                "rate" => format.sample_rate = value.trim().parse().ok()?,
+                "channels" => format.channels = value.trim().parse().ok()?,
+                _ => {}
+            }
+        }
+        Some(format)
+    }
+}
+
+type PatchWavHeader<W> = for<'w> fn(&'w mut W, u64) -> BoxFuture<'w, std::io::Result<()>>;
+
+/// Writes audio deltas from a stream to an [`AsyncWrite`] sink as they arrive.
+///
+/// Create one with [`pcm()`](Self::pcm) or [`wav()`](Self::wav), feed it every
+/// stream event with [`write_event()`](Self::write_event), then call
+/// [`finish()`](Self::finish). Non-audio events are ignored. Each audio delta
+/// is written and flushed immediately.
+///
+/// Base64 data may be split across deltas at any position; incomplete groups
+/// are carried over to the next delta.
+pub struct AudioStreamWriter<W> {
+    writer: W,
+    /// Set for WAV output; seeks back to fill in the header sizes
+    patch_wav_header: Option<PatchWavHeader<W>>,
+    /// Base64 characters not yet decoded
+    pending: String,
+    format: Option<PcmFormat>,
+    header_written: bool,
+    bytes_written: u64,
+}
+
+impl<W> std::fmt::Debug for AudioStreamWriter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioStreamWriter")
+            .field("wav", &self.patch_wav_header.is_some())
+            .field("format", &self.format)
+            .field("bytes_written", &self.bytes_written)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AudioStreamWriter<W> {
+    /// Writes decoded audio bytes as they arrive, without a container.
+    ///
+    /// For the `audio/L16` output of TTS models this is raw 16-bit
+    /// little-endian PCM; see [`format()`](Self::format) for its sample rate.
+    /// Audio in other formats is passed through as decoded.
+    #[must_use]
+    pub fn pcm(writer: W) -> Self {
+        Self {
+            writer,
+            patch_wav_header: None,
+            pending: String::new(),
+            format: None,
+            header_written: false,
+            bytes_written: 0,
+        }
+    }
+
+    /// Writes the audio of a stream event, if it is an audio delta.
+    ///
+    /// Accepts [`StreamEvent`](crate::StreamEvent)s and
+    /// [`AutoFunctionStreamEvent`](crate::AutoFunctionStreamEvent)s. Returns the
+    /// number of audio bytes written.
+    ///
+    /// # Errors
+    ///
+    /// See [`write_content()`](Self::write_content).
+    pub async fn write_event(&mut self, event: &impl DeltaEvent) -> Result<usize, GenaiError> {
+        match event.delta() {
+            Some(content) => self.write_content(content).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Writes the audio of a content delta, if it is audio.
+    ///
+    /// Returns the number of audio bytes written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::InvalidInput`] if the data is not valid base64, if
+    /// WAV output receives audio that is not `audio/L16` PCM, or if the PCM
+    /// format changes mid-stream. Returns [`GenaiError::Io`] if writing fails.
+    pub async fn write_content(&mut self, content: &Content) -> Result<usize, GenaiError> {
+        let Content::Audio {
+            data: Some(data),
+            mime_type,
+            ..
+        } = content
+        else {
+            return Ok(0);
+        };
+        self.check_format(mime_type.as_deref())?;
+        if !data.is_ascii() {
+            return Err(GenaiError::InvalidInput(
+                "Invalid base64 audio data: non-ASCII characters".to_string(),
+            ));
+        }
+
+        self.pending
+            .extend(data.chars().filter(|c| !c.is_whitespace()));
+        let complete = self.pending.len() / 4 * 4;
+        let bytes = decode_base64(&self.pending[..complete])?;
+        self.pending.drain(..complete);
+        self.write_audio(&bytes).await?;
+        Ok(bytes.len())
+    }
+
+    /// The PCM format of the audio written so far, if known.
+    #[must_use]
+    pub fn format(&self) -> Option<PcmFormat> {
+        self.format
+    }
+
+    /// Number of audio bytes written, excluding any WAV header.
+    #[must_use]
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Writes any remaining audio, completes the WAV header, flushes, and
+    /// returns the sink.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::InvalidInput`] if leftover base64 data is invalid,
+    /// or [`GenaiError::Io`] if writing fails.
+    pub async fn finish(mut self) -> Result<W, GenaiError> {
+        if !self.pending.is_empty() {
+            let bytes = decode_base64(&std::mem::take(&mut self.pending))?;
+            self.write_audio(&bytes).await?;
+        }
+        if let Some(patch_wav_header) = self.patch_wav_header {
+            if !self.header_written {
+                self.write_wav_header().await?;
+            }
+            patch_wav_header(&mut self.writer, self.bytes_written).await?;
+        }
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
+
+    fn check_format(&mut self, mime_type: Option<&str>) -> Result<(), GenaiError> {
+        let format = match mime_type {
+            None => Some(PcmFormat::default()),
+            Some(mime_type) => PcmFormat::from_mime_type(mime_type),
+        };
+        match (format, self.format) {
+            (None, _) if self.patch_wav_header.is_some() => Err(GenaiError::InvalidInput(format!(
+                "WAV output requires audio/L16 PCM audio, got '{}'",
+                mime_type.unwrap_or_default()
+            ))),
+            (Some(format), Some(current)) if format != current => Err(GenaiError::InvalidInput(
+                format!("Audio format changed mid-stream from {current:?} to {format:?}"),
+            )),
+            (Some(format), None) => {
+                self.format = Some(format);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn write_audio(&mut self, bytes: &[u8]) -> Result<(), GenaiError> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        if self.patch_wav_header.is_some() && !self.header_written {
+            self.write_wav_header().await?;
+        }
+        self.writer.write_all(bytes).await?;
+        self.writer.flush().await?;
+        self.bytes_written += bytes.len() as u64;
+        Ok(())
+    }
+
+    async fn write_wav_header(&mut self) -> Result<(), GenaiError> {
+        // Sizes are unknown until finish(); the maximum marks a stream in progress
+        let header = wav_header(self.format.unwrap_or_default(), u32::MAX - WAV_HEADER_LEN);
+        self.writer.write_all(&header).await?;
+        self.header_written = true;
+        Ok(())
+    }
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin + Send> AudioStreamWriter<W> {
+    /// Writes a WAV file: a 16-bit PCM header followed by the audio as it
+    /// arrives.
+    ///
+    /// The header is written with the first audio delta, using placeholder
+    /// sizes that [`finish()`](Self::finish) replaces by seeking back. Only
+    /// `audio/L16` audio (the output of TTS models) can be written as WAV.
+    #[must_use]
+    pub fn wav(writer: W) -> Self {
+        Self {
+            patch_wav_header: Some(patch_wav_sizes::<W>),
+            ..Self::pcm(writer)
+        }
+    }
+}
+
+fn decode_base64(data: &str) -> Result<Vec<u8>, GenaiError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| GenaiError::InvalidInput(format!("Invalid base64 audio data: {}", e)))
+}
+
+fn wav_header(format: PcmFormat, data_len: u32) -> Vec<u8> {
+    const BITS_PER_SAMPLE: u16 = 16;
+    let block_align = format.channels * BITS_PER_SAMPLE / 8;
+    let byte_rate = format.sample_rate * u32::from(block_align);
+
+    let mut header = Vec::with_capacity(WAV_HEADER_LEN as usize);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(data_len + WAV_HEADER_LEN - 8).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&format.channels.to_le_bytes());
+    header.extend_from_slice(&format.sample_rate.to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+fn patch_wav_sizes<W>(writer: &mut W, data_len: u64) -> BoxFuture<'_, std::io::Result<()>>
+where
+    W: AsyncWrite + AsyncSeek + Unpin + Send,
+{
+    Box::pin(async move {
+        let data_len = u32::try_from(data_len)
+            .unwrap_or(u32::MAX)
+            .min(u32::MAX - WAV_HEADER_LEN);
+        writer.seek(SeekFrom::Start(4)).await?;
+        writer
+            .write_all(&(data_len + WAV_HEADER_LEN - 8).to_le_bytes())
+            .await?;
+        writer.seek(SeekFrom::Start(40)).await?;
+        writer.write_all(&data_len.to_le_bytes()).await?;
+        writer.seek(SeekFrom::End(0)).await?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn audio(bytes: &[u8], mime_type: Option<&str>) -> Content {
+        Content::Audio {
+            data: Some(base64::engine::general_purpose::STANDARD.encode(bytes)),
+            uri: None,
+            mime_type: mime_type.map(str::to_string),
+        }
+    }
+
+    fn raw_audio(data: &str) -> Content {
+        Content::Audio {
+            data: Some(data.to_string()),
+            uri: None,
+            mime_type: Some("audio/L16;codec=pcm;rate=24000".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_pcm_format_from_mime_type() {
+        assert_eq!(
+            PcmFormat::from_mime_type("audio/L16;codec=pcm;rate=16000"),
+            Some(PcmFormat {
+                sample_rate: 16_000,
+                channels: 1
+            })
+        );
+        assert_eq!(
+            PcmFormat::from_mime_type("audio/l16; rate=48000; channels=2"),
+            Some(PcmFormat {
+                sample_rate: 48_000,
+                channels: 2
+            })
+        );
+        assert_eq!(
+            PcmFormat::from_mime_type("audio/L16"),
+            Some(PcmFormat::default())
+        );
+        assert_eq!(PcmFormat::from_mime_type("audio/mpeg"), None);
+        assert_eq!(PcmFormat::from_mime_type("audio/L16;rate=fast"), None);
+    }
+
+    #[tokio::test]
+    async fn test_pcm_writes_each_delta() {
+        let mut writer = AudioStreamWriter::pcm(Vec::new());
+        let mime = Some("audio/L16;codec=pcm;rate=24000");
+
+        assert_eq!(
+            writer
+                .write_content(&audio(&[1, 2, 3], mime))
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            writer.write_content(&audio(&[4, 5], mime)).await.unwrap(),
+            2
+        );
+        let text = Content::Text {
+            text: Some("ignored".to_string()),
+            annotations: None,
+        };
+        assert_eq!(writer.write_content(&text).await.unwrap(), 0);
+
+        assert_eq!(writer.bytes_written(), 5);
+        assert_eq!(writer.format(), Some(PcmFormat::default()));
+        assert_eq!(writer.finish().await.unwrap(), [1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_base64_split_across_deltas() {
+        // "AQIDBAU=" is [1, 2, 3, 4, 5]
+        let mut writer = AudioStreamWriter::pcm(Vec::new());
+        assert_eq!(writer.write_content(&raw_audio("AQ")).await.unwrap(), 0);
+        assert_eq!(writer.write_content(&raw_audio("IDBA")).await.unwrap(), 3);
+        assert_eq!(writer.write_content(&raw_audio("U=")).await.unwrap(), 2);
+        assert_eq!(writer.finish().await.unwrap(), [1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_wav_header_patched_on_finish() {
+        let mut writer = AudioStreamWriter::wav(Cursor::new(Vec::new()));
+        let mime = Some("audio/L16;codec=pcm;rate=16000");
+        writer.write_content(&audio(&[0; 6], mime)).await.unwrap();
+        writer.write_content(&audio(&[1; 4], mime)).await.unwrap();
+
+        let wav = writer.finish().await.unwrap().into_inner();
+
+        assert_eq!(wav.len(), 44 + 10);
+        assert_eq!(
+            wav,
+            [
+                wav_header(
+                    PcmFormat {
+                        sample_rate: 16_000,
+                        channels: 1
+                    },
+                    10
+                ),
+                vec![0; 6],
+                vec![1; 4]
+            ]
+            .concat()
+        );
+        assert_eq!(&wav[4..8], &46u32.to_le_bytes());
+        assert_eq!(&wav[24..28], &16_000u32.to_le_bytes());
+        assert_eq!(&wav[40..44], &10u32.to_le_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_wav_without_audio_is_empty_wav() {
+        let writer = AudioStreamWriter::wav(Cursor::new(Vec::new()));
+        let wav = writer.finish().await.unwrap().into_inner();
+        assert_eq!(wav, wav_header(PcmFormat::default(), 0));
+    }
+
+    #[tokio::test]
+    async fn test_wav_rejects_non_pcm_audio() {
+        let mut writer = AudioStreamWriter::wav(Cursor::new(Vec::new()));
+        let result = writer.write_content(&audio(&[1], Some("audio/mpeg"))).await;
+        assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_format_change_mid_stream_is_rejected() {
+        let mut writer = AudioStreamWriter::pcm(Vec::new());
+        writer
+            .write_content(&audio(&[1, 2], Some("audio/L16;rate=24000")))
+            .await
+            .unwrap();
+        let result = writer
+            .write_content(&audio(&[1, 2], Some("audio/L16;rate=16000")))
+            .await;
+        assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,221 @@
+//! Cancelling background interactions that are abandoned early.
+//!
+//! A background interaction (`with_background(true)`) keeps running on the
+//! server, and consuming quota, after the client stops listening. A
+//! [`CancelGuard`] calls [`Client::cancel_interaction()`] when it is dropped
+//! while still armed, and [`CancelOnDrop`] wraps a stream from `create_stream()`
+//! or `get_interaction_stream()` in such a guard: it learns the interaction ID
+//! from [`StreamChunk::Start`](crate::StreamChunk::Start) (or any later event
+//! that carries it) and disarms once a terminal event arrives.
+//!
+//! Drop cannot be async, so the cancel request is spawned onto the current
+//! Tokio runtime. If the guard is dropped outside a runtime, the interaction is
+//! left running and a warning is logged.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use genai_rs::{CancelGuard, Client};
+//!
+//! # async fn example() -> Result<(), genai_rs::GenaiError> {
+//! let client = Client::new("your-api-key".to_string());
+//!
+//! let response = client
+//!     .interaction()
+//!     .with_agent("deep-research-pro-preview-12-2025")
+//!     .with_text("Research AI safety")
+//!     .with_background(true)
+//!     .create()
+//!     .await?;
+//! let mut guard = CancelGuard::new(&client, response.id.clone().unwrap_or_default());
+//!
+//! // If this returns early with `?`, the guard cancels the interaction
+//! poll_until_done(&client, &response).await?;
+//! guard.disarm();
+//! # Ok(())
+//! # }
+//! # async fn poll_until_done(_: &Client, _: &genai_rs::InteractionResponse) -> Result<(), genai_rs::GenaiError> { Ok(()) }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+use tracing::{debug, warn};
+
+use crate::{Client, GenaiError, InteractionResponse, StreamEvent};
+
+/// Cancels an interaction when dropped, unless disarmed first.
+///
+/// Holds a clone of the [`Client`], so the guard is `'static` and can outlive
+/// the borrow it was created from.
+#[must_use = "the interaction is cancelled as soon as the guard is dropped"]
+pub struct CancelGuard {
+    client: Client,
+    /// `None` while disarmed or before the interaction ID is known
+    interaction_id: Option<String>,
+}
+
+impl std::fmt::Debug for CancelGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancelGuard")
+            .field("interaction_id", &self.interaction_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CancelGuard {
+    /// Creates a guard armed for `interaction_id`.
+    pub fn new(client: &Client, interaction_id: impl Into<String>) -> Self {
+        Self {
+            client: client.clone(),
+            interaction_id: Some(interaction_id.into()),
+        }
+    }
+
+    /// Creates a guard that does nothing until [`arm()`](Self::arm) is called.
+    pub fn unarmed(client: &Client) -> Self {
+        Self {
+            client: client.clone(),
+            interaction_id: None,
+        }
+    }
+
+    /// Arms the guard for `interaction_id`, replacing any previous ID.
+    pub fn arm(&mut self, interaction_id: impl Into<String>) {
+        self.interaction_id = Some(interaction_id.into());
+    }
+
+    /// Disarms the guard, so that dropping it leaves the interaction running.
+    pub fn disarm(&mut self) {
+        self.interaction_id = None;
+    }
+
+    /// Returns the interaction ID the guard will cancel, if armed.
+    #[must_use]
+    pub fn interaction_id(&self) -> Option<&str> {
+        self.interaction_id.as_deref()
+    }
+
+    /// Returns `true` if dropping the guard would cancel an interaction.
+    #[must_use]
+    pub fn is_armed(&self) -> bool {
+        self.interaction_id.is_some()
+    }
+
+    /// Cancels the interaction now and waits for the result.
+    ///
+    /// Returns `Ok(None)` if the guard was not armed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cancel request fails.
+    pub async fn cancel(mut self) -> Result<Option<InteractionResponse>, GenaiError> {
+        let Some(interaction_id) = self.interaction_id.take() else {
+            return Ok(None);
+        };
+        self.client
+            .cancel_interaction(&interaction_id)
+            .await
+            .map(Some)
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        let Some(interaction_id) = self.interaction_id.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!(
+                "CancelGuard dropped outside a Tokio runtime; interaction {interaction_id} keeps running"
+            );
+            return;
+        };
+        let client = self.client.clone();
+        debug!("Cancelling abandoned interaction {interaction_id}");
+        runtime.spawn(async move {
+            if let Err(e) = client.cancel_interaction(&interaction_id).await {
+                warn!("Failed to cancel abandoned interaction {interaction_id}: {e}");
+            }
+        });
+    }
+}
+
+/// A stream that cancels its interaction if dropped before a terminal event.
+///
+/// The guard arms itself with the first interaction ID seen in the stream and
+/// disarms on `Complete` or `Error`. A stream that ends or fails without a
+/// terminal event stays armed, since the interaction may still be running.
+/// Use [`with_interaction_id()`](Self::with_interaction_id) to arm it up
+/// front, e.g. for a stream resumed with `get_interaction_stream()` that may
+/// not repeat the `Start` event.
+pub struct CancelOnDrop<'a> {
+    stream: BoxStream<'a, Result<StreamEvent, GenaiError>>,
+    guard: CancelGuard,
+    /// Set after a terminal event or `disarm()`; the guard no longer arms itself
+    settled: bool,
+}
+
+impl std::fmt::Debug for CancelOnDrop<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancelOnDrop")
+            .field("guard", &self.guard)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> CancelOnDrop<'a> {
+    /// Wraps `stream`, cancelling its interaction through `client` if the
+    /// stream is dropped early.
+    pub fn new<S>(client: &Client, stream: S) -> Self
+    where
+        S: Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a,
+    {
+        Self {
+            stream: stream.boxed(),
+            guard: CancelGuard::unarmed(client),
+            settled: false,
+        }
+    }
+
+    /// Arms the guard for `interaction_id` before any event arrives.
+    #[must_use]
+    pub fn with_interaction_id(mut self, interaction_id: impl Into<String>) -> Self {
+        self.guard.arm(interaction_id);
+        self
+    }
+
+    /// Keeps the interaction running when the stream is dropped.
+    pub fn disarm(&mut self) {
+        self.guard.disarm();
+        self.settled = true;
+    }
+
+    /// Returns the interaction ID that dropping the stream would cancel.
+    #[must_use]
+    pub fn interaction_id(&self) -> Option<&str> {
+        self.guard.interaction_id()
+    }
+}
+
+impl Stream for CancelOnDrop<'_> {
+    type Item = Result<StreamEvent, GenaiError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = std::task::ready!(self.stream.poll_next_unpin(cx));
+        if let Some(Ok(event)) = &item {
+            if event.is_terminal() {
+                self.disarm();
+            } else if !self.settled
+                && !self.guard.is_armed()
+                && let Some(interaction_id) = event.interaction_id()
+            {
+                let interaction_id = interaction_id.to_string();
+                self.guard.arm(interaction_id);
+            }
+        }
+        Poll::Ready(item)
+    }
+}
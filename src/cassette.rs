@@ -0,0 +1,667 @@
+//! Record/replay of HTTP traffic for deterministic offline tests.
+//!
+//! A [`Cassette`] attached with
+//! [`ClientBuilder::with_cassette()`](crate::ClientBuilder::with_cassette)
+//! sits underneath every request the client sends:
+//!
+//! - In [`Record`](CassetteMode::Record) mode, requests go to the network as
+//!   usual and each request/response pair (status, headers and the full body,
+//!   including SSE streams) is kept once it completes. The recording is written
+//!   to the cassette file by [`Cassette::save()`], or when the last clone of
+//!   the cassette is dropped.
+//! - In [`Replay`](CassetteMode::Replay) mode, nothing is sent. Each request is
+//!   answered with the bytes of the first unused recorded exchange with the
+//!   same method, path and body, so auto-function loops, stream resumption
+//!   and the Files API behave exactly as they did while recording.
+//!
+//! JSON request bodies are compared after normalization, so field order and
+//! equivalent `InteractionRequest` encodings do not cause misses. A request
+//! with no matching recording fails with [`GenaiError::Cassette`].
+//!
+//! Credentials are never written to the cassette: the `Authorization` and
+//! `X-Goog-Api-Key` request headers, every header configured with
+//! [`ClientBuilder::with_default_header()`](crate::ClientBuilder::with_default_header),
+//! and any header named with [`Cassette::with_redacted_header()`] (e.g., one
+//! added by middleware) are dropped when recording.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use genai_rs::{Cassette, Client};
+//!
+//! # async fn run() -> Result<(), genai_rs::GenaiError> {
+//! // Replays when the file exists; otherwise records against the live API.
+//! let cassette = Cassette::replay_or_record("tests/cassettes/hello.json")?;
+//! let api_key = std::env::var("GEMINI_API_KEY").unwrap_or_default();
+//! let client = Client::builder(api_key).with_cassette(cassette).build()?;
+//!
+//! let response = client
+//!     .interaction()
+//!     .with_model("gemini-3-flash-preview")
+//!     .with_text("Hello")
+//!     .create()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Limitations
+//!
+//! Streamed request bodies (chunked file uploads) are not recorded, so those
+//! requests are matched by method and path only. `ResumableUpload::query_offset()`
+//! and `ResumableUpload::resume()` take their own HTTP client and bypass the
+//! cassette.
+
+use crate::{GenaiError, InteractionRequest};
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Request, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Whether a [`Cassette`] records live traffic or replays a recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CassetteMode {
+    /// Send requests to the network and save each exchange to the file.
+    Record,
+    /// Serve recorded exchanges without touching the network.
+    Replay,
+}
+
+/// A file of recorded HTTP exchanges. See the [module documentation](self).
+///
+/// Clones share the same recording, so a test can keep a handle to check
+/// [`remaining()`](Self::remaining) after the client has been used.
+#[derive(Clone)]
+pub struct Cassette {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    exchanges: Vec<Exchange>,
+    /// Record mode: the response body is still being read.
+    /// Replay mode: the exchange has already been served.
+    flags: Vec<bool>,
+    /// Lowercase names of request headers that are never recorded.
+    redacted_headers: Vec<String>,
+    /// Record mode: exchanges completed since the file was last written.
+    unsaved: bool,
+}
+
+impl std::fmt::Debug for Cassette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cassette")
+            .field("path", &self.inner.path)
+            .field("mode", &self.inner.mode)
+            .field("exchanges", &self.lock().exchanges.len())
+            .finish()
+    }
+}
+
+impl Cassette {
+    /// Creates a cassette that records to `path`, replacing any existing file
+    /// when the recording is [saved](Self::save).
+    #[must_use]
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::with_state(path.into(), CassetteMode::Record, State::default())
+    }
+
+    /// Loads a recording from `path` for replay.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::Cassette`] if the file cannot be read or is not a
+    /// valid cassette.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, GenaiError> {
+        let path = path.into();
+        let file: CassetteFile = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_slice(&data).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                GenaiError::Cassette(format!("Failed to load '{}': {e}", path.display()))
+            })?;
+        let flags = vec![false; file.interactions.len()];
+        let state = State {
+            exchanges: file.interactions,
+            flags,
+            ..State::default()
+        };
+        Ok(Self::with_state(path, CassetteMode::Replay, state))
+    }
+
+    /// Replays `path` if it exists, and records to it otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::Cassette`] if the file exists but cannot be loaded.
+    pub fn replay_or_record(path: impl Into<PathBuf>) -> Result<Self, GenaiError> {
+        let path = path.into();
+        if path.exists() {
+            Self::replay(path)
+        } else {
+            Ok(Self::record(path))
+        }
+    }
+
+    fn with_state(path: PathBuf, mode: CassetteMode, state: State) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                path,
+                mode,
+                state: Mutex::new(state),
+            }),
+        }
+    }
+
+    /// Drops the request header `name` (case-insensitive) when recording.
+    ///
+    /// Headers set with
+    /// [`ClientBuilder::with_default_header()`](crate::ClientBuilder::with_default_header)
+    /// are always dropped; use this for secrets added elsewhere, such as by
+    /// [`Middleware`](crate::Middleware).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Cassette;
+    ///
+    /// let cassette = Cassette::record("tests/cassettes/gateway.json")
+    ///     .with_redacted_header("x-gateway-key");
+    /// ```
+    #[must_use]
+    pub fn with_redacted_header(self, name: impl Into<String>) -> Self {
+        self.lock()
+            .redacted_headers
+            .push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Returns the cassette file path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns whether this cassette records or replays.
+    #[must_use]
+    pub fn mode(&self) -> CassetteMode {
+        self.inner.mode
+    }
+
+    /// Returns the number of recorded exchanges that have not been replayed yet.
+    ///
+    /// Always `0` in record mode.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        match self.inner.mode {
+            CassetteMode::Record => 0,
+            CassetteMode::Replay => self.lock().flags.iter().filter(|used| !**used).count(),
+        }
+    }
+
+    /// Writes the exchanges recorded so far to the cassette file.
+    ///
+    /// This happens automatically when the last clone of the cassette (including
+    /// the one held by the client) is dropped; call it to read the file while
+    /// the client is still in use. Responses whose bodies are still being read
+    /// are left out. Does nothing in replay mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::Cassette`] if the file cannot be written.
+    pub fn save(&self) -> Result<(), GenaiError> {
+        if self.inner.mode == CassetteMode::Replay {
+            return Ok(());
+        }
+        let file = {
+            let mut state = self.lock();
+            state.unsaved = false;
+            state.completed()
+        };
+        self.inner.write(&file).map_err(|e| {
+            GenaiError::Cassette(format!(
+                "Failed to save '{}': {e}",
+                self.inner.path.display()
+            ))
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner.lock()
+    }
+
+    /// Sends `request` through the cassette: to the network when recording,
+    /// or to the matching recorded exchange when replaying. Headers named in
+    /// `redact` are not recorded.
+    pub(crate) async fn send(
+        &self,
+        request: RequestBuilder,
+        redact: &HeaderMap,
+    ) -> Result<Response, GenaiError> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let recorded = {
+            let state = self.lock();
+            RecordedRequest::from_request(&request, |name| {
+                redact.contains_key(name)
+                    || state
+                        .redacted_headers
+                        .iter()
+                        .any(|redacted| name.eq_ignore_ascii_case(redacted))
+            })
+        };
+
+        match self.inner.mode {
+            CassetteMode::Replay => self.replay_response(&recorded),
+            CassetteMode::Record => {
+                let response = client.execute(request).await?;
+                Ok(self.record_response(recorded, response))
+            }
+        }
+    }
+
+    fn replay_response(&self, request: &RecordedRequest) -> Result<Response, GenaiError> {
+        let mut state = self.lock();
+        let State {
+            exchanges, flags, ..
+        } = &mut *state;
+        let index = exchanges
+            .iter()
+            .zip(flags.iter())
+            .position(|(exchange, used)| !used && exchange.request.matches(request))
+            .ok_or_else(|| {
+                GenaiError::Cassette(format!(
+                    "No unused recording in '{}' matches {} {}",
+                    self.inner.path.display(),
+                    request.method,
+                    request.path
+                ))
+            })?;
+        flags[index] = true;
+        tracing::debug!(
+            "Replaying {} {} from cassette (exchange {index})",
+            request.method,
+            request.path
+        );
+        exchanges[index].response.to_response()
+    }
+
+    fn record_response(&self, request: RecordedRequest, response: Response) -> Response {
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        let slot = {
+            let mut state = self.lock();
+            state.exchanges.push(Exchange {
+                request,
+                response: RecordedResponse {
+                    status: status.as_u16(),
+                    headers: header_pairs(&headers),
+                    body: RecordedBody::Text(String::new()),
+                    error: None,
+                },
+            });
+            state.flags.push(true);
+            state.exchanges.len() - 1
+        };
+
+        // The body is teed as the caller reads it, so streams keep streaming
+        // while recording and the exchange is saved once the body ends (or is dropped).
+        let mut recorder = BodyRecorder {
+            cassette: self.clone(),
+            slot,
+            body: Vec::new(),
+            error: None,
+        };
+        let body = response.bytes_stream().map(move |chunk| {
+            match &chunk {
+                Ok(bytes) => recorder.body.extend_from_slice(bytes),
+                Err(e) => recorder.error = Some(e.to_string()),
+            }
+            chunk
+        });
+
+        build_response(status.as_u16(), &headers, reqwest::Body::wrap_stream(body))
+    }
+
+    fn finish_recording(&self, slot: usize, body: Vec<u8>, error: Option<String>) {
+        let mut state = self.lock();
+        let response = &mut state.exchanges[slot].response;
+        response.body = RecordedBody::from_bytes(&body);
+        response.error = error;
+        state.flags[slot] = false;
+        state.unsaved = true;
+    }
+}
+
+impl State {
+    /// The recorded exchanges whose response bodies have been fully read.
+    fn completed(&self) -> CassetteFile {
+        CassetteFile {
+            interactions: self
+                .exchanges
+                .iter()
+                .zip(&self.flags)
+                .filter(|(_, pending)| !**pending)
+                .map(|(exchange, _)| exchange.clone())
+                .collect(),
+        }
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn write(&self, file: &CassetteFile) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(file).map_err(std::io::Error::other)?;
+        std::fs::write(&self.path, json)
+    }
+}
+
+/// Saves a recording that has changed since the last [`Cassette::save()`].
+impl Drop for Inner {
+    fn drop(&mut self) {
+        let file = {
+            let state = self.lock();
+            if self.mode == CassetteMode::Replay || !state.unsaved {
+                return;
+            }
+            state.completed()
+        };
+        if let Err(e) = self.write(&file) {
+            tracing::warn!("Failed to save cassette '{}': {e}", self.path.display());
+        }
+    }
+}
+
+/// Accumulates a response body while it is read and records it when dropped.
+struct BodyRecorder {
+    cassette: Cassette,
+    slot: usize,
+    body: Vec<u8>,
+    error: Option<String>,
+}
+
+impl Drop for BodyRecorder {
+    fn drop(&mut self) {
+        self.cassette.finish_recording(
+            self.slot,
+            std::mem::take(&mut self.body),
+            self.error.take(),
+        );
+    }
+}
+
+// --- Cassette file format ---
+
+#[derive(Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Exchange>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Exchange {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    /// Path and query, without scheme and host, so recordings replay
+    /// regardless of the base URL.
+    path: String,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    /// `None` for streamed bodies, which cannot be captured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<RecordedBody>,
+}
+
+impl RecordedRequest {
+    fn from_request(request: &Request, redacted: impl Fn(&str) -> bool) -> Self {
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let headers = header_pairs(request.headers())
+            .into_iter()
+            .filter(|(name, _)| {
+                !name.eq_ignore_ascii_case(AUTHORIZATION.as_str())
+                    && !name.eq_ignore_ascii_case(crate::http::common::API_KEY_HEADER)
+                    && !redacted(name)
+            })
+            .collect();
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .map(RecordedBody::from_request_bytes);
+
+        Self {
+            method: request.method().to_string(),
+            path,
+            headers,
+            body,
+        }
+    }
+
+    /// Requests match on method, path and normalized body. A body that was
+    /// not captured on either side matches any body.
+    fn matches(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.path == other.path
+            && match (&self.body, &other.body) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: RecordedBody,
+    /// Transport error that ended the body early (e.g., a dropped SSE stream).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl RecordedResponse {
+    fn to_response(&self) -> Result<Response, GenaiError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| GenaiError::Cassette(format!("Invalid header '{name}': {e}")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| GenaiError::Cassette(format!("Invalid header value: {e}")))?;
+            headers.append(name, value);
+        }
+
+        let bytes = Bytes::from(self.body.to_bytes()?);
+        let body = match &self.error {
+            None => reqwest::Body::from(bytes),
+            Some(error) => {
+                let chunks: Vec<Result<Bytes, std::io::Error>> =
+                    vec![Ok(bytes), Err(std::io::Error::other(error.clone()))];
+                reqwest::Body::wrap_stream(futures_util::stream::iter(chunks))
+            }
+        };
+        Ok(build_response(self.status, &headers, body))
+    }
+}
+
+/// A recorded body. Request bodies that are JSON are stored normalized so
+/// they can be compared structurally; response bodies keep their exact bytes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedBody {
+    Json(Value),
+    Text(String),
+    Base64(String),
+}
+
+impl RecordedBody {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self::Text(text.to_string()),
+            Err(_) => Self::Base64(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        }
+    }
+
+    fn from_request_bytes(bytes: &[u8]) -> Self {
+        match serde_json::from_slice::<Value>(bytes) {
+            Ok(value) => Self::Json(normalize_json(value)),
+            Err(_) => Self::from_bytes(bytes),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, GenaiError> {
+        match self {
+            Self::Json(value) => Ok(serde_json::to_vec(value)?),
+            Self::Text(text) => Ok(text.clone().into_bytes()),
+            Self::Base64(data) => base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| GenaiError::Cassette(format!("Invalid base64 body: {e}"))),
+        }
+    }
+}
+
+/// Round-trips interaction requests through [`InteractionRequest`] so that
+/// equivalent encodings (e.g., an explicit `null` vs. an omitted field)
+/// compare equal. Other JSON bodies are compared as-is.
+fn normalize_json(value: Value) -> Value {
+    serde_json::from_value::<InteractionRequest>(value.clone())
+        .ok()
+        .and_then(|request| serde_json::to_value(request).ok())
+        .unwrap_or(value)
+}
+
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+fn build_response(status: u16, headers: &HeaderMap, body: reqwest::Body) -> Response {
+    let mut response = http::Response::new(body);
+    *response.status_mut() =
+        http::StatusCode::from_u16(status).unwrap_or(http::StatusCode::INTERNAL_SERVER_ERROR);
+    *response.headers_mut() = headers.clone();
+    Response::from(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, body: Option<&str>) -> RecordedRequest {
+        RecordedRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: Vec::new(),
+            body: body.map(|b| RecordedBody::from_request_bytes(b.as_bytes())),
+        }
+    }
+
+    #[test]
+    fn test_json_bodies_match_regardless_of_field_order() {
+        let a = request(
+            "POST",
+            "/v1beta/interactions",
+            Some(r#"{"a": 1, "b": [true]}"#),
+        );
+        let b = request(
+            "POST",
+            "/v1beta/interactions",
+            Some(r#"{"b":[true],"a":1}"#),
+        );
+        assert!(a.matches(&b));
+
+        let c = request(
+            "POST",
+            "/v1beta/interactions",
+            Some(r#"{"a": 2, "b": [true]}"#),
+        );
+        assert!(!a.matches(&c));
+        assert!(!a.matches(&request("GET", "/v1beta/interactions", None)));
+    }
+
+    #[test]
+    fn test_interaction_requests_are_normalized() {
+        let explicit_null = r#"{"model": "gemini-3-flash-preview", "input": "Hi", "tools": null}"#;
+        let omitted = r#"{"input": "Hi", "model": "gemini-3-flash-preview"}"#;
+        assert!(
+            request("POST", "/v1beta/interactions", Some(explicit_null)).matches(&request(
+                "POST",
+                "/v1beta/interactions",
+                Some(omitted)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_uncaptured_body_matches_any_body() {
+        let streamed = request("POST", "/upload/1", None);
+        assert!(streamed.matches(&request("POST", "/upload/1", Some("bytes"))));
+        assert!(request("POST", "/upload/1", Some("bytes")).matches(&streamed));
+    }
+
+    #[test]
+    fn test_body_encoding_round_trip() {
+        let binary = [0xff, 0x00, 0x10];
+        let body = RecordedBody::from_bytes(&binary);
+        assert!(matches!(body, RecordedBody::Base64(_)));
+        assert_eq!(body.to_bytes().unwrap(), binary);
+
+        let sse = "data: {\"x\":1}\n\n";
+        assert_eq!(
+            RecordedBody::from_bytes(sse.as_bytes()).to_bytes().unwrap(),
+            sse.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replayed_error_ends_body_after_recorded_bytes() {
+        let response = RecordedResponse {
+            status: 200,
+            headers: vec![("content-type".to_string(), "text/event-stream".to_string())],
+            body: RecordedBody::Text("data: partial\n\n".to_string()),
+            error: Some("connection reset".to_string()),
+        }
+        .to_response()
+        .unwrap();
+
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let chunks: Vec<_> = response.bytes_stream().collect().await;
+        assert_eq!(chunks[0].as_ref().unwrap().as_ref(), b"data: partial\n\n");
+        assert!(chunks[1].is_err());
+    }
+
+    #[test]
+    fn test_replay_missing_file_is_cassette_error() {
+        let result = Cassette::replay("/nonexistent/cassette.json");
+        assert!(matches!(result, Err(GenaiError::Cassette(_))));
+    }
+}
@@ -1,5 +1,14 @@
 use crate::GenaiError;
+use crate::cassette::Cassette;
+use crate::credentials::{Credential, CredentialProvider};
+use crate::http::common::{ApiVersion, Backend, DEFAULT_BASE_URL};
+use crate::http::transport::Transport;
+use crate::middleware::Middleware;
+use crate::rate_limit::RateLimiter;
+use crate::retry::{RetryPolicy, with_retry};
 use reqwest::Client as ReqwestClient;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Logs a request body at debug level, preferring JSON format when possible.
@@ -21,9 +30,12 @@ fn log_response_body<T: std::fmt::Debug + serde::Serialize>(body: &T) {
 /// The main client for interacting with the Google Generative AI API.
 #[derive(Clone)]
 pub struct Client {
-    pub(crate) api_key: String,
-    #[allow(clippy::struct_field_names)]
-    pub(crate) http_client: ReqwestClient,
+    /// HTTP client, base URL, backend, API version and credential provider
+    pub(crate) transport: Transport,
+    /// Default retry policy (None = no retries)
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    /// Client-side RPM/TPM limiter, shared across clones (None = unlimited)
+    pub(crate) rate_limiter: Option<RateLimiter>,
 }
 
 // Custom Debug implementation that redacts the API key for security.
@@ -31,8 +43,19 @@ pub struct Client {
 impl std::fmt::Debug for Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Client")
-            .field("api_key", &"[REDACTED]")
-            .field("http_client", &self.http_client)
+            .field("credentials", &self.transport.credentials)
+            .field("http_client", &self.transport.http_client)
+            .field("base_url", &self.transport.base_url)
+            .field("backend", &self.transport.backend)
+            .field("api_version", &self.transport.api_version)
+            .field(
+                "default_headers",
+                &self.transport.default_headers.keys().collect::<Vec<_>>(),
+            )
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("middleware", &self.transport.middleware)
+            .field("cassette", &self.transport.cassette)
             .finish()
     }
 }
@@ -52,18 +75,70 @@ impl std::fmt::Debug for Client {
 /// # Ok::<(), genai_rs::GenaiError>(())
 /// ```
 pub struct ClientBuilder {
-    api_key: String,
+    credentials: CredentialSource,
     timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    base_url: Option<String>,
+    backend: Backend,
+    api_version: ApiVersion,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    cassette: Option<Cassette>,
+    http_client: Option<ReqwestClient>,
+    proxies: Vec<reqwest::Proxy>,
+    root_certificates: Vec<reqwest::Certificate>,
+    default_headers: Vec<(String, String)>,
+    user_agent_suffix: Option<String>,
+}
+
+/// Headers that carry credentials and must come from the credential provider.
+const RESERVED_HEADERS: &[&str] = &["authorization", "x-goog-api-key"];
+
+/// Where a [`ClientBuilder`] gets its credentials from.
+enum CredentialSource {
+    /// A fixed secret whose kind (API key or bearer token) follows the backend
+    Static(String),
+    Provider(Arc<dyn CredentialProvider>),
+}
+
+// Custom Debug implementation that redacts the API key for security.
+impl std::fmt::Debug for CredentialSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static(_) => f.write_str("[REDACTED]"),
+            Self::Provider(provider) => provider.fmt(f),
+        }
+    }
 }
 
 // Custom Debug implementation that redacts the API key for security.
 impl std::fmt::Debug for ClientBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ClientBuilder")
-            .field("api_key", &"[REDACTED]")
+            .field("credentials", &self.credentials)
             .field("timeout", &self.timeout)
             .field("connect_timeout", &self.connect_timeout)
+            .field("base_url", &self.base_url)
+            .field("backend", &self.backend)
+            .field("api_version", &self.api_version)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("middleware", &self.middleware)
+            .field("cassette", &self.cassette)
+            .field("http_client", &self.http_client)
+            .field("proxies", &self.proxies.len())
+            .field("root_certificates", &self.root_certificates.len())
+            // Header values may carry tokens, so only the names are shown
+            .field(
+                "default_headers",
+                &self
+                    .default_headers
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("user_agent_suffix", &self.user_agent_suffix)
             .finish()
     }
 }
@@ -121,34 +196,468 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the base URL used for every API endpoint.
+    ///
+    /// Defaults to `https://generativelanguage.googleapis.com`. Override this to route
+    /// requests through an egress gateway, a regional proxy, or a local stand-in server.
+    /// The URL may include a path prefix (e.g., `https://proxy.example.com/gemini`);
+    /// endpoint paths such as `/v1beta/interactions` and `/upload/v1beta/files` are
+    /// appended to it. A trailing slash is ignored.
+    ///
+    /// The URL is validated when [`build()`](Self::build) is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Client;
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_base_url("http://localhost:8080")
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets the API backend.
+    ///
+    /// Defaults to [`Backend::GeminiApi`]. With [`Backend::Vertex`], endpoint URLs are
+    /// scoped to the project and location, the default base URL becomes the regional
+    /// Vertex AI host (e.g., `https://us-central1-aiplatform.googleapis.com`), and the
+    /// credential passed to [`Client::builder()`] is sent as an OAuth 2.0 bearer token.
+    /// A base URL set via [`with_base_url()`](Self::with_base_url) still takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{Backend, Client};
+    ///
+    /// let client = Client::builder("ya29.access-token".to_string())
+    ///     .with_backend(Backend::vertex("my-project", "us-central1"))
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Sets the API version used for every endpoint (interactions and files).
+    ///
+    /// Defaults to [`ApiVersion::V1Beta`]. Clients are independent, so one can
+    /// trial alpha features while another stays pinned to the stable API. See
+    /// [`ApiVersion`] for how versions map onto Vertex AI.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{ApiVersion, Client};
+    ///
+    /// let alpha = Client::builder("api_key".to_string())
+    ///     .with_api_version(ApiVersion::V1Alpha)
+    ///     .build()?;
+    /// let stable = Client::builder("api_key".to_string())
+    ///     .with_api_version(ApiVersion::V1)
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Sets the provider consulted for credentials on every request.
+    ///
+    /// Replaces the static key passed to [`Client::builder()`]. Use this for rotating
+    /// keys, short-lived OAuth tokens that are refreshed before expiry, or per-tenant
+    /// keys. See [`CredentialProvider`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{Client, Credential};
+    ///
+    /// // Send the secret as a bearer token instead of an API key
+    /// let client = Client::builder(String::new())
+    ///     .with_credential_provider(Credential::BearerToken("ya29.token".to_string()))
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_credential_provider(mut self, provider: impl CredentialProvider + 'static) -> Self {
+        self.credentials = CredentialSource::Provider(Arc::new(provider));
+        self
+    }
+
+    /// Sets the retry policy for transient failures.
+    ///
+    /// Applies to [`execute()`](Client::execute), [`get_interaction()`](Client::get_interaction),
+    /// Files API operations, and the initial connection of streaming requests.
+    /// Individual interactions can override it with
+    /// [`InteractionBuilder::with_retry_policy()`](crate::InteractionBuilder::with_retry_policy).
+    ///
+    /// If not set, requests are attempted once and errors are returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{Client, RetryPolicy};
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_retry_policy(RetryPolicy::new().with_max_attempts(5))
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Sets a client-side rate limiter for model calls.
+    ///
+    /// Each call to the Interactions API to create an interaction waits until its
+    /// model has request and token capacity. Pass clones of the same limiter to
+    /// several clients to have them share one budget. See [`RateLimiter`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{Client, RateLimit, RateLimiter};
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_rate_limiter(RateLimiter::new(RateLimit::new().with_requests_per_minute(60)))
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Adds a middleware that observes (and may modify) every interaction the client creates.
+    ///
+    /// Middleware run in the order they are added. See [`Middleware`] for the
+    /// available hooks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::{Client, InteractionResponse, Middleware};
+    ///
+    /// #[derive(Debug)]
+    /// struct UsageLogger;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl Middleware for UsageLogger {
+    ///     fn on_response(&self, response: &InteractionResponse) {
+    ///         println!("{:?} used {:?}", response.id, response.usage);
+    ///     }
+    /// }
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_middleware(UsageLogger)
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Records or replays all HTTP traffic with a [`Cassette`].
+    ///
+    /// In replay mode no request reaches the network, which lets tests that
+    /// exercise the live API (including streaming, auto-function loops and
+    /// the Files API) run offline and deterministically. See the
+    /// [`cassette`](crate::cassette) module for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use genai_rs::{Cassette, Client};
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_cassette(Cassette::replay_or_record("tests/cassettes/weather.json")?)
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Uses a preconfigured `reqwest::Client` for all HTTP traffic.
+    ///
+    /// Use this for connection pooling, TLS or DNS settings this builder does
+    /// not expose. Headers configured on the injected client are sent with
+    /// every request. Because the client is already built, it cannot be
+    /// combined with [`with_timeout()`](Self::with_timeout),
+    /// [`with_connect_timeout()`](Self::with_connect_timeout),
+    /// [`with_proxy()`](Self::with_proxy) or
+    /// [`with_root_certificate()`](Self::with_root_certificate); configure
+    /// those on the `reqwest::Client` instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Client;
+    /// use std::time::Duration;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .pool_idle_timeout(Duration::from_secs(30))
+    ///     .build()?;
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_http_client(http_client)
+    ///     .build()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_http_client(mut self, http_client: ReqwestClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Routes requests through an HTTP(S) proxy.
+    ///
+    /// May be called multiple times; reqwest picks the first proxy that
+    /// matches each request. When no proxy is set, reqwest honors the
+    /// standard `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Client;
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_proxy(reqwest::Proxy::https("http://proxy.corp.example:3128")?)
+    ///     .build()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trusts an additional root certificate, e.g. for a TLS-intercepting
+    /// corporate proxy.
+    ///
+    /// May be called multiple times. The built-in roots remain trusted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use genai_rs::Client;
+    ///
+    /// let pem = std::fs::read("corp-root-ca.pem")?;
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_root_certificate(reqwest::Certificate::from_pem(&pem)?)
+    ///     .build()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Adds a header sent with every request, such as `x-goog-user-project`
+    /// or a tracing header.
+    ///
+    /// Setting the same name twice keeps the last value. Credential headers
+    /// (`authorization`, `x-goog-api-key`) are rejected by
+    /// [`build()`](Self::build); use a
+    /// [`CredentialProvider`](crate::CredentialProvider) instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Client;
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_default_header("x-goog-user-project", "my-billing-project")
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_default_header(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Appends a product token to the `User-Agent` header, which becomes
+    /// `genai-rs/<version> <suffix>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use genai_rs::Client;
+    ///
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_user_agent_suffix("my-app/1.2.0")
+    ///     .build()?;
+    /// # Ok::<(), genai_rs::GenaiError>(())
+    /// ```
+    #[must_use]
+    pub fn with_user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
     /// Builds the `Client`.
     ///
     /// # Errors
     ///
-    /// Returns an error if the underlying HTTP client cannot be constructed. This should only
-    /// happen in exceptional circumstances such as TLS backend initialization failures.
+    /// Returns an error if:
+    /// - The base URL set via [`with_base_url()`](Self::with_base_url) is not a valid
+    ///   `http` or `https` URL, or contains a query string or fragment
+    /// - A [`Backend::Vertex`] project or location is empty or contains characters
+    ///   that are not valid in a resource path
+    /// - The API version is an empty or malformed [`ApiVersion::Custom`], or is
+    ///   not available on the selected backend
+    /// - A default header or the user agent suffix is not a valid header, or a
+    ///   default header would override credentials
+    /// - [`with_http_client()`](Self::with_http_client) is combined with
+    ///   timeouts, proxies or root certificates
+    /// - The underlying HTTP client cannot be constructed. This should only happen in
+    ///   exceptional circumstances such as TLS backend initialization failures.
     pub fn build(self) -> Result<Client, GenaiError> {
-        let mut builder = ReqwestClient::builder();
+        self.backend.validate()?;
+        self.api_version.validate(&self.backend)?;
+        let base_url = match self.base_url {
+            Some(url) => normalize_base_url(&url)?,
+            None => self.backend.default_base_url(),
+        };
+
+        let default_headers =
+            build_default_headers(&self.default_headers, self.user_agent_suffix.as_deref())?;
+
+        let http_client = match self.http_client {
+            Some(http_client) => {
+                if self.timeout.is_some()
+                    || self.connect_timeout.is_some()
+                    || !self.proxies.is_empty()
+                    || !self.root_certificates.is_empty()
+                {
+                    return Err(GenaiError::InvalidInput(
+                        "Timeouts, proxies and root certificates cannot be combined with \
+                         with_http_client(); configure them on the reqwest::Client instead"
+                            .to_string(),
+                    ));
+                }
+                http_client
+            }
+            None => {
+                let mut builder = ReqwestClient::builder();
 
-        if let Some(timeout) = self.timeout {
-            builder = builder.timeout(timeout);
-        }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
 
-        if let Some(connect_timeout) = self.connect_timeout {
-            builder = builder.connect_timeout(connect_timeout);
-        }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
 
-        let http_client = builder
-            .build()
-            .map_err(|e| GenaiError::ClientBuild(e.to_string()))?;
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                builder
+                    .build()
+                    .map_err(|e| GenaiError::ClientBuild(e.to_string()))?
+            }
+        };
+
+        let credentials: Arc<dyn CredentialProvider> = match self.credentials {
+            CredentialSource::Provider(provider) => provider,
+            CredentialSource::Static(secret) => Arc::new(match self.backend {
+                Backend::GeminiApi => Credential::ApiKey(secret),
+                Backend::Vertex { .. } => Credential::BearerToken(secret),
+            }),
+        };
 
         Ok(Client {
-            api_key: self.api_key,
-            http_client,
+            transport: Transport::new(http_client, base_url, self.backend, credentials)
+                .with_api_version(self.api_version)
+                .with_default_headers(default_headers)
+                .with_middleware(self.middleware)
+                .with_cassette(self.cassette),
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
         })
     }
 }
 
+/// Validates the configured default headers and user agent suffix.
+fn build_default_headers(
+    headers: &[(String, String)],
+    user_agent_suffix: Option<&str>,
+) -> Result<HeaderMap, GenaiError> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            GenaiError::InvalidInput(format!("Invalid default header name '{name}': {e}"))
+        })?;
+        if RESERVED_HEADERS.contains(&header_name.as_str()) {
+            return Err(GenaiError::InvalidInput(format!(
+                "Default header '{name}' would override credentials; \
+                 use Client::builder_with_credentials() with a CredentialProvider instead"
+            )));
+        }
+        let header_value = HeaderValue::from_str(value).map_err(|e| {
+            GenaiError::InvalidInput(format!("Invalid value for default header '{name}': {e}"))
+        })?;
+        map.insert(header_name, header_value);
+    }
+
+    if let Some(suffix) = user_agent_suffix {
+        let user_agent = format!("genai-rs/{} {}", env!("CARGO_PKG_VERSION"), suffix.trim());
+        let value = HeaderValue::from_str(&user_agent).map_err(|e| {
+            GenaiError::InvalidInput(format!("Invalid user agent suffix '{suffix}': {e}"))
+        })?;
+        map.insert(USER_AGENT, value);
+    }
+
+    Ok(map)
+}
+
+/// Validates a user-supplied base URL and strips any trailing slashes.
+fn normalize_base_url(url: &str) -> Result<String, GenaiError> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let parsed = reqwest::Url::parse(trimmed)
+        .map_err(|e| GenaiError::InvalidInput(format!("Invalid base URL '{url}': {e}")))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(GenaiError::InvalidInput(format!(
+            "Invalid base URL '{url}': scheme must be http or https"
+        )));
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(GenaiError::InvalidInput(format!(
+            "Invalid base URL '{url}': query strings and fragments are not supported"
+        )));
+    }
+
+    Ok(trimmed.to_string())
+}
+
 impl Client {
     /// Creates a new builder for `Client` instances.
     ///
@@ -157,10 +666,35 @@ impl Client {
     /// * `api_key` - Your Google AI API key.
     #[must_use]
     pub const fn builder(api_key: String) -> ClientBuilder {
+        Self::builder_from(CredentialSource::Static(api_key))
+    }
+
+    /// Creates a new builder whose requests are authenticated by `provider`.
+    ///
+    /// The provider is consulted before every request, including retries. See
+    /// [`CredentialProvider`] for an example.
+    #[must_use]
+    pub fn builder_with_credentials(provider: impl CredentialProvider + 'static) -> ClientBuilder {
+        Self::builder_from(CredentialSource::Provider(Arc::new(provider)))
+    }
+
+    const fn builder_from(credentials: CredentialSource) -> ClientBuilder {
         ClientBuilder {
-            api_key,
+            credentials,
             timeout: None,
             connect_timeout: None,
+            base_url: None,
+            backend: Backend::GeminiApi,
+            api_version: ApiVersion::V1Beta,
+            retry_policy: None,
+            rate_limiter: None,
+            middleware: Vec::new(),
+            cassette: None,
+            http_client: None,
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            default_headers: Vec::new(),
+            user_agent_suffix: None,
         }
     }
 
@@ -172,8 +706,14 @@ impl Client {
     #[must_use]
     pub fn new(api_key: String) -> Self {
         Self {
-            api_key,
-            http_client: ReqwestClient::new(),
+            transport: Transport::new(
+                ReqwestClient::new(),
+                DEFAULT_BASE_URL.to_string(),
+                Backend::GeminiApi,
+                Arc::new(Credential::ApiKey(api_key)),
+            ),
+            retry_policy: None,
+            rate_limiter: None,
         }
     }
 
@@ -312,45 +852,56 @@ impl Client {
     ///
     /// # Retry Example
     ///
+    /// Transient failures (429, 5xx, network errors) are retried automatically
+    /// when the client has a [`RetryPolicy`]:
+    ///
     /// ```no_run
-    /// use genai_rs::Client;
-    /// use std::time::Duration;
+    /// use genai_rs::{Client, RetryPolicy};
     ///
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let client = Client::new("api_key".to_string());
+    /// let client = Client::builder("api_key".to_string())
+    ///     .with_retry_policy(RetryPolicy::new().with_max_attempts(4))
+    ///     .build()?;
     /// let request = client.interaction()
     ///     .with_model("gemini-3-flash-preview")
     ///     .with_text("Hello!")
     ///     .build()?;
     ///
-    /// // Retry loop with exponential backoff
-    /// let mut attempts = 0;
-    /// let response = loop {
-    ///     match client.execute(request.clone()).await {
-    ///         Ok(r) => break r,
-    ///         Err(e) if e.is_retryable() && attempts < 3 => {
-    ///             attempts += 1;
-    ///             tokio::time::sleep(Duration::from_millis(100 * 2u64.pow(attempts))).await;
-    ///         }
-    ///         Err(e) => return Err(e.into()),
-    ///     }
-    /// };
+    /// let response = client.execute(request).await?;
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self), fields(model = ?request.model, agent = ?request.agent))]
     pub async fn execute(
         &self,
         request: crate::InteractionRequest,
+    ) -> Result<crate::InteractionResponse, GenaiError> {
+        self.execute_with_retry_policy(request, self.retry_policy.as_ref())
+            .await
+    }
+
+    /// Executes a request with an explicit retry policy (used for per-request overrides).
+    #[tracing::instrument(skip(self, retry_policy), fields(model = ?request.model, agent = ?request.agent))]
+    pub(crate) async fn execute_with_retry_policy(
+        &self,
+        request: crate::InteractionRequest,
+        retry_policy: Option<&RetryPolicy>,
     ) -> Result<crate::InteractionResponse, GenaiError> {
         tracing::debug!("Creating interaction");
         log_request_body(&request);
 
-        let response = crate::http::interactions::create_interaction(
-            &self.http_client,
-            &self.api_key,
-            request,
-        )
+        let response = with_retry(retry_policy, "create_interaction", || async {
+            let permit = match &self.rate_limiter {
+                Some(limiter) => Some(limiter.acquire(&request).await),
+                None => None,
+            };
+            let result =
+                crate::http::interactions::create_interaction(&self.transport, request.clone())
+                    .await;
+            if let Some(permit) = permit {
+                permit.settle(result.as_ref().ok().and_then(|r| r.usage.as_ref()));
+            }
+            result
+        })
         .await?;
 
         log_response_body(&response);
@@ -400,21 +951,58 @@ impl Client {
     /// # Ok(())
     /// # }
     /// ```
-    #[tracing::instrument(skip(self), fields(model = ?request.model, agent = ?request.agent))]
     pub fn execute_stream(
         &self,
         request: crate::InteractionRequest,
+    ) -> futures_util::stream::BoxStream<'_, Result<crate::StreamEvent, GenaiError>> {
+        self.execute_stream_with_retry_policy(request, self.retry_policy.clone(), None, None)
+    }
+
+    /// Executes a streaming request with an explicit retry policy for the initial
+    /// connection and, if `resume_policy` is set, automatic resumption after a
+    /// dropped connection. `stall_policy` enables stall detection.
+    #[tracing::instrument(skip(self, retry_policy, resume_policy, stall_policy), fields(model = ?request.model, agent = ?request.agent))]
+    pub(crate) fn execute_stream_with_retry_policy(
+        &self,
+        request: crate::InteractionRequest,
+        retry_policy: Option<RetryPolicy>,
+        resume_policy: Option<RetryPolicy>,
+        stall_policy: Option<crate::StallPolicy>,
     ) -> futures_util::stream::BoxStream<'_, Result<crate::StreamEvent, GenaiError>> {
         use futures_util::StreamExt;
 
         tracing::debug!("Creating streaming interaction");
         log_request_body(&request);
 
-        let stream = crate::http::interactions::create_interaction_stream(
-            &self.http_client,
-            &self.api_key,
-            request,
-        );
+        let rate_limiter = self.rate_limiter.clone();
+        let stream = async_stream::stream! {
+            let mut permit = match &rate_limiter {
+                Some(limiter) => Some(limiter.acquire(&request).await),
+                None => None,
+            };
+            let inner = crate::http::interactions::create_interaction_stream(
+                &self.transport,
+                request,
+                retry_policy,
+                resume_policy,
+                stall_policy,
+            );
+            futures_util::pin_mut!(inner);
+            while let Some(result) = inner.next().await {
+                match &result {
+                    Ok(event) => {
+                        self.transport.observe_stream_event(event);
+                        if let crate::StreamChunk::Complete(response) = &event.chunk
+                            && let Some(permit) = permit.take()
+                        {
+                            permit.settle(response.usage.as_ref());
+                        }
+                    }
+                    Err(e) => self.transport.observe_error(e),
+                }
+                yield result;
+            }
+        };
 
         stream
             .map(move |result| {
@@ -450,11 +1038,9 @@ impl Client {
     ) -> Result<crate::InteractionResponse, GenaiError> {
         tracing::debug!("Getting interaction: ID={interaction_id}");
 
-        let response = crate::http::interactions::get_interaction(
-            &self.http_client,
-            &self.api_key,
-            interaction_id,
-        )
+        let response = with_retry(self.retry_policy.as_ref(), "get_interaction", || {
+            crate::http::interactions::get_interaction(&self.transport, interaction_id)
+        })
         .await?;
 
         log_response_body(&response);
@@ -526,10 +1112,10 @@ impl Client {
         );
 
         let stream = crate::http::interactions::get_interaction_stream(
-            &self.http_client,
-            &self.api_key,
+            &self.transport,
             interaction_id,
             last_event_id,
+            self.retry_policy.clone(),
         );
 
         stream
@@ -562,12 +1148,7 @@ impl Client {
     pub async fn delete_interaction(&self, interaction_id: &str) -> Result<(), GenaiError> {
         tracing::debug!("Deleting interaction: ID={interaction_id}");
 
-        crate::http::interactions::delete_interaction(
-            &self.http_client,
-            &self.api_key,
-            interaction_id,
-        )
-        .await?;
+        crate::http::interactions::delete_interaction(&self.transport, interaction_id).await?;
 
         tracing::debug!("Interaction deleted successfully");
 
@@ -632,12 +1213,8 @@ impl Client {
     ) -> Result<crate::InteractionResponse, GenaiError> {
         tracing::debug!("Cancelling interaction: ID={interaction_id}");
 
-        let response = crate::http::interactions::cancel_interaction(
-            &self.http_client,
-            &self.api_key,
-            interaction_id,
-        )
-        .await?;
+        let response =
+            crate::http::interactions::cancel_interaction(&self.transport, interaction_id).await?;
 
         log_response_body(&response);
         tracing::debug!("Interaction cancelled: status={:?}", response.status);
@@ -725,14 +1302,8 @@ impl Client {
             mime_type
         );
 
-        crate::http::files::upload_file(
-            &self.http_client,
-            &self.api_key,
-            file_data,
-            mime_type,
-            display_name.as_deref(),
-        )
-        .await
+        self.upload_bytes(file_data, mime_type, display_name.as_deref())
+            .await
     }
 
     /// Uploads a file with an explicit MIME type.
@@ -780,14 +1351,8 @@ impl Client {
             mime_type
         );
 
-        crate::http::files::upload_file(
-            &self.http_client,
-            &self.api_key,
-            file_data,
-            mime_type,
-            display_name.as_deref(),
-        )
-        .await
+        self.upload_bytes(file_data, mime_type, display_name.as_deref())
+            .await
     }
 
     /// Uploads file bytes directly with a specified MIME type.
@@ -827,13 +1392,20 @@ impl Client {
             display_name
         );
 
-        crate::http::files::upload_file(
-            &self.http_client,
-            &self.api_key,
-            data,
-            mime_type,
-            display_name,
-        )
+        self.upload_bytes(data, mime_type, display_name).await
+    }
+
+    /// Uploads in-memory bytes, retrying the whole upload per the client's retry policy.
+    async fn upload_bytes(
+        &self,
+        data: Vec<u8>,
+        mime_type: &str,
+        display_name: Option<&str>,
+    ) -> Result<crate::FileMetadata, GenaiError> {
+        let data = bytes::Bytes::from(data);
+        with_retry(self.retry_policy.as_ref(), "upload_file", || {
+            crate::http::files::upload_file(&self.transport, data.clone(), mime_type, display_name)
+        })
         .await
     }
 
@@ -863,7 +1435,10 @@ impl Client {
     /// # }
     /// ```
     pub async fn get_file(&self, file_name: &str) -> Result<crate::FileMetadata, GenaiError> {
-        crate::http::files::get_file(&self.http_client, &self.api_key, file_name).await
+        with_retry(self.retry_policy.as_ref(), "get_file", || {
+            crate::http::files::get_file(&self.transport, file_name)
+        })
+        .await
     }
 
     /// Lists all uploaded files.
@@ -888,8 +1463,10 @@ impl Client {
         page_size: Option<u32>,
         page_token: Option<&str>,
     ) -> Result<crate::ListFilesResponse, GenaiError> {
-        crate::http::files::list_files(&self.http_client, &self.api_key, page_size, page_token)
-            .await
+        with_retry(self.retry_policy.as_ref(), "list_files", || {
+            crate::http::files::list_files(&self.transport, page_size, page_token)
+        })
+        .await
     }
 
     /// Deletes an uploaded file.
@@ -914,7 +1491,10 @@ impl Client {
     /// # }
     /// ```
     pub async fn delete_file(&self, file_name: &str) -> Result<(), GenaiError> {
-        crate::http::files::delete_file(&self.http_client, &self.api_key, file_name).await
+        with_retry(self.retry_policy.as_ref(), "delete_file", || {
+            crate::http::files::delete_file(&self.transport, file_name)
+        })
+        .await
     }
 
     /// Uploads a file using chunked transfer to minimize memory usage.
@@ -999,13 +1579,14 @@ impl Client {
             mime_type
         );
 
-        crate::http::files::upload_file_chunked(
-            &self.http_client,
-            &self.api_key,
-            path,
-            mime_type,
-            display_name.as_deref(),
-        )
+        with_retry(self.retry_policy.as_ref(), "upload_file_chunked", || {
+            crate::http::files::upload_file_chunked(
+                &self.transport,
+                path,
+                mime_type,
+                display_name.as_deref(),
+            )
+        })
         .await
     }
 
@@ -1051,13 +1632,14 @@ impl Client {
             mime_type
         );
 
-        crate::http::files::upload_file_chunked(
-            &self.http_client,
-            &self.api_key,
-            path,
-            mime_type,
-            display_name.as_deref(),
-        )
+        with_retry(self.retry_policy.as_ref(), "upload_file_chunked", || {
+            crate::http::files::upload_file_chunked(
+                &self.transport,
+                path,
+                mime_type,
+                display_name.as_deref(),
+            )
+        })
         .await
     }
 
@@ -1111,14 +1693,15 @@ impl Client {
             chunk_size
         );
 
-        crate::http::files::upload_file_chunked_with_chunk_size(
-            &self.http_client,
-            &self.api_key,
-            path,
-            mime_type,
-            display_name.as_deref(),
-            chunk_size,
-        )
+        with_retry(self.retry_policy.as_ref(), "upload_file_chunked", || {
+            crate::http::files::upload_file_chunked_with_chunk_size(
+                &self.transport,
+                path,
+                mime_type,
+                display_name.as_deref(),
+                chunk_size,
+            )
+        })
         .await
     }
 
@@ -1247,11 +1830,27 @@ impl Client {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures_util::FutureExt;
+
+    /// Resolves the client's credential; static credentials resolve immediately.
+    fn current_credential(client: &Client) -> Credential {
+        client
+            .transport
+            .credentials
+            .credential()
+            .now_or_never()
+            .expect("static credential resolves immediately")
+            .unwrap()
+    }
+
+    fn api_key(key: &str) -> Credential {
+        Credential::ApiKey(key.to_string())
+    }
 
     #[test]
     fn test_client_builder_default() {
         let client = Client::builder("test_key".to_string()).build().unwrap();
-        assert_eq!(client.api_key, "test_key");
+        assert_eq!(current_credential(&client), api_key("test_key"));
     }
 
     #[test]
@@ -1260,7 +1859,7 @@ mod tests {
             .with_timeout(Duration::from_secs(120))
             .build()
             .unwrap();
-        assert_eq!(client.api_key, "test_key");
+        assert_eq!(current_credential(&client), api_key("test_key"));
         // Note: We can't easily inspect the reqwest client's timeout,
         // but this test verifies the builder chain works
     }
@@ -1271,7 +1870,7 @@ mod tests {
             .with_connect_timeout(Duration::from_secs(10))
             .build()
             .unwrap();
-        assert_eq!(client.api_key, "test_key");
+        assert_eq!(current_credential(&client), api_key("test_key"));
     }
 
     #[test]
@@ -1281,13 +1880,236 @@ mod tests {
             .with_connect_timeout(Duration::from_secs(10))
             .build()
             .unwrap();
-        assert_eq!(client.api_key, "test_key");
+        assert_eq!(current_credential(&client), api_key("test_key"));
+    }
+
+    #[test]
+    fn test_client_builder_default_base_url() {
+        let client = Client::builder("test_key".to_string()).build().unwrap();
+        assert_eq!(client.transport.base_url, DEFAULT_BASE_URL);
+        assert_eq!(
+            Client::new("test_key".to_string()).transport.base_url,
+            DEFAULT_BASE_URL
+        );
+    }
+
+    #[test]
+    fn test_client_builder_with_base_url() {
+        let client = Client::builder("test_key".to_string())
+            .with_base_url("http://127.0.0.1:8080")
+            .build()
+            .unwrap();
+        assert_eq!(client.transport.base_url, "http://127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_client_builder_default_has_no_retry_policy() {
+        let client = Client::builder("test_key".to_string()).build().unwrap();
+        assert!(client.retry_policy.is_none());
+        assert!(Client::new("test_key".to_string()).retry_policy.is_none());
+    }
+
+    #[test]
+    fn test_client_builder_with_retry_policy() {
+        let policy = RetryPolicy::new().with_max_attempts(5);
+        let client = Client::builder("test_key".to_string())
+            .with_retry_policy(policy.clone())
+            .build()
+            .unwrap();
+        assert_eq!(client.retry_policy, Some(policy));
+    }
+
+    #[test]
+    fn test_client_builder_base_url_strips_trailing_slash() {
+        let client = Client::builder("test_key".to_string())
+            .with_base_url("https://proxy.example.com/gemini/")
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.transport.base_url,
+            "https://proxy.example.com/gemini"
+        );
+    }
+
+    #[test]
+    fn test_client_builder_rejects_invalid_base_url() {
+        for url in [
+            "not a url",
+            "ftp://example.com",
+            "https://example.com?key=abc",
+            "https://example.com#frag",
+        ] {
+            let result = Client::builder("test_key".to_string())
+                .with_base_url(url)
+                .build();
+            match result {
+                Err(GenaiError::InvalidInput(msg)) => {
+                    assert!(
+                        msg.contains("Invalid base URL"),
+                        "unexpected message: {msg}"
+                    );
+                }
+                other => panic!("Expected InvalidInput for '{url}', got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_client_builder_vertex_backend() {
+        let client = Client::builder("token".to_string())
+            .with_backend(Backend::vertex("my-project", "us-central1"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.transport.base_url,
+            "https://us-central1-aiplatform.googleapis.com"
+        );
+
+        // An explicit base URL wins over the backend default
+        let client = Client::builder("token".to_string())
+            .with_backend(Backend::vertex("my-project", "us-central1"))
+            .with_base_url("http://localhost:8080")
+            .build()
+            .unwrap();
+        assert_eq!(client.transport.base_url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_client_builder_static_key_follows_backend() {
+        let client = Client::builder("token".to_string())
+            .with_backend(Backend::vertex("my-project", "us-central1"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            current_credential(&client),
+            Credential::BearerToken("token".to_string())
+        );
+    }
+
+    #[test]
+    fn test_client_builder_with_credential_provider() {
+        let client = Client::builder("ignored".to_string())
+            .with_credential_provider(api_key("from-provider"))
+            .build()
+            .unwrap();
+        assert_eq!(current_credential(&client), api_key("from-provider"));
+
+        let client = Client::builder_with_credentials(api_key("from-provider"))
+            .with_backend(Backend::vertex("my-project", "us-central1"))
+            .build()
+            .unwrap();
+        // An explicit provider is not reinterpreted by the backend
+        assert_eq!(current_credential(&client), api_key("from-provider"));
+    }
+
+    #[test]
+    fn test_client_builder_rejects_invalid_vertex_backend() {
+        let result = Client::builder("token".to_string())
+            .with_backend(Backend::vertex("", "us-central1"))
+            .build();
+        assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_client_builder_api_version() {
+        let client = Client::builder("key".to_string()).build().unwrap();
+        assert_eq!(client.transport.api_version, ApiVersion::V1Beta);
+
+        let client = Client::builder("key".to_string())
+            .with_api_version(ApiVersion::V1Alpha)
+            .build()
+            .unwrap();
+        assert_eq!(client.transport.api_version, ApiVersion::V1Alpha);
+    }
+
+    #[test]
+    fn test_client_builder_rejects_invalid_api_version() {
+        for version in [ApiVersion::custom(""), ApiVersion::custom("v1/../v2")] {
+            let result = Client::builder("key".to_string())
+                .with_api_version(version)
+                .build();
+            assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+        }
+
+        let result = Client::builder("token".to_string())
+            .with_backend(Backend::vertex("my-project", "us-central1"))
+            .with_api_version(ApiVersion::V1Alpha)
+            .build();
+        assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_client_builder_default_headers() {
+        let client = Client::builder("key".to_string())
+            .with_default_header("X-Goog-User-Project", "billing")
+            .with_default_header("x-goog-user-project", "billing-2")
+            .with_user_agent_suffix("my-app/1.0")
+            .build()
+            .unwrap();
+        let headers = &client.transport.default_headers;
+        assert_eq!(headers["x-goog-user-project"], "billing-2");
+        assert_eq!(
+            headers[USER_AGENT],
+            format!("genai-rs/{} my-app/1.0", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_client_builder_rejects_invalid_default_headers() {
+        for (name, value) in [
+            ("bad header", "value"),
+            ("x-trace", "line\nbreak"),
+            ("Authorization", "Bearer token"),
+            ("x-goog-api-key", "key"),
+        ] {
+            let result = Client::builder("key".to_string())
+                .with_default_header(name, value)
+                .build();
+            assert!(
+                matches!(result, Err(GenaiError::InvalidInput(_))),
+                "{name} should be rejected"
+            );
+        }
+
+        let result = Client::builder("key".to_string())
+            .with_user_agent_suffix("app/1.0\r\nx-injected: yes")
+            .build();
+        assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_client_builder_http_client_conflicts() {
+        let conflicting = [
+            Client::builder("key".to_string()).with_timeout(Duration::from_secs(1)),
+            Client::builder("key".to_string()).with_connect_timeout(Duration::from_secs(1)),
+            Client::builder("key".to_string())
+                .with_proxy(reqwest::Proxy::all("http://127.0.0.1:3128").unwrap()),
+        ];
+        for builder in conflicting {
+            let result = builder.with_http_client(ReqwestClient::new()).build();
+            assert!(matches!(result, Err(GenaiError::InvalidInput(_))));
+        }
+
+        let client = Client::builder("key".to_string())
+            .with_http_client(ReqwestClient::new())
+            .with_default_header("x-goog-user-project", "billing")
+            .build();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_client_builder_debug_hides_header_values() {
+        let builder = Client::builder("key".to_string())
+            .with_default_header("x-trace-token", "secret-trace-value");
+        let debug_output = format!("{builder:?}");
+        assert!(debug_output.contains("x-trace-token"));
+        assert!(!debug_output.contains("secret-trace-value"));
     }
 
     #[test]
     fn test_client_new() {
         let client = Client::new("test_key".to_string());
-        assert_eq!(client.api_key, "test_key");
+        assert_eq!(current_credential(&client), api_key("test_key"));
     }
 
     #[test]
@@ -0,0 +1,137 @@
+//! Pluggable credentials for API authentication.
+//!
+//! Every request asks the client's [`CredentialProvider`] for a [`Credential`]
+//! just before it is sent (including each retry attempt), so providers can
+//! rotate keys from a secrets store, refresh short-lived OAuth tokens before
+//! they expire, or choose a key per tenant.
+//!
+//! [`Client::new()`](crate::Client::new) and [`Client::builder()`](crate::Client::builder)
+//! keep accepting a plain `String`, which becomes a static credential.
+//!
+//! # Example
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use genai_rs::{Client, Credential, CredentialProvider, GenaiError};
+//!
+//! #[derive(Debug)]
+//! struct EnvKey;
+//!
+//! #[async_trait]
+//! impl CredentialProvider for EnvKey {
+//!     async fn credential(&self) -> Result<Credential, GenaiError> {
+//!         std::env::var("GEMINI_API_KEY")
+//!             .map(Credential::ApiKey)
+//!             .map_err(|e| GenaiError::Credential(format!("GEMINI_API_KEY: {e}")))
+//!     }
+//! }
+//!
+//! let client = Client::builder_with_credentials(EnvKey).build()?;
+//! # Ok::<(), GenaiError>(())
+//! ```
+
+use crate::GenaiError;
+use crate::http::common::API_KEY_HEADER;
+use async_trait::async_trait;
+use reqwest::RequestBuilder;
+
+/// A credential attached to an outgoing request.
+///
+/// The `Debug` output never includes the secret.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Credential {
+    /// A Gemini API key, sent in the `X-Goog-Api-Key` header.
+    ApiKey(String),
+    /// An OAuth 2.0 access token, sent as `Authorization: Bearer <token>`.
+    ///
+    /// Required by Vertex AI; also accepted by the Gemini API.
+    BearerToken(String),
+}
+
+impl std::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ApiKey(_) => f.write_str("ApiKey([REDACTED])"),
+            Self::BearerToken(_) => f.write_str("BearerToken([REDACTED])"),
+        }
+    }
+}
+
+impl Credential {
+    /// Adds the authentication header for this credential to a request.
+    pub(crate) fn apply(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self {
+            Self::ApiKey(key) => builder.header(API_KEY_HEADER, key),
+            Self::BearerToken(token) => builder.bearer_auth(token),
+        }
+    }
+}
+
+/// Supplies the credential for each API request.
+///
+/// Implementations are called once per HTTP request, so they should be cheap
+/// in the common case (e.g., return a cached token and refresh it only when
+/// it is close to expiry). Returning an error aborts the request with that
+/// error; [`GenaiError::Credential`] is the conventional variant to use.
+///
+/// A [`Credential`] is itself a provider that always returns a clone of itself,
+/// and `Arc<P>` is a provider whenever `P` is, so one provider can be shared
+/// across several clients.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync + std::fmt::Debug {
+    /// Returns the credential to authenticate the next request with.
+    async fn credential(&self) -> Result<Credential, GenaiError>;
+}
+
+#[async_trait]
+impl CredentialProvider for Credential {
+    async fn credential(&self) -> Result<Credential, GenaiError> {
+        Ok(self.clone())
+    }
+}
+
+#[async_trait]
+impl<T: CredentialProvider + ?Sized> CredentialProvider for std::sync::Arc<T> {
+    async fn credential(&self) -> Result<Credential, GenaiError> {
+        (**self).credential().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_debug_redacts_secret() {
+        let debug = format!(
+            "{:?} {:?}",
+            Credential::ApiKey("key-123".to_string()),
+            Credential::BearerToken("ya29.token".to_string())
+        );
+        assert_eq!(debug, "ApiKey([REDACTED]) BearerToken([REDACTED])");
+    }
+
+    #[test]
+    fn test_credential_apply_headers() {
+        let client = reqwest::Client::new();
+
+        let request = Credential::ApiKey("key-123".to_string())
+            .apply(client.get("https://example.com"))
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()[API_KEY_HEADER], "key-123");
+
+        let request = Credential::BearerToken("ya29.token".to_string())
+            .apply(client.get("https://example.com"))
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer ya29.token");
+    }
+
+    #[tokio::test]
+    async fn test_static_credential_is_a_provider() {
+        let credential = Credential::BearerToken("ya29.token".to_string());
+        assert_eq!(credential.credential().await.unwrap(), credential);
+    }
+}
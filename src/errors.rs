@@ -74,6 +74,19 @@ pub enum GenaiError {
     /// TLS backend initialization failures.
     #[error("Failed to build HTTP client: {0}")]
     ClientBuild(String),
+    /// A [`CredentialProvider`](crate::CredentialProvider) failed to supply a credential.
+    ///
+    /// The request was not sent.
+    #[error("Failed to obtain credentials: {0}")]
+    Credential(String),
+    /// A [`Cassette`](crate::Cassette) could not be loaded or saved, or has no
+    /// recorded response for a request made in replay mode.
+    #[error("Cassette error: {0}")]
+    Cassette(String),
+    /// Writing to a caller-supplied sink, such as the one given to an
+    /// [`AudioStreamWriter`](crate::AudioStreamWriter), failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl GenaiError {
@@ -152,7 +165,10 @@ impl GenaiError {
             | GenaiError::Internal(_)
             | GenaiError::InvalidInput(_)
             | GenaiError::MalformedResponse(_)
-            | GenaiError::ClientBuild(_) => false,
+            | GenaiError::ClientBuild(_)
+            | GenaiError::Credential(_)
+            | GenaiError::Cassette(_)
+            | GenaiError::Io(_) => false,
         }
     }
 
@@ -374,6 +390,22 @@ mod tests {
         assert!(debug.contains("some error"));
     }
 
+    #[test]
+    fn test_genai_error_credential_display() {
+        let error = GenaiError::Credential("token refresh failed".to_string());
+        let display = format!("{}", error);
+        assert!(display.contains("Failed to obtain credentials"));
+        assert!(display.contains("token refresh failed"));
+    }
+
+    #[test]
+    fn test_genai_error_cassette_display() {
+        let error = GenaiError::Cassette("no recorded response for GET /v1beta/files".to_string());
+        let display = format!("{}", error);
+        assert!(display.contains("Cassette error"));
+        assert!(display.contains("GET /v1beta/files"));
+    }
+
     // =============================================================================
     // is_retryable() Tests
     // =============================================================================
@@ -483,6 +515,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_retryable_credential_not_retryable() {
+        let error = GenaiError::Credential("secret not found".to_string());
+        assert!(
+            !error.is_retryable(),
+            "Credential errors should NOT be retryable"
+        );
+    }
+
+    #[test]
+    fn test_is_retryable_cassette_not_retryable() {
+        let error = GenaiError::Cassette("no recorded response".to_string());
+        assert!(
+            !error.is_retryable(),
+            "Cassette errors should NOT be retryable"
+        );
+    }
+
+    #[test]
+    fn test_is_retryable_io_not_retryable() {
+        let error: GenaiError = std::io::Error::other("disk full").into();
+        assert!(!error.is_retryable(), "I/O errors should NOT be retryable");
+    }
+
     #[test]
     fn test_is_retryable_utf8_error_not_retryable() {
         let bytes = vec![0xff, 0xfe];
@@ -0,0 +1,177 @@
+//! Several consumers driving one shared stream.
+//!
+//! [`FanOut`] is the machinery behind
+//! [`split_text()`](crate::InteractionStreamExt::split_text) and
+//! [`SharedInteractionStream`](crate::SharedInteractionStream). The source is
+//! polled by whichever consumer needs the next item, so no task is spawned.
+//! Each item is handed to a [`Distribute`] implementation, which queues it for
+//! the consumers that should receive it.
+//!
+//! While a consumer polls the source, it installs a waker that wakes every
+//! waiting consumer: any of them may be the one to poll the source next, and
+//! an item it yields may be queued for any of them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use futures_util::task::{ArcWake, waker};
+
+/// Routes items from the shared source into per-consumer queues.
+pub(crate) trait Distribute {
+    /// An item of the source stream.
+    type Item;
+    /// An item as delivered to a consumer.
+    type Output;
+
+    /// Queues an item from the source for the consumers that should receive it.
+    fn distribute(&mut self, item: Self::Item);
+
+    /// Takes the next queued item for `consumer`.
+    fn take(&mut self, consumer: usize) -> Option<Self::Output>;
+}
+
+/// A stream shared by consumers identified by `usize` ids.
+pub(crate) struct FanOut<'a, D: Distribute> {
+    state: Mutex<FanOutState<'a, D>>,
+    wakers: Arc<ConsumerWakers>,
+}
+
+pub(crate) struct FanOutState<'a, D: Distribute> {
+    /// `None` once the source has ended
+    source: Option<BoxStream<'a, D::Item>>,
+    pub(crate) consumers: D,
+}
+
+impl<D: Distribute> FanOutState<'_, D> {
+    /// Returns `true` once the source has ended.
+    pub(crate) fn is_ended(&self) -> bool {
+        self.source.is_none()
+    }
+}
+
+impl<'a, D: Distribute> FanOut<'a, D> {
+    pub(crate) fn new(source: BoxStream<'a, D::Item>, queues: D) -> Self {
+        Self {
+            state: Mutex::new(FanOutState {
+                source: Some(source),
+                consumers: queues,
+            }),
+            wakers: Arc::new(ConsumerWakers::default()),
+        }
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, FanOutState<'a, D>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the next item for `consumer`, polling the source until one is
+    /// queued for it or the source ends.
+    pub(crate) fn poll_next(
+        &self,
+        consumer: usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<D::Output>> {
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = state.consumers.take(consumer) {
+                return Poll::Ready(Some(item));
+            }
+            let Some(source) = state.source.as_mut() else {
+                return Poll::Ready(None);
+            };
+
+            self.wakers.lock().insert(consumer, cx.waker().clone());
+            let shared_waker = waker(Arc::clone(&self.wakers));
+            match source.poll_next_unpin(&mut Context::from_waker(&shared_waker)) {
+                Poll::Pending => return Poll::Pending,
+                // Others waiting on the source must observe its end
+                Poll::Ready(None) => state.source = None,
+                // Others may have a new item queued
+                Poll::Ready(Some(item)) => state.consumers.distribute(item),
+            }
+            drop(state);
+            ArcWake::wake_by_ref(&self.wakers);
+            state = self.lock();
+        }
+    }
+
+    /// Forgets a dropped consumer. Call after removing its queue.
+    pub(crate) fn leave(&self, consumer: usize) {
+        self.wakers.lock().remove(&consumer);
+        // The others may be waiting for a poll this consumer would have made
+        ArcWake::wake_by_ref(&self.wakers);
+    }
+}
+
+/// The wakers of the consumers waiting on the source.
+#[derive(Default)]
+struct ConsumerWakers {
+    wakers: Mutex<HashMap<usize, Waker>>,
+}
+
+impl ConsumerWakers {
+    fn lock(&self) -> MutexGuard<'_, HashMap<usize, Waker>> {
+        self.wakers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ArcWake for ConsumerWakers {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let wakers: Vec<_> = arc_self.lock().drain().map(|(_, waker)| waker).collect();
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Routes even numbers to consumer 0 and odd numbers to consumer 1.
+    #[derive(Default)]
+    struct EvenOdd([VecDeque<u32>; 2]);
+
+    impl Distribute for EvenOdd {
+        type Item = u32;
+        type Output = u32;
+
+        fn distribute(&mut self, item: u32) {
+            self.0[(item % 2) as usize].push_back(item);
+        }
+
+        fn take(&mut self, consumer: usize) -> Option<u32> {
+            self.0[consumer].pop_front()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_item_for_waiting_consumer_wakes_it() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let fan_out = FanOut::new(
+            futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx)).boxed(),
+            EvenOdd::default(),
+        );
+        let consume = |consumer| {
+            let fan_out = &fan_out;
+            futures_util::stream::poll_fn(move |cx| fan_out.poll_next(consumer, cx))
+                .collect::<Vec<_>>()
+        };
+
+        let send = async {
+            for item in 1..=5 {
+                tx.send(item).unwrap();
+                tokio::task::yield_now().await;
+            }
+            drop(tx);
+        };
+        let ((), even, odd) = futures_util::join!(send, consume(0), consume(1));
+        assert_eq!(even, [2, 4]);
+        assert_eq!(odd, [1, 3, 5]);
+        assert!(fan_out.lock().is_ended());
+    }
+}
@@ -4,6 +4,8 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing::warn;
 
 use crate::FunctionDeclaration;
@@ -47,6 +49,31 @@ pub trait CallableFunction: Send + Sync {
     /// The arguments are provided as a serde_json::Value,
     /// and the function should return a serde_json::Value.
     async fn call(&self, args: Value) -> Result<Value, FunctionError>;
+
+    /// Returns how long one call may run during automatic function calling.
+    ///
+    /// Overrides the builder-wide
+    /// [`with_function_timeout()`](crate::InteractionBuilder::with_function_timeout).
+    /// `#[tool(timeout_secs = N)]` implements this. Default is `None`.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Executes the function with a token that is cancelled when the call
+    /// times out or the auto-function loop is dropped.
+    ///
+    /// The call's future is dropped at that point as well, so override this
+    /// only when the function starts work that outlives its future, such as
+    /// spawned tasks or blocking threads, and should stop it cooperatively.
+    /// The default ignores the token and delegates to [`call()`](Self::call).
+    async fn call_with_cancellation(
+        &self,
+        args: Value,
+        cancel: CancellationToken,
+    ) -> Result<Value, FunctionError> {
+        let _ = cancel;
+        self.call(args).await
+    }
 }
 
 /// A provider of callable functions with shared state/dependencies.
@@ -1,20 +1,178 @@
-/// Represents the API version to target.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The API version a [`Client`](crate::Client) targets.
+///
+/// Applies to every endpoint the client calls (interactions and files). The
+/// default is [`V1Beta`](Self::V1Beta), which is where the Interactions API
+/// currently lives. Set it per client with
+/// [`ClientBuilder::with_api_version()`](crate::ClientBuilder::with_api_version),
+/// e.g. to trial alpha features on one client while pinning another to GA.
+///
+/// On Vertex AI, `V1Beta` maps to Vertex's `v1beta1`; `V1Alpha` has no Vertex
+/// equivalent and is rejected by `ClientBuilder::build()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ApiVersion {
-    /// V1 Beta API version (current)
+    /// Stable API (`v1`)
+    V1,
+    /// Alpha API with early-access features (`v1alpha`)
+    V1Alpha,
+    /// Beta API (`v1beta`)
+    #[default]
     V1Beta,
+    /// Any other version path segment (e.g., `v1beta2`), used verbatim on
+    /// every backend.
+    Custom(String),
 }
 
 impl ApiVersion {
-    const fn as_str(self) -> &'static str {
+    /// Creates a custom version from a path segment.
+    #[must_use]
+    pub fn custom(version: impl Into<String>) -> Self {
+        Self::Custom(version.into())
+    }
+
+    /// Returns the version path segment on the Gemini API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
         match self {
+            Self::V1 => "v1",
+            Self::V1Alpha => "v1alpha",
             Self::V1Beta => "v1beta",
+            Self::Custom(version) => version,
         }
     }
+
+    /// Returns the equivalent version segment on Vertex AI, which uses
+    /// numbered beta versions.
+    fn vertex_as_str(&self) -> &str {
+        match self {
+            Self::V1Beta => "v1beta1",
+            other => other.as_str(),
+        }
+    }
+
+    /// Checks that the version can be used on `backend` and in a URL path.
+    pub(crate) fn validate(&self, backend: &Backend) -> Result<(), crate::GenaiError> {
+        if let Self::Custom(version) = self
+            && (version.is_empty()
+                || !version
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        {
+            return Err(crate::GenaiError::InvalidInput(format!(
+                "Invalid API version '{version}': must be non-empty and contain only \
+                 letters, digits, '-', '_' or '.'"
+            )));
+        }
+        if *self == Self::V1Alpha && matches!(backend, Backend::Vertex { .. }) {
+            return Err(crate::GenaiError::InvalidInput(
+                "API version v1alpha is not available on Vertex AI".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// --- Backends ---
+
+/// The API backend a [`Client`](crate::Client) talks to.
+///
+/// The backend determines how endpoint URLs are constructed and how requests
+/// are authenticated. Everything else (`InteractionBuilder`, streaming,
+/// auto-functions) works the same regardless of backend.
+///
+/// | Backend | Default host | Auth header |
+/// |---------|--------------|-------------|
+/// | `GeminiApi` | `generativelanguage.googleapis.com` | `X-Goog-Api-Key: <key>` |
+/// | `Vertex` | `{location}-aiplatform.googleapis.com` | `Authorization: Bearer <token>` |
+///
+/// # Example
+///
+/// ```
+/// use genai_rs::{Backend, Client};
+///
+/// // Vertex AI: the credential is an OAuth 2.0 access token
+/// // (e.g., from `gcloud auth print-access-token`)
+/// let client = Client::builder("ya29.access-token".to_string())
+///     .with_backend(Backend::vertex("my-project", "us-central1"))
+///     .build()?;
+/// # Ok::<(), genai_rs::GenaiError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The Gemini Developer API, authenticated with an API key.
+    #[default]
+    GeminiApi,
+    /// Vertex AI, scoped to a Google Cloud project and location and
+    /// authenticated with an OAuth 2.0 access token.
+    Vertex {
+        /// Google Cloud project ID
+        project: String,
+        /// Region (e.g., `us-central1`) or `global`
+        location: String,
+    },
+}
+
+impl Backend {
+    /// Creates a Vertex AI backend for the given project and location.
+    #[must_use]
+    pub fn vertex(project: impl Into<String>, location: impl Into<String>) -> Self {
+        Self::Vertex {
+            project: project.into(),
+            location: location.into(),
+        }
+    }
+
+    /// Returns the base URL used when none is set via `ClientBuilder::with_base_url()`.
+    pub(crate) fn default_base_url(&self) -> String {
+        match self {
+            Self::GeminiApi => DEFAULT_BASE_URL.to_string(),
+            Self::Vertex { location, .. } if location == "global" => {
+                "https://aiplatform.googleapis.com".to_string()
+            }
+            Self::Vertex { location, .. } => {
+                format!("https://{location}-aiplatform.googleapis.com")
+            }
+        }
+    }
+
+    /// Returns the path prefix every resource path is nested under.
+    fn scope(&self, version: &ApiVersion) -> String {
+        match self {
+            Self::GeminiApi => format!("/{}", version.as_str()),
+            Self::Vertex { project, location } => format!(
+                "/{}/projects/{project}/locations/{location}",
+                version.vertex_as_str()
+            ),
+        }
+    }
+
+    /// Checks that the backend's identifiers are usable in a URL path.
+    pub(crate) fn validate(&self) -> Result<(), crate::GenaiError> {
+        if let Self::Vertex { project, location } = self {
+            for (field, value) in [("project", project), ("location", location)] {
+                if value.is_empty()
+                    || !value
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    return Err(crate::GenaiError::InvalidInput(format!(
+                        "Invalid Vertex AI {field} '{value}': must be non-empty and contain \
+                         only letters, digits, '-' or '_'"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 // --- URL Construction ---
-const BASE_URL_PREFIX: &str = "https://generativelanguage.googleapis.com";
+
+/// Default base URL for the Gemini Developer API.
+///
+/// Override per client with `ClientBuilder::with_base_url()`.
+pub const DEFAULT_BASE_URL: &str = "https://generativelanguage.googleapis.com";
 
 /// Header name for API key authentication.
 ///
@@ -24,9 +182,8 @@ const BASE_URL_PREFIX: &str = "https://generativelanguage.googleapis.com";
 /// - Matches Google Cloud API best practices
 pub const API_KEY_HEADER: &str = "X-Goog-Api-Key";
 
-/// Represents different API endpoints for the Interactions API
+/// Represents different API endpoints for the Interactions and Files APIs
 #[derive(Debug, Clone, PartialEq, Eq)]
-#[allow(clippy::enum_variant_names)] // "Interaction" suffix is intentional for API clarity
 pub enum Endpoint<'a> {
     /// Create a new interaction
     CreateInteraction { stream: bool },
@@ -42,24 +199,34 @@ pub enum Endpoint<'a> {
     DeleteInteraction { id: &'a str },
     /// Cancel a background interaction by ID
     CancelInteraction { id: &'a str },
+    /// Start a resumable file upload session
+    UploadFile,
+    /// Retrieve file metadata by resource name (e.g., "files/abc123")
+    GetFile { name: &'a str },
+    /// List uploaded files
+    ListFiles {
+        /// Maximum number of files to return
+        page_size: Option<u32>,
+        /// Token for retrieving the next page of results
+        page_token: Option<&'a str>,
+    },
+    /// Delete a file by resource name (e.g., "files/abc123")
+    DeleteFile { name: &'a str },
 }
 
 impl Endpoint<'_> {
-    /// Constructs the URL path for this endpoint
-    fn to_path(&self, version: ApiVersion) -> String {
+    /// Constructs the URL path for this endpoint under the given scope
+    /// (e.g., `/v1beta` or `/v1beta1/projects/p/locations/l`)
+    fn to_path(&self, scope: &str) -> String {
         match self {
-            Self::CreateInteraction { .. } => {
-                format!("/{}/interactions", version.as_str())
-            }
-            Self::GetInteraction { id, .. } => {
-                format!("/{}/interactions/{}", version.as_str(), id)
-            }
-            Self::DeleteInteraction { id } => {
-                format!("/{}/interactions/{}", version.as_str(), id)
-            }
-            Self::CancelInteraction { id } => {
-                format!("/{}/interactions/{}/cancel", version.as_str(), id)
+            Self::CreateInteraction { .. } => format!("{scope}/interactions"),
+            Self::GetInteraction { id, .. } | Self::DeleteInteraction { id } => {
+                format!("{scope}/interactions/{id}")
             }
+            Self::CancelInteraction { id } => format!("{scope}/interactions/{id}/cancel"),
+            Self::UploadFile => format!("/upload{scope}/files"),
+            Self::GetFile { name } | Self::DeleteFile { name } => format!("{scope}/{name}"),
+            Self::ListFiles { .. } => format!("{scope}/files"),
         }
     }
 
@@ -68,7 +235,12 @@ impl Endpoint<'_> {
         match self {
             Self::CreateInteraction { stream } => *stream,
             Self::GetInteraction { stream, .. } => *stream,
-            Self::DeleteInteraction { .. } | Self::CancelInteraction { .. } => false,
+            Self::DeleteInteraction { .. }
+            | Self::CancelInteraction { .. }
+            | Self::UploadFile
+            | Self::GetFile { .. }
+            | Self::ListFiles { .. }
+            | Self::DeleteFile { .. } => false,
         }
     }
 
@@ -84,6 +256,19 @@ impl Endpoint<'_> {
                 last_event_id: Some(event_id),
                 ..
             } => Some(format!("last_event_id={}", urlencoding::encode(event_id))),
+            Self::ListFiles {
+                page_size,
+                page_token,
+            } => {
+                let mut params = Vec::new();
+                if let Some(size) = page_size {
+                    params.push(format!("pageSize={size}"));
+                }
+                if let Some(token) = page_token {
+                    params.push(format!("pageToken={}", urlencoding::encode(token)));
+                }
+                (!params.is_empty()).then(|| params.join("&"))
+            }
             _ => None,
         }
     }
@@ -91,12 +276,25 @@ impl Endpoint<'_> {
 
 /// Constructs a URL for a specific endpoint.
 ///
-/// Note: API key authentication is handled via the `X-Goog-Api-Key` header,
-/// not as a query parameter. Use [`API_KEY_HEADER`] when making requests.
+/// `base_url` is the scheme, host and optional path prefix that every endpoint
+/// path is appended to (e.g., [`DEFAULT_BASE_URL`] or a proxy such as
+/// `https://proxy.example.com/gemini`). It must not end with a trailing slash;
+/// `ClientBuilder::build()` normalizes this.
+///
+/// The `backend` and `version` determine how resource paths are scoped: with
+/// [`ApiVersion::V1Beta`] the Gemini API uses `/v1beta/...` while Vertex AI
+/// nests resources under `/v1beta1/projects/{project}/locations/{location}/...`.
+///
+/// Note: Authentication is handled via headers, not query parameters.
+/// See `Transport` for how each backend authenticates.
 #[must_use]
-pub fn construct_endpoint_url(endpoint: Endpoint) -> String {
-    let version = ApiVersion::V1Beta; // Default version for new function
-    let path = endpoint.to_path(version);
+pub fn construct_endpoint_url(
+    base_url: &str,
+    backend: &Backend,
+    version: &ApiVersion,
+    endpoint: Endpoint,
+) -> String {
+    let path = endpoint.to_path(&backend.scope(version));
 
     // Build query string from SSE requirement and additional params
     let mut query_parts = Vec::new();
@@ -113,7 +311,7 @@ pub fn construct_endpoint_url(endpoint: Endpoint) -> String {
         format!("?{}", query_parts.join("&"))
     };
 
-    format!("{BASE_URL_PREFIX}{path}{query_string}")
+    format!("{base_url}{path}{query_string}")
 }
 
 #[cfg(test)]
@@ -122,7 +320,66 @@ mod tests {
 
     #[test]
     fn test_api_version_as_str() {
+        assert_eq!(ApiVersion::V1.as_str(), "v1");
+        assert_eq!(ApiVersion::V1Alpha.as_str(), "v1alpha");
         assert_eq!(ApiVersion::V1Beta.as_str(), "v1beta");
+        assert_eq!(ApiVersion::custom("v2beta").as_str(), "v2beta");
+        assert_eq!(ApiVersion::default(), ApiVersion::V1Beta);
+    }
+
+    #[test]
+    fn test_endpoint_urls_follow_api_version() {
+        let url = |version: &ApiVersion, endpoint| {
+            construct_endpoint_url(DEFAULT_BASE_URL, &Backend::GeminiApi, version, endpoint)
+        };
+        assert_eq!(
+            url(
+                &ApiVersion::V1,
+                Endpoint::CreateInteraction { stream: true }
+            ),
+            "https://generativelanguage.googleapis.com/v1/interactions?alt=sse"
+        );
+        assert_eq!(
+            url(&ApiVersion::V1Alpha, Endpoint::UploadFile),
+            "https://generativelanguage.googleapis.com/upload/v1alpha/files"
+        );
+        assert_eq!(
+            url(
+                &ApiVersion::custom("v2beta"),
+                Endpoint::GetFile { name: "files/f1" }
+            ),
+            "https://generativelanguage.googleapis.com/v2beta/files/f1"
+        );
+
+        let vertex = Backend::vertex("p", "l");
+        assert_eq!(
+            construct_endpoint_url(
+                "https://l-aiplatform.googleapis.com",
+                &vertex,
+                &ApiVersion::V1,
+                Endpoint::CancelInteraction { id: "abc" }
+            ),
+            "https://l-aiplatform.googleapis.com/v1/projects/p/locations/l/interactions/abc/cancel"
+        );
+    }
+
+    #[test]
+    fn test_api_version_validate() {
+        let vertex = Backend::vertex("p", "l");
+        assert!(ApiVersion::V1Alpha.validate(&Backend::GeminiApi).is_ok());
+        assert!(ApiVersion::V1Alpha.validate(&vertex).is_err());
+        assert!(ApiVersion::V1.validate(&vertex).is_ok());
+        assert!(ApiVersion::custom("v1beta2").validate(&vertex).is_ok());
+        assert!(
+            ApiVersion::custom("")
+                .validate(&Backend::GeminiApi)
+                .is_err()
+        );
+        assert!(
+            ApiVersion::custom("v1?x=1")
+                .validate(&Backend::GeminiApi)
+                .is_err()
+        );
     }
 
     // --- Tests for Endpoint-based URL construction ---
@@ -130,7 +387,12 @@ mod tests {
     #[test]
     fn test_endpoint_create_interaction_non_streaming() {
         let endpoint = Endpoint::CreateInteraction { stream: false };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -143,7 +405,12 @@ mod tests {
     #[test]
     fn test_endpoint_create_interaction_streaming() {
         let endpoint = Endpoint::CreateInteraction { stream: true };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -160,7 +427,12 @@ mod tests {
             stream: false,
             last_event_id: None,
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -178,7 +450,12 @@ mod tests {
             stream: true,
             last_event_id: None,
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -195,7 +472,12 @@ mod tests {
             stream: true,
             last_event_id: Some("evt_abc123"),
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -213,7 +495,12 @@ mod tests {
             stream: true,
             last_event_id: Some("evt+abc&123=test"),
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         // + becomes %2B, & becomes %26, = becomes %3D
         assert!(url.contains("last_event_id=evt%2Babc%26123%3Dtest"));
@@ -228,7 +515,12 @@ mod tests {
             stream: false,
             last_event_id: Some("evt_should_be_ignored"),
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -246,7 +538,12 @@ mod tests {
         let endpoint = Endpoint::DeleteInteraction {
             id: "interaction-456",
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -288,7 +585,7 @@ mod tests {
     #[test]
     fn test_endpoint_to_path() {
         let endpoint = Endpoint::CreateInteraction { stream: false };
-        let path = endpoint.to_path(ApiVersion::V1Beta);
+        let path = endpoint.to_path("/v1beta");
         assert_eq!(path, "/v1beta/interactions");
     }
 
@@ -331,7 +628,12 @@ mod tests {
         let endpoint = Endpoint::CancelInteraction {
             id: "interaction-789",
         };
-        let url = construct_endpoint_url(endpoint);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint,
+        );
 
         assert_eq!(
             url,
@@ -346,4 +648,173 @@ mod tests {
     fn test_cancel_interaction_requires_sse() {
         assert!(!Endpoint::CancelInteraction { id: "test" }.requires_sse());
     }
+
+    #[test]
+    fn test_endpoint_custom_base_url() {
+        let url = construct_endpoint_url(
+            "http://127.0.0.1:8080",
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            Endpoint::CreateInteraction { stream: true },
+        );
+        assert_eq!(url, "http://127.0.0.1:8080/v1beta/interactions?alt=sse");
+    }
+
+    #[test]
+    fn test_endpoint_custom_base_url_with_path_prefix() {
+        let url = construct_endpoint_url(
+            "https://proxy.example.com/gemini",
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            Endpoint::GetInteraction {
+                id: "interaction-123",
+                stream: false,
+                last_event_id: None,
+            },
+        );
+        assert_eq!(
+            url,
+            "https://proxy.example.com/gemini/v1beta/interactions/interaction-123"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_file_urls() {
+        assert_eq!(
+            construct_endpoint_url(
+                DEFAULT_BASE_URL,
+                &Backend::GeminiApi,
+                &ApiVersion::V1Beta,
+                Endpoint::UploadFile
+            ),
+            "https://generativelanguage.googleapis.com/upload/v1beta/files"
+        );
+        assert_eq!(
+            construct_endpoint_url(
+                DEFAULT_BASE_URL,
+                &Backend::GeminiApi,
+                &ApiVersion::V1Beta,
+                Endpoint::GetFile { name: "files/abc" }
+            ),
+            "https://generativelanguage.googleapis.com/v1beta/files/abc"
+        );
+        assert_eq!(
+            construct_endpoint_url(
+                DEFAULT_BASE_URL,
+                &Backend::GeminiApi,
+                &ApiVersion::V1Beta,
+                Endpoint::DeleteFile { name: "files/abc" }
+            ),
+            "https://generativelanguage.googleapis.com/v1beta/files/abc"
+        );
+        assert_eq!(
+            construct_endpoint_url(
+                DEFAULT_BASE_URL,
+                &Backend::GeminiApi,
+                &ApiVersion::V1Beta,
+                Endpoint::ListFiles {
+                    page_size: None,
+                    page_token: None
+                }
+            ),
+            "https://generativelanguage.googleapis.com/v1beta/files"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_list_files_query_params() {
+        let url = construct_endpoint_url(
+            "http://localhost:9000",
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            Endpoint::ListFiles {
+                page_size: Some(10),
+                page_token: Some("next+page"),
+            },
+        );
+        assert_eq!(
+            url,
+            "http://localhost:9000/v1beta/files?pageSize=10&pageToken=next%2Bpage"
+        );
+
+        let url = construct_endpoint_url(
+            "http://localhost:9000",
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            Endpoint::ListFiles {
+                page_size: None,
+                page_token: Some("tok"),
+            },
+        );
+        assert_eq!(url, "http://localhost:9000/v1beta/files?pageToken=tok");
+    }
+
+    #[test]
+    fn test_vertex_endpoint_urls() {
+        let backend = Backend::vertex("my-project", "us-central1");
+        let base = backend.default_base_url();
+        assert_eq!(base, "https://us-central1-aiplatform.googleapis.com");
+
+        let scope = "https://us-central1-aiplatform.googleapis.com/v1beta1/projects/my-project/locations/us-central1";
+        assert_eq!(
+            construct_endpoint_url(
+                &base,
+                &backend,
+                &ApiVersion::V1Beta,
+                Endpoint::CreateInteraction { stream: true }
+            ),
+            format!("{scope}/interactions?alt=sse")
+        );
+        assert_eq!(
+            construct_endpoint_url(
+                &base,
+                &backend,
+                &ApiVersion::V1Beta,
+                Endpoint::CancelInteraction { id: "abc" }
+            ),
+            format!("{scope}/interactions/abc/cancel")
+        );
+        assert_eq!(
+            construct_endpoint_url(
+                &base,
+                &backend,
+                &ApiVersion::V1Beta,
+                Endpoint::GetFile { name: "files/f1" }
+            ),
+            format!("{scope}/files/f1")
+        );
+        assert_eq!(
+            construct_endpoint_url(&base, &backend, &ApiVersion::V1Beta, Endpoint::UploadFile),
+            "https://us-central1-aiplatform.googleapis.com/upload/v1beta1/projects/my-project/locations/us-central1/files"
+        );
+    }
+
+    #[test]
+    fn test_vertex_global_location_uses_global_host() {
+        assert_eq!(
+            Backend::vertex("p", "global").default_base_url(),
+            "https://aiplatform.googleapis.com"
+        );
+        assert_eq!(Backend::GeminiApi.default_base_url(), DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn test_backend_validate() {
+        assert!(Backend::GeminiApi.validate().is_ok());
+        assert!(
+            Backend::vertex("my-project", "europe-west4")
+                .validate()
+                .is_ok()
+        );
+
+        for (project, location) in [("", "us-central1"), ("p", ""), ("a/b", "l"), ("p", "l?x")] {
+            assert!(
+                matches!(
+                    Backend::vertex(project, location).validate(),
+                    Err(crate::GenaiError::InvalidInput(_))
+                ),
+                "expected InvalidInput for ({project:?}, {location:?})"
+            );
+        }
+    }
 }
@@ -49,10 +49,12 @@
 //! # }
 //! ```
 
-use super::common::API_KEY_HEADER;
+use super::common::Endpoint;
 use super::error_helpers::{check_response, deserialize_with_context};
 use super::loud_wire;
+use super::transport::Transport;
 use crate::errors::GenaiError;
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use reqwest::Client as ReqwestClient;
 use serde::{Deserialize, Serialize};
@@ -326,9 +328,6 @@ pub struct FileUploadResponse {
 
 // --- API Functions ---
 
-const BASE_URL: &str = "https://generativelanguage.googleapis.com";
-const UPLOAD_URL: &str = "https://generativelanguage.googleapis.com/upload/v1beta/files";
-const API_VERSION: &str = "v1beta";
 /// Maximum file size for uploads (2 GB)
 const MAX_FILE_SIZE: u64 = 2_147_483_648;
 
@@ -336,9 +335,8 @@ const MAX_FILE_SIZE: u64 = 2_147_483_648;
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
-/// * `file_data` - Raw bytes of the file
+/// * `transport` - Client, base URL, backend and credential to use
+/// * `file_data` - Raw bytes of the file (cheaply cloneable, so the upload can be retried)
 /// * `mime_type` - MIME type of the file
 /// * `display_name` - Optional display name for the file
 ///
@@ -346,9 +344,8 @@ const MAX_FILE_SIZE: u64 = 2_147_483_648;
 ///
 /// Returns an error if the upload fails or the response cannot be parsed.
 pub async fn upload_file(
-    http_client: &ReqwestClient,
-    api_key: &str,
-    file_data: Vec<u8>,
+    transport: &Transport,
+    file_data: Bytes,
     mime_type: &str,
     display_name: Option<&str>,
 ) -> Result<FileMetadata, GenaiError> {
@@ -394,17 +391,17 @@ pub async fn upload_file(
         serde_json::json!({ "file": {} })
     };
 
-    let start_response = http_client
-        .post(UPLOAD_URL)
-        .header(API_KEY_HEADER, api_key)
+    let upload_start_url = transport.url(Endpoint::UploadFile);
+    let request = transport
+        .post(&upload_start_url)
+        .await?
         .header("X-Goog-Upload-Protocol", "resumable")
         .header("X-Goog-Upload-Command", "start")
         .header("X-Goog-Upload-Header-Content-Length", file_size.to_string())
         .header("X-Goog-Upload-Header-Content-Type", mime_type)
         .header("Content-Type", "application/json")
-        .json(&metadata)
-        .send()
-        .await?;
+        .json(&metadata);
+    let start_response = transport.send(request).await?;
 
     let start_response = check_response(start_response).await?;
 
@@ -421,14 +418,13 @@ pub async fn upload_file(
     tracing::debug!("Got upload URL, uploading file data...");
 
     // Step 2: Upload the file bytes
-    let upload_response = http_client
-        .post(&upload_url)
+    let request = transport
+        .unauthenticated_post(&upload_url)
         .header("X-Goog-Upload-Offset", "0")
         .header("X-Goog-Upload-Command", "upload, finalize")
         .header("Content-Length", file_size.to_string())
-        .body(file_data)
-        .send()
-        .await?;
+        .body(file_data);
+    let upload_response = transport.send(request).await?;
 
     let upload_response = check_response(upload_response).await?;
     let response_text = upload_response.text().await.map_err(GenaiError::Http)?;
@@ -472,8 +468,7 @@ pub async fn upload_file(
 ///
 /// // Start a streaming upload
 /// let (file, upload) = upload_file_chunked(
-///     &http_client,
-///     "api-key",
+///     &transport,
 ///     "large_video.mp4",
 ///     "video/mp4",
 ///     Some("my-video"),
@@ -634,8 +629,7 @@ pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `path` - Path to the file to upload
 /// * `mime_type` - MIME type of the file
 /// * `display_name` - Optional display name for the file
@@ -665,12 +659,9 @@ pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
 /// use genai_rs::upload_file_chunked;
 ///
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-/// let http_client = reqwest::Client::new();
-///
 /// // Upload a large video file without loading it all into memory
 /// let (file, _upload_handle) = upload_file_chunked(
-///     &http_client,
-///     "api-key",
+///     &transport,
 ///     "large_video.mp4",
 ///     "video/mp4",
 ///     Some("my-video"),
@@ -681,15 +672,13 @@ pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024; // 8 MB
 /// # }
 /// ```
 pub async fn upload_file_chunked(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     path: impl AsRef<Path>,
     mime_type: &str,
     display_name: Option<&str>,
 ) -> Result<(FileMetadata, ResumableUpload), GenaiError> {
     upload_file_chunked_with_chunk_size(
-        http_client,
-        api_key,
+        transport,
         path,
         mime_type,
         display_name,
@@ -706,8 +695,7 @@ pub async fn upload_file_chunked(
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `path` - Path to the file to upload
 /// * `mime_type` - MIME type of the file
 /// * `display_name` - Optional display name for the file
@@ -717,8 +705,7 @@ pub async fn upload_file_chunked(
 ///
 /// Returns an error if the file cannot be read or the upload fails.
 pub async fn upload_file_chunked_with_chunk_size(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     path: impl AsRef<Path>,
     mime_type: &str,
     display_name: Option<&str>,
@@ -775,17 +762,17 @@ pub async fn upload_file_chunked_with_chunk_size(
         serde_json::json!({ "file": {} })
     };
 
-    let start_response = http_client
-        .post(UPLOAD_URL)
-        .header(API_KEY_HEADER, api_key)
+    let upload_start_url = transport.url(Endpoint::UploadFile);
+    let request = transport
+        .post(&upload_start_url)
+        .await?
         .header("X-Goog-Upload-Protocol", "resumable")
         .header("X-Goog-Upload-Command", "start")
         .header("X-Goog-Upload-Header-Content-Length", file_size.to_string())
         .header("X-Goog-Upload-Header-Content-Type", mime_type)
         .header("Content-Type", "application/json")
-        .json(&metadata_json)
-        .send()
-        .await?;
+        .json(&metadata_json);
+    let start_response = transport.send(request).await?;
 
     let start_response = check_response(start_response).await?;
 
@@ -819,14 +806,13 @@ pub async fn upload_file_chunked_with_chunk_size(
     let body = reqwest::Body::wrap_stream(stream);
 
     // Step 3: Upload the file bytes using streaming
-    let upload_response = http_client
-        .post(&upload_url)
+    let request = transport
+        .unauthenticated_post(&upload_url)
         .header("X-Goog-Upload-Offset", "0")
         .header("X-Goog-Upload-Command", "upload, finalize")
         .header("Content-Length", file_size.to_string())
-        .body(body)
-        .send()
-        .await?;
+        .body(body);
+    let upload_response = transport.send(request).await?;
 
     let upload_response = check_response(upload_response).await?;
     let response_text = upload_response.text().await.map_err(GenaiError::Http)?;
@@ -849,31 +835,23 @@ pub async fn upload_file_chunked_with_chunk_size(
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `file_name` - The resource name of the file (e.g., "files/abc123")
 ///
 /// # Errors
 ///
 /// Returns an error if the request fails or the file doesn't exist.
-pub async fn get_file(
-    http_client: &ReqwestClient,
-    api_key: &str,
-    file_name: &str,
-) -> Result<FileMetadata, GenaiError> {
+pub async fn get_file(transport: &Transport, file_name: &str) -> Result<FileMetadata, GenaiError> {
     tracing::debug!("Getting file metadata: {}", file_name);
 
-    let url = format!("{BASE_URL}/{API_VERSION}/{file_name}");
+    let url = transport.url(Endpoint::GetFile { name: file_name });
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "GET", &url, None);
 
-    let response = http_client
-        .get(&url)
-        .header(API_KEY_HEADER, api_key)
-        .send()
-        .await?;
+    let request = transport.get(&url).await?;
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -895,8 +873,7 @@ pub async fn get_file(
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `page_size` - Optional maximum number of files to return
 /// * `page_token` - Optional token for pagination
 ///
@@ -904,8 +881,7 @@ pub async fn get_file(
 ///
 /// Returns an error if the request fails.
 pub async fn list_files(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     page_size: Option<u32>,
     page_token: Option<&str>,
 ) -> Result<ListFilesResponse, GenaiError> {
@@ -915,28 +891,17 @@ pub async fn list_files(
         page_token
     );
 
-    let mut url = format!("{BASE_URL}/{API_VERSION}/files");
-
-    // Add query parameters
-    let mut has_params = false;
-    if let Some(size) = page_size {
-        url.push_str(&format!("?pageSize={size}"));
-        has_params = true;
-    }
-    if let Some(token) = page_token {
-        let separator = if has_params { "&" } else { "?" };
-        url.push_str(&format!("{separator}pageToken={token}"));
-    }
+    let url = transport.url(Endpoint::ListFiles {
+        page_size,
+        page_token,
+    });
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "GET", &url, None);
 
-    let response = http_client
-        .get(&url)
-        .header(API_KEY_HEADER, api_key)
-        .send()
-        .await?;
+    let request = transport.get(&url).await?;
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -959,31 +924,23 @@ pub async fn list_files(
 ///
 /// # Arguments
 ///
-/// * `http_client` - The HTTP client to use
-/// * `api_key` - API key for authentication
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `file_name` - The resource name of the file to delete (e.g., "files/abc123")
 ///
 /// # Errors
 ///
 /// Returns an error if the request fails or the file doesn't exist.
-pub async fn delete_file(
-    http_client: &ReqwestClient,
-    api_key: &str,
-    file_name: &str,
-) -> Result<(), GenaiError> {
+pub async fn delete_file(transport: &Transport, file_name: &str) -> Result<(), GenaiError> {
     tracing::debug!("Deleting file: {}", file_name);
 
-    let url = format!("{BASE_URL}/{API_VERSION}/{file_name}");
+    let url = transport.url(Endpoint::DeleteFile { name: file_name });
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "DELETE", &url, None);
 
-    let response = http_client
-        .delete(&url)
-        .header(API_KEY_HEADER, api_key)
-        .send()
-        .await?;
+    let request = transport.delete(&url).await?;
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -1,16 +1,20 @@
-use super::common::{API_KEY_HEADER, Endpoint, construct_endpoint_url};
+use super::common::Endpoint;
 use super::error_helpers::{check_response, deserialize_with_context};
 use super::loud_wire;
-use super::sse_parser::parse_sse_stream;
+use super::sse_parser::{SseMessage, parse_sse_stream};
+use super::transport::Transport;
 use crate::errors::GenaiError;
+use crate::retry::{RetryPolicy, with_retry};
+use crate::stall::{StallAction, StallPolicy};
 use crate::{
-    Content, InteractionRequest, InteractionResponse, InteractionStreamEvent, StreamChunk,
-    StreamEvent,
+    Content, InteractionRequest, InteractionResponse, InteractionStatus, InteractionStreamEvent,
+    StreamAccumulator, StreamChunk, StreamEvent,
 };
 use async_stream::try_stream;
 use futures_util::{Stream, StreamExt};
-use reqwest::Client as ReqwestClient;
-use std::collections::HashMap;
+use reqwest::Response;
+use std::collections::HashSet;
+use std::time::Duration;
 use tracing::{debug, warn};
 
 /// Creates a new interaction with the Gemini API.
@@ -25,12 +29,24 @@ use tracing::{debug, warn};
 /// - The response status is not successful
 /// - The response cannot be parsed as JSON
 pub async fn create_interaction(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     request: InteractionRequest,
+) -> Result<InteractionResponse, GenaiError> {
+    let result = send_create_interaction(transport, request).await;
+    match &result {
+        Ok(response) => transport.observe_response(response),
+        Err(e) => transport.observe_error(e),
+    }
+    result
+}
+
+async fn send_create_interaction(
+    transport: &Transport,
+    mut request: InteractionRequest,
 ) -> Result<InteractionResponse, GenaiError> {
     let endpoint = Endpoint::CreateInteraction { stream: false };
-    let url = construct_endpoint_url(endpoint);
+    let url = transport.url(endpoint);
+    let headers = transport.prepare_interaction(&mut request).await?;
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
@@ -43,15 +59,12 @@ pub async fn create_interaction(
     };
     loud_wire::log_request(request_id, "POST", &url, request_body.as_deref());
 
-    let response = http_client
-        .post(&url)
-        .header(API_KEY_HEADER, api_key)
-        .json(&request)
-        .send()
-        .await?;
+    let request = transport.post(&url).await?.headers(headers).json(&request);
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
+    transport.observe_response_head(&response);
 
     let response = check_response(response).await?;
     let response_text = response.text().await.map_err(GenaiError::Http)?;
@@ -84,7 +97,7 @@ pub async fn create_interaction(
 /// # Example
 /// ```ignore
 /// let mut last_event_id = None;
-/// let stream = create_interaction_stream(&client, &api_key, request);
+/// let stream = create_interaction_stream(&transport, request, None, None, None);
 /// while let Some(event) = stream.next().await {
 ///     let event = event?;
 ///     last_event_id = event.event_id.clone();  // Track for resume
@@ -107,195 +120,428 @@ pub async fn create_interaction(
 ///     }
 /// }
 /// ```
+///
+/// If `retry_policy` is set, only the initial connection is retried. Errors after
+/// the first event has been received are yielded to the caller, unless
+/// `resume_policy` is set: then a retryable error reconnects via the GET stream
+/// endpoint from the last received event, backing off per the policy, and the
+/// stream continues without repeating events already yielded.
+///
+/// If `stall_policy` is set, a connection that goes quiet for longer than the
+/// policy's idle threshold is probed, reconnected the same way, or ended with
+/// [`GenaiError::Timeout`], per the policy's [`StallAction`].
+///
+/// Middleware `on_request` and `on_response_head` hooks run here; the caller is
+/// responsible for `on_stream_event` and `on_error`.
 pub fn create_interaction_stream<'a>(
-    http_client: &'a ReqwestClient,
-    api_key: &'a str,
-    request: InteractionRequest,
+    transport: &'a Transport,
+    mut request: InteractionRequest,
+    retry_policy: Option<RetryPolicy>,
+    resume_policy: Option<RetryPolicy>,
+    stall_policy: Option<StallPolicy>,
 ) -> impl Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a {
     let endpoint = Endpoint::CreateInteraction { stream: true };
-    let url = construct_endpoint_url(endpoint);
-
-    // LOUD_WIRE: Log outgoing request (before try_stream! to capture request_id)
+    let url = transport.url(endpoint);
     let request_id = loud_wire::next_request_id();
-    let request_body = match serde_json::to_string(&request) {
-        Ok(body) => Some(body),
-        Err(e) => {
-            tracing::warn!("LOUD_WIRE: Failed to serialize request body: {}", e);
-            None
-        }
-    };
-    loud_wire::log_request(request_id, "POST (stream)", &url, request_body.as_deref());
-
-    try_stream! {
-        // Accumulate content from deltas to include in Complete response.
-        // In streaming, the API sends content via delta events, but the final
-        // interaction.complete event has empty outputs. We accumulate here so
-        // that response.function_calls() and response.as_text() work consistently.
-        //
-        // We track content by index because content can arrive in multiple chunks
-        // that need to be merged (e.g., text fragments, function call arguments).
-        let mut content_by_index: HashMap<usize, Content> = HashMap::new();
-
-        let response = http_client
-            .post(&url)
-            .header(API_KEY_HEADER, api_key)
-            .json(&request)
-            .send()
-            .await?;
-
-        // LOUD_WIRE: Log response status
-        loud_wire::log_response_status(request_id, response.status().as_u16());
-
-        let response = check_response(response).await?;
-        let byte_stream = response.bytes_stream();
-        let parsed_stream = parse_sse_stream::<InteractionStreamEvent>(byte_stream, request_id);
-        futures_util::pin_mut!(parsed_stream);
 
-        while let Some(result) = parsed_stream.next().await {
-            let event = result?;
-            debug!(
-                "SSE event received: event_type={:?}, has_delta={}, has_interaction={}, event_id={:?}",
-                event.event_type,
-                event.delta.is_some(),
-                event.interaction.is_some(),
-                event.event_id
-            );
+    with_accumulated_outputs(try_stream! {
+        let headers = transport.prepare_interaction(&mut request).await?;
 
-            // Extract event_id for the StreamEvent wrapper
-            let event_id = event.event_id.clone();
+        // LOUD_WIRE: Log outgoing request (after middleware may have modified it)
+        let request_body = match serde_json::to_string(&request) {
+            Ok(body) => Some(body),
+            Err(e) => {
+                tracing::warn!("LOUD_WIRE: Failed to serialize request body: {}", e);
+                None
+            }
+        };
+        loud_wire::log_request(request_id, "POST (stream)", &url, request_body.as_deref());
+
+        let mut response = with_retry(retry_policy.as_ref(), "create_interaction_stream", || async {
+            let request = transport
+                .post(&url)
+                .await?
+                .headers(headers.clone())
+                .json(&request);
+            let response = transport.send(request).await?;
+
+            // LOUD_WIRE: Log response status
+            loud_wire::log_response_status(request_id, response.status().as_u16());
+            transport.observe_response_head(&response);
+
+            check_response(response).await
+        })
+        .await?;
 
-            // Handle different event types from the Interactions API:
-            // - interaction.start: Initial event with interaction data (yields Start)
-            // - interaction.status_update: Status changes (yields StatusUpdate)
-            // - content.start: Content block begins (yields ContentStart)
-            // - content.delta: Incremental content updates (yields Delta)
-            // - content.stop: Content block ends (yields ContentStop)
-            // - interaction.complete: Final response (yields Complete)
-            // - error: Error occurred (yields Error)
-            match event.event_type.as_str() {
-                "interaction.start" => {
-                    // Interaction has started - provides early access to interaction ID
-                    if let Some(interaction) = event.interaction {
-                        yield StreamEvent::new(StreamChunk::Start { interaction }, event_id);
-                    } else {
-                        warn!("interaction.start event missing interaction field - event dropped");
-                    }
-                }
-                "interaction.status_update" => {
-                    // Status change during processing
-                    match (event.interaction_id, event.status) {
-                        (Some(interaction_id), Some(status)) => {
-                            yield StreamEvent::new(
-                                StreamChunk::StatusUpdate { interaction_id, status },
-                                event_id,
-                            );
+        // Position in the event sequence, used to resume after a disconnect
+        let mut resume = ResumeState::default();
+        let resumable = resume_policy.is_some() || stall_policy.is_some();
+        // Whether a content block is open, which selects the stall threshold
+        let mut in_content = false;
+
+        loop {
+            let byte_stream = response.bytes_stream();
+            let parsed_stream = parse_sse_stream::<InteractionStreamEvent>(byte_stream, request_id);
+            futures_util::pin_mut!(parsed_stream);
+            let mut disconnect = None;
+            let mut stalled = None;
+
+            loop {
+                let next = match &stall_policy {
+                    Some(stall) => {
+                        let idle = stall.idle_threshold(in_content);
+                        match tokio::time::timeout(idle, parsed_stream.next()).await {
+                            Ok(next) => next,
+                            Err(_) => {
+                                if stall.action() == StallAction::Probe
+                                    && probe_in_progress(transport, &resume).await
+                                {
+                                    debug!("Stream idle for {idle:?} but interaction is still in progress");
+                                    continue;
+                                }
+                                stalled = Some(idle);
+                                break;
+                            }
                         }
-                        (has_id, has_status) => {
-                            warn!(
-                                "interaction.status_update missing required fields: interaction_id={}, status={} - event dropped",
-                                has_id.is_some(),
-                                has_status.is_some()
-                            );
+                    }
+                    None => parsed_stream.next().await,
+                };
+                let Some(result) = next else {
+                    break;
+                };
+                let (event, sse_last_event_id) = match result {
+                    Ok(mut message) => {
+                        if message.retry.is_some() {
+                            resume.server_retry = message.retry;
                         }
+                        let last_event_id = message.last_event_id.take();
+                        (with_sse_fields(message), last_event_id)
+                    }
+                    Err(e) if resume_policy.is_some() && e.is_retryable() => {
+                        disconnect = Some(e);
+                        break;
                     }
+                    Err(e) => Err(e)?,
+                };
+                if resumable && !resume.record(&event, sse_last_event_id) {
+                    debug!("Skipping replayed SSE event: event_id={:?}", event.event_id);
+                    continue;
                 }
-                "content.start" => {
-                    // Content generation begins
-                    if let Some(index) = event.index {
-                        // Try to get content type from the content field if present
-                        let content_type = event.content.as_ref().and_then(|c| {
-                            // Get the content type name from the variant
-                            match c {
-                                Content::Text { .. } => Some("text".to_string()),
-                                Content::Thought { .. } => Some("thought".to_string()),
-                                Content::FunctionCall { .. } => Some("function_call".to_string()),
-                                Content::FunctionResult { .. } => Some("function_result".to_string()),
-                                Content::CodeExecutionCall { .. } => Some("code_execution_call".to_string()),
-                                Content::CodeExecutionResult { .. } => Some("code_execution_result".to_string()),
-                                Content::GoogleSearchCall { .. } => Some("google_search_call".to_string()),
-                                Content::GoogleSearchResult { .. } => Some("google_search_result".to_string()),
-                                Content::UrlContextCall { .. } => Some("url_context_call".to_string()),
-                                Content::UrlContextResult { .. } => Some("url_context_result".to_string()),
-                                Content::Unknown { content_type, .. } => Some(content_type.clone()),
-                                _ => None,
+                in_content = matches!(event.event_type.as_str(), "content.start" | "content.delta");
+                debug!(
+                    "SSE event received: event_type={:?}, has_delta={}, has_interaction={}, event_id={:?}",
+                    event.event_type,
+                    event.delta.is_some(),
+                    event.interaction.is_some(),
+                    event.event_id
+                );
+
+                // Extract event_id for the StreamEvent wrapper
+                let event_id = event.event_id.clone();
+
+                // Handle different event types from the Interactions API:
+                // - interaction.start: Initial event with interaction data (yields Start)
+                // - interaction.status_update: Status changes (yields StatusUpdate)
+                // - content.start: Content block begins (yields ContentStart)
+                // - content.delta: Incremental content updates (yields Delta)
+                // - content.stop: Content block ends (yields ContentStop)
+                // - interaction.complete: Final response (yields Complete)
+                // - error: Error occurred (yields Error)
+                match event.event_type.as_str() {
+                    "interaction.start" => {
+                        // Interaction has started - provides early access to interaction ID
+                        if let Some(interaction) = event.interaction {
+                            yield StreamEvent::new(StreamChunk::Start { interaction }, event_id);
+                        } else {
+                            warn!("interaction.start event missing interaction field - event dropped");
+                        }
+                    }
+                    "interaction.status_update" => {
+                        // Status change during processing
+                        match (event.interaction_id, event.status) {
+                            (Some(interaction_id), Some(status)) => {
+                                yield StreamEvent::new(
+                                    StreamChunk::StatusUpdate { interaction_id, status },
+                                    event_id,
+                                );
+                            }
+                            (has_id, has_status) => {
+                                warn!(
+                                    "interaction.status_update missing required fields: interaction_id={}, status={} - event dropped",
+                                    has_id.is_some(),
+                                    has_status.is_some()
+                                );
                             }
-                        });
-                        yield StreamEvent::new(StreamChunk::ContentStart { index, content_type }, event_id);
-                    } else {
-                        warn!("content.start event missing index field - event dropped");
+                        }
                     }
-                }
-                "content.delta" => {
-                    // Incremental content update
-                    if let Some(delta) = event.delta.clone() {
-                        // Accumulate content for the Complete response by merging
-                        // deltas at the same index. This ensures response.function_calls()
-                        // and response.as_text() work in streaming mode.
+                    "content.start" => {
+                        // Content generation begins
                         if let Some(index) = event.index {
-                            merge_content_at_index(&mut content_by_index, index, delta.clone());
+                            // Try to get content type from the content field if present
+                            let content_type = event.content.as_ref().and_then(|c| {
+                                // Get the content type name from the variant
+                                match c {
+                                    Content::Text { .. } => Some("text".to_string()),
+                                    Content::Thought { .. } => Some("thought".to_string()),
+                                    Content::FunctionCall { .. } => Some("function_call".to_string()),
+                                    Content::FunctionResult { .. } => Some("function_result".to_string()),
+                                    Content::CodeExecutionCall { .. } => Some("code_execution_call".to_string()),
+                                    Content::CodeExecutionResult { .. } => Some("code_execution_result".to_string()),
+                                    Content::GoogleSearchCall { .. } => Some("google_search_call".to_string()),
+                                    Content::GoogleSearchResult { .. } => Some("google_search_result".to_string()),
+                                    Content::UrlContextCall { .. } => Some("url_context_call".to_string()),
+                                    Content::UrlContextResult { .. } => Some("url_context_result".to_string()),
+                                    Content::Unknown { content_type, .. } => Some(content_type.clone()),
+                                    _ => None,
+                                }
+                            });
+                            yield StreamEvent::new(StreamChunk::ContentStart { index, content_type }, event_id);
+                        } else {
+                            warn!("content.start event missing index field - event dropped");
                         }
-                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id);
-                    } else {
-                        warn!("content.delta event missing delta field - event dropped");
                     }
-                }
-                "content.stop" => {
-                    // Content generation ends
-                    if let Some(index) = event.index {
-                        yield StreamEvent::new(StreamChunk::ContentStop { index }, event_id);
-                    } else {
-                        warn!("content.stop event missing index field - event dropped");
+                    "content.delta" => {
+                        // Incremental content update
+                        if let Some(delta) = event.delta {
+                            yield StreamEvent::new(StreamChunk::Delta(delta), event_id).with_index(event.index);
+                        } else {
+                            warn!("content.delta event missing delta field - event dropped");
+                        }
                     }
-                }
-                "interaction.complete" => {
-                    // Final complete response
-                    if let Some(mut interaction) = event.interaction {
-                        // Merge accumulated delta content into outputs.
-                        // The API's Complete event has empty outputs in streaming mode,
-                        // but users expect response.function_calls() and as_text() to work.
-                        if !content_by_index.is_empty() {
-                            // Sort by index to maintain order
-                            let mut outputs: Vec<_> = content_by_index.drain().collect();
-                            outputs.sort_by_key(|(idx, _)| *idx);
-                            interaction.outputs = outputs.into_iter().map(|(_, c)| c).collect();
+                    "content.stop" => {
+                        // Content generation ends
+                        if let Some(index) = event.index {
+                            yield StreamEvent::new(StreamChunk::ContentStop { index }, event_id);
+                        } else {
+                            warn!("content.stop event missing index field - event dropped");
                         }
-                        yield StreamEvent::new(StreamChunk::Complete(interaction), event_id);
-                    } else {
-                        warn!("interaction.complete event missing interaction field - event dropped");
                     }
-                }
-                "error" => {
-                    // Error occurred during streaming
-                    if let Some(error) = event.error {
-                        yield StreamEvent::new(
-                            StreamChunk::Error { message: error.message, code: error.code },
-                            event_id,
-                        );
-                    } else {
-                        // If no error object, treat as unknown error
-                        yield StreamEvent::new(
-                            StreamChunk::Error { message: "Unknown streaming error".to_string(), code: None },
-                            event_id,
-                        );
+                    "interaction.complete" => {
+                        // Final complete response
+                        // Outputs are filled in from the deltas by with_accumulated_outputs()
+                        if let Some(interaction) = event.interaction {
+                            yield StreamEvent::new(StreamChunk::Complete(interaction), event_id);
+                        } else {
+                            warn!("interaction.complete event missing interaction field - event dropped");
+                        }
+                    }
+                    "error" => {
+                        // Error occurred during streaming
+                        if let Some(error) = event.error {
+                            yield StreamEvent::new(
+                                StreamChunk::Error { message: error.message, code: error.code },
+                                event_id,
+                            );
+                        } else {
+                            // If no error object, treat as unknown error
+                            yield StreamEvent::new(
+                                StreamChunk::Error { message: "Unknown streaming error".to_string(), code: None },
+                                event_id,
+                            );
+                        }
+                    }
+                    _ => {
+                        // For unknown event types, only yield if they have delta content.
+                        // Do NOT yield Complete for other event types that happen to have
+                        // an interaction field - only interaction.complete is the final response.
+                        if let Some(delta) = event.delta {
+                            yield StreamEvent::new(StreamChunk::Delta(delta), event_id).with_index(event.index);
+                        } else if event.interaction.is_some() {
+                            // Warn about unknown event types with interaction fields - this could
+                            // indicate API version drift that needs attention
+                            warn!(
+                                "Unknown event type '{}' has interaction field but is not 'interaction.complete' - skipping",
+                                event.event_type
+                            );
+                        }
+                        // Skip events without useful content
                     }
                 }
-                _ => {
-                    // For unknown event types, only yield if they have delta content.
-                    // Do NOT yield Complete for other event types that happen to have
-                    // an interaction field - only interaction.complete is the final response.
-                    if let Some(delta) = event.delta {
-                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id);
-                    } else if event.interaction.is_some() {
-                        // Warn about unknown event types with interaction fields - this could
-                        // indicate API version drift that needs attention
-                        warn!(
-                            "Unknown event type '{}' has interaction field but is not 'interaction.complete' - skipping",
-                            event.event_type
-                        );
+            }
+
+            let (error, policy) = match (stalled, disconnect, &resume_policy) {
+                (Some(idle), _, _) => {
+                    if stall_policy.as_ref().map(StallPolicy::action) == Some(StallAction::Fail) {
+                        warn!("Stream stalled for {idle:?}");
+                        Err(GenaiError::Timeout(idle))?;
                     }
-                    // Skip events without useful content
+                    (GenaiError::Timeout(idle), resume_policy.clone().unwrap_or_default())
                 }
+                (None, Some(error), Some(policy)) => (error, policy.clone()),
+                _ => break,
+            };
+            response =
+                reconnect_interaction_stream(transport, &mut resume, error, &policy, request_id)
+                    .await?;
+        }
+    })
+}
+
+/// Feeds every event to a [`StreamAccumulator`] and fills the outputs of
+/// `Complete` from its snapshot.
+///
+/// In streaming, the API sends content via delta events, but the final
+/// `interaction.complete` event has empty outputs. Merging here, with the same
+/// rules as a caller's own accumulator, makes `response.function_calls()` and
+/// `response.as_text()` work and keeps the `Complete` response identical to
+/// `StreamAccumulator::snapshot()` over the same events.
+fn with_accumulated_outputs<'a>(
+    stream: impl Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a,
+) -> impl Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a {
+    let mut accumulator = StreamAccumulator::new();
+    stream.map(move |result| {
+        let mut event = result?;
+        accumulator.push(&event);
+        if let StreamChunk::Complete(interaction) = &mut event.chunk {
+            interaction.outputs = accumulator.snapshot().outputs;
+        }
+        Ok(event)
+    })
+}
+
+/// Fills in what the JSON payload omitted from the SSE framing: the SSE `id` as
+/// a fallback event ID and the SSE `event` name as a fallback event type.
+fn with_sse_fields(message: SseMessage<InteractionStreamEvent>) -> InteractionStreamEvent {
+    let mut event = message.data;
+    if event.event_id.is_none() {
+        event.event_id = message.id;
+    }
+    if event.event_type.is_empty()
+        && let Some(name) = message.event
+    {
+        event.event_type = name;
+    }
+    event
+}
+
+/// Tracks how far a stream got, so a dropped connection can resume from the
+/// last received event without repeating events already yielded.
+#[derive(Debug, Default)]
+struct ResumeState {
+    /// From `interaction.start` (or any later event that carries it)
+    interaction_id: Option<String>,
+    last_event_id: Option<String>,
+    seen_event_ids: HashSet<String>,
+    /// Events received so far
+    received: usize,
+    /// Replayed events to drop after resuming from the beginning
+    skip: usize,
+    /// Consecutive reconnect attempts without receiving a new event
+    attempt: u32,
+    /// Reconnection time requested by the server's SSE `retry` field
+    server_retry: Option<Duration>,
+}
+
+impl ResumeState {
+    /// Records a received event. Returns `false` if it is a replay of an event
+    /// that was already delivered before a reconnect.
+    ///
+    /// Only the event's own ID is checked for replays. Without one, the SSE
+    /// last event ID carried over from earlier events (`sse_last_event_id`)
+    /// is what a reconnect resumes from; an empty one means the server cleared it.
+    fn record(
+        &mut self,
+        event: &InteractionStreamEvent,
+        sse_last_event_id: Option<String>,
+    ) -> bool {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return false;
+        }
+        if let Some(event_id) = &event.event_id {
+            if !self.seen_event_ids.insert(event_id.clone()) {
+                return false;
             }
+            self.last_event_id = Some(event_id.clone());
+        } else if let Some(last_event_id) = sse_last_event_id {
+            self.last_event_id = Some(last_event_id).filter(|id| !id.is_empty());
+        }
+        if self.interaction_id.is_none() {
+            self.interaction_id = event
+                .interaction
+                .as_ref()
+                .and_then(|interaction| interaction.id.clone())
+                .or_else(|| event.interaction_id.clone());
+        }
+        self.received += 1;
+        self.attempt = 0;
+        true
+    }
+}
+
+/// Asks the server whether a quiet stream's interaction is still in progress.
+///
+/// Returns `false` if it has finished, the interaction ID is not yet known, or
+/// the probe fails, in which case the caller reconnects.
+async fn probe_in_progress(transport: &Transport, state: &ResumeState) -> bool {
+    let Some(interaction_id) = &state.interaction_id else {
+        return false;
+    };
+    match get_interaction(transport, interaction_id).await {
+        Ok(interaction) => interaction.status == InteractionStatus::InProgress,
+        Err(e) => {
+            warn!("Probing stalled interaction {interaction_id} failed: {e}");
+            false
+        }
+    }
+}
+
+/// Reopens a dropped stream via the GET stream endpoint, starting after the
+/// last received event and backing off between attempts per `policy`.
+///
+/// Returns the original error if the interaction ID is not yet known, or the
+/// latest error once the policy gives up.
+async fn reconnect_interaction_stream(
+    transport: &Transport,
+    state: &mut ResumeState,
+    mut error: GenaiError,
+    policy: &RetryPolicy,
+    request_id: usize,
+) -> Result<Response, GenaiError> {
+    let Some(interaction_id) = state.interaction_id.clone() else {
+        warn!("Stream dropped before the interaction ID was received; cannot resume: {error}");
+        return Err(error);
+    };
+    let url = transport.url(Endpoint::GetInteraction {
+        id: &interaction_id,
+        stream: true,
+        last_event_id: state.last_event_id.as_deref(),
+    });
+    // Without an event ID the server replays from the start
+    state.skip = if state.last_event_id.is_none() {
+        state.received
+    } else {
+        0
+    };
+
+    loop {
+        state.attempt += 1;
+        let Some(backoff) = policy.delay_before_retry(state.attempt, &error) else {
+            warn!(
+                "Giving up resuming interaction {interaction_id} after {} attempt(s): {error}",
+                state.attempt
+            );
+            return Err(error);
+        };
+        // The server's SSE `retry` time replaces the computed backoff
+        let delay = state.server_retry.unwrap_or(backoff);
+        warn!(
+            "Stream for interaction {interaction_id} dropped, resuming from {:?} in {delay:?}: {error}",
+            state.last_event_id
+        );
+        tokio::time::sleep(delay).await;
+
+        loud_wire::log_request(request_id, "GET (stream resume)", &url, None);
+        let result = async {
+            let request = transport.get(&url).await?;
+            let response = transport.send(request).await?;
+            loud_wire::log_response_status(request_id, response.status().as_u16());
+            check_response(response).await
+        }
+        .await;
+        match result {
+            Ok(response) => return Ok(response),
+            Err(e) => error = e,
         }
     }
 }
@@ -312,8 +558,7 @@ pub fn create_interaction_stream<'a>(
 /// - The response status is not successful
 /// - The response cannot be parsed as JSON
 pub async fn get_interaction(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     interaction_id: &str,
 ) -> Result<InteractionResponse, GenaiError> {
     let endpoint = Endpoint::GetInteraction {
@@ -321,17 +566,14 @@ pub async fn get_interaction(
         stream: false,
         last_event_id: None,
     };
-    let url = construct_endpoint_url(endpoint);
+    let url = transport.url(endpoint);
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "GET", &url, None);
 
-    let response = http_client
-        .get(&url)
-        .header(API_KEY_HEADER, api_key)
-        .send()
-        .await?;
+    let request = transport.get(&url).await?;
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -360,15 +602,15 @@ pub async fn get_interaction(
 ///
 /// # Arguments
 ///
-/// * `http_client` - The reqwest HTTP client
-/// * `api_key` - The Gemini API key
+/// * `transport` - Client, base URL, backend and credential to use
 /// * `interaction_id` - The ID of the interaction to stream
 /// * `last_event_id` - Optional event ID to resume from (for stream resumption)
+/// * `retry_policy` - Optional policy for retrying the initial connection
 ///
 /// # Example
 /// ```ignore
 /// // Resume a stream after interruption
-/// let mut stream = get_interaction_stream(&client, &api_key, &id, Some("evt_abc123"));
+/// let mut stream = get_interaction_stream(&transport, &id, Some("evt_abc123"), None);
 /// while let Some(event) = stream.next().await {
 ///     let event = event?;
 ///     println!("Received chunk: {:?}", event.chunk);
@@ -379,17 +621,17 @@ pub async fn get_interaction(
 /// }
 /// ```
 pub fn get_interaction_stream<'a>(
-    http_client: &'a ReqwestClient,
-    api_key: &'a str,
+    transport: &'a Transport,
     interaction_id: &'a str,
     last_event_id: Option<&'a str>,
+    retry_policy: Option<RetryPolicy>,
 ) -> impl Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a {
     let endpoint = Endpoint::GetInteraction {
         id: interaction_id,
         stream: true,
         last_event_id,
     };
-    let url = construct_endpoint_url(endpoint);
+    let url = transport.url(endpoint);
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
@@ -403,26 +645,25 @@ pub fn get_interaction_stream<'a>(
         None,
     );
 
-    try_stream! {
-        // Accumulate content by index (same as create_interaction_stream)
-        let mut content_by_index: HashMap<usize, Content> = HashMap::new();
+    with_accumulated_outputs(try_stream! {
+        let response = with_retry(retry_policy.as_ref(), "get_interaction_stream", || async {
+            let request = transport
+                .get(&url)
+                .await?;
+            let response = transport.send(request).await?;
 
-        let response = http_client
-            .get(&url)
-            .header(API_KEY_HEADER, api_key)
-            .send()
-            .await?;
+            // LOUD_WIRE: Log response status
+            loud_wire::log_response_status(request_id, response.status().as_u16());
 
-        // LOUD_WIRE: Log response status
-        loud_wire::log_response_status(request_id, response.status().as_u16());
-
-        let response = check_response(response).await?;
+            check_response(response).await
+        })
+        .await?;
         let byte_stream = response.bytes_stream();
         let parsed_stream = parse_sse_stream::<InteractionStreamEvent>(byte_stream, request_id);
         futures_util::pin_mut!(parsed_stream);
 
         while let Some(result) = parsed_stream.next().await {
-            let event = result?;
+            let event = with_sse_fields(result?);
             debug!(
                 "SSE event received: event_type={:?}, has_delta={}, has_interaction={}, event_id={:?}",
                 event.event_type,
@@ -437,20 +678,12 @@ pub fn get_interaction_stream<'a>(
             // Handle different event types (same logic as create_interaction_stream)
             match event.event_type.as_str() {
                 "content.delta" => {
-                    if let Some(delta) = event.delta.clone() {
-                        if let Some(index) = event.index {
-                            merge_content_at_index(&mut content_by_index, index, delta.clone());
-                        }
-                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id);
+                    if let Some(delta) = event.delta {
+                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id).with_index(event.index);
                     }
                 }
                 "interaction.complete" => {
-                    if let Some(mut interaction) = event.interaction {
-                        if !content_by_index.is_empty() {
-                            let mut outputs: Vec<_> = content_by_index.drain().collect();
-                            outputs.sort_by_key(|(idx, _)| *idx);
-                            interaction.outputs = outputs.into_iter().map(|(_, c)| c).collect();
-                        }
+                    if let Some(interaction) = event.interaction {
                         yield StreamEvent::new(StreamChunk::Complete(interaction), event_id);
                     }
                 }
@@ -459,7 +692,7 @@ pub fn get_interaction_stream<'a>(
                 }
                 _ => {
                     if let Some(delta) = event.delta {
-                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id);
+                        yield StreamEvent::new(StreamChunk::Delta(delta), event_id).with_index(event.index);
                     } else if event.interaction.is_some() {
                         warn!(
                             "Unknown event type '{}' has interaction field but is not 'interaction.complete' - skipping",
@@ -469,7 +702,7 @@ pub fn get_interaction_stream<'a>(
                 }
             }
         }
-    }
+    })
 }
 
 /// Deletes an interaction by its ID.
@@ -483,22 +716,18 @@ pub fn get_interaction_stream<'a>(
 /// - The HTTP request fails
 /// - The response status is not successful
 pub async fn delete_interaction(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     interaction_id: &str,
 ) -> Result<(), GenaiError> {
     let endpoint = Endpoint::DeleteInteraction { id: interaction_id };
-    let url = construct_endpoint_url(endpoint);
+    let url = transport.url(endpoint);
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "DELETE", &url, None);
 
-    let response = http_client
-        .delete(&url)
-        .header(API_KEY_HEADER, api_key)
-        .send()
-        .await?;
+    let request = transport.delete(&url).await?;
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -520,24 +749,19 @@ pub async fn delete_interaction(
 /// - The response cannot be parsed as JSON
 /// - The interaction is not in a cancellable state
 pub async fn cancel_interaction(
-    http_client: &ReqwestClient,
-    api_key: &str,
+    transport: &Transport,
     interaction_id: &str,
 ) -> Result<InteractionResponse, GenaiError> {
     let endpoint = Endpoint::CancelInteraction { id: interaction_id };
-    let url = construct_endpoint_url(endpoint);
+    let url = transport.url(endpoint);
 
     // LOUD_WIRE: Log outgoing request
     let request_id = loud_wire::next_request_id();
     loud_wire::log_request(request_id, "POST", &url, Some("{}"));
 
     // Send empty JSON body - the API requires Content-Length header
-    let response = http_client
-        .post(&url)
-        .header(API_KEY_HEADER, api_key)
-        .json(&serde_json::json!({}))
-        .send()
-        .await?;
+    let request = transport.post(&url).await?.json(&serde_json::json!({}));
+    let response = transport.send(request).await?;
 
     // LOUD_WIRE: Log response status
     loud_wire::log_response_status(request_id, response.status().as_u16());
@@ -554,70 +778,10 @@ pub async fn cancel_interaction(
     Ok(interaction_response)
 }
 
-/// Merges a delta Content into the accumulated content at a given index.
-///
-/// For text content, this concatenates the text. For function calls, arguments
-/// are merged as JSON string chunks. For other content types, the delta replaces
-/// any existing content.
-fn merge_content_at_index(content_map: &mut HashMap<usize, Content>, index: usize, delta: Content) {
-    match content_map.get_mut(&index) {
-        Some(existing) => {
-            // Merge based on content type
-            match (existing, &delta) {
-                // Text content: concatenate the text
-                (
-                    Content::Text {
-                        text: Some(existing_text),
-                        ..
-                    },
-                    Content::Text {
-                        text: Some(delta_text),
-                        ..
-                    },
-                ) => {
-                    existing_text.push_str(delta_text);
-                }
-                // FunctionCall: merge name and arguments as they stream in
-                (
-                    Content::FunctionCall {
-                        name: existing_name,
-                        args: existing_args,
-                        ..
-                    },
-                    Content::FunctionCall {
-                        name: delta_name,
-                        args: delta_args,
-                        ..
-                    },
-                ) => {
-                    // Name is a String, concatenate if delta has content
-                    if !delta_name.is_empty() {
-                        existing_name.push_str(delta_name);
-                    }
-                    // Args: if both are strings, concatenate the JSON string chunks
-                    if let (serde_json::Value::String(es), serde_json::Value::String(ds)) =
-                        (existing_args, delta_args)
-                    {
-                        es.push_str(ds);
-                    }
-                    // If delta args isn't a string, just keep existing
-                }
-                // For other types, just replace
-                _ => {
-                    content_map.insert(index, delta);
-                }
-            }
-        }
-        None => {
-            // No existing content at this index, just insert
-            content_map.insert(index, delta);
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::common::{ApiVersion, Backend, DEFAULT_BASE_URL, construct_endpoint_url};
     use crate::{Content, InteractionInput, InteractionStatus};
 
     #[test]
@@ -625,7 +789,12 @@ mod tests {
         // Test that we can construct proper URLs for each endpoint
         // API key is now passed via header, not in URL
         let endpoint_create = Endpoint::CreateInteraction { stream: false };
-        let url = construct_endpoint_url(endpoint_create);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint_create,
+        );
         assert!(url.contains("/v1beta/interactions"));
         assert!(!url.contains("key=")); // API key should not be in URL
 
@@ -634,17 +803,32 @@ mod tests {
             stream: false,
             last_event_id: None,
         };
-        let url = construct_endpoint_url(endpoint_get);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint_get,
+        );
         assert!(url.contains("/v1beta/interactions/test_id_123"));
         assert!(!url.contains("key=")); // API key should not be in URL
 
         let endpoint_delete = Endpoint::DeleteInteraction { id: "test_id_456" };
-        let url = construct_endpoint_url(endpoint_delete);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint_delete,
+        );
         assert!(url.contains("/v1beta/interactions/test_id_456"));
         assert!(!url.contains("key=")); // API key should not be in URL
 
         let endpoint_cancel = Endpoint::CancelInteraction { id: "test_id_789" };
-        let url = construct_endpoint_url(endpoint_cancel);
+        let url = construct_endpoint_url(
+            DEFAULT_BASE_URL,
+            &Backend::GeminiApi,
+            &ApiVersion::V1Beta,
+            endpoint_cancel,
+        );
         assert!(url.contains("/v1beta/interactions/test_id_789/cancel"));
         assert!(!url.contains("key=")); // API key should not be in URL
     }
@@ -9,3 +9,4 @@ pub(crate) mod files;
 pub(crate) mod interactions;
 pub(crate) mod loud_wire;
 pub(crate) mod sse_parser;
+pub(crate) mod transport;
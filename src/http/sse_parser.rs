@@ -1,7 +1,12 @@
-/// SSE (Server-Sent Events) parsing utilities
-///
-/// This module provides generic utilities for parsing SSE streams from the Gemini API.
-/// SSE format consists of lines starting with "data: " followed by JSON payloads.
+//! SSE (Server-Sent Events) parsing utilities
+//!
+//! Implements the event stream format from the WHATWG HTML specification:
+//! `data`, `event`, `id` and `retry` fields, comment lines, multi-line `data`
+//! payloads joined until the blank line that ends an event, and `\n`, `\r\n` or
+//! lone `\r` line endings, regardless of how the bytes are chunked. Like the
+//! `retry` reconnection time, the last event ID set by an `id` field persists
+//! across events until the server changes or clears it. Each event's data is
+//! then deserialized from JSON.
 use super::error_helpers::format_json_parse_error;
 use super::loud_wire;
 use crate::errors::GenaiError;
@@ -9,16 +14,189 @@ use async_stream::try_stream;
 use bytes::Bytes;
 use futures_util::{Stream, StreamExt};
 use serde::de::DeserializeOwned;
-use std::str;
+use std::time::Duration;
 use tracing::debug;
 
-/// Parses an SSE byte stream into a stream of deserialized objects.
+/// A dispatched SSE event, before its data is deserialized.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SseEvent {
+    /// The `event` field; `None` means the default `message` type
+    pub event: Option<String>,
+    /// The `data` lines of the event, joined with `\n`
+    pub data: String,
+    /// The `id` field, if this event set a non-empty one
+    pub id: Option<String>,
+    /// The last event ID buffer when this event was dispatched: the latest
+    /// `id` field from this or an earlier event, `Some("")` if the server
+    /// cleared it, or `None` if no `id` field has been seen
+    pub last_event_id: Option<String>,
+}
+
+/// Incremental decoder for the SSE wire format.
+///
+/// Bytes may be fed in chunks split at arbitrary positions, including between
+/// the `\r` and `\n` of a line ending.
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes of the current, incomplete line
+    buffer: Vec<u8>,
+    /// Prefix of `buffer` already scanned for a line ending
+    scanned: usize,
+    /// The previous chunk ended in `\r`; a leading `\n` completes that line ending
+    skip_lf: bool,
+    /// At least one line has been decoded (the first may start with a BOM)
+    started: bool,
+    /// The event being built
+    pending: SseEvent,
+    has_data: bool,
+    /// Reconnection time from the latest `retry` field
+    retry: Option<Duration>,
+    /// Value of the latest `id` field; an empty `id` clears it to `""`
+    last_event_id: Option<String>,
+}
+
+impl SseDecoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk of bytes, returning the events it completed.
+    ///
+    /// Lines are decoded as UTF-8, with invalid sequences replaced by U+FFFD
+    /// as the specification requires.
+    pub(crate) fn feed(&mut self, mut chunk: &[u8]) -> Vec<SseEvent> {
+        if self.skip_lf && !chunk.is_empty() {
+            if chunk[0] == b'\n' {
+                chunk = &chunk[1..];
+            }
+            self.skip_lf = false;
+        }
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        let mut start = 0;
+        let mut from = self.scanned;
+        while let Some(pos) = self.buffer[from..]
+            .iter()
+            .position(|&b| b == b'\n' || b == b'\r')
+        {
+            let end = from + pos;
+            let line = String::from_utf8_lossy(&self.buffer[start..end]).into_owned();
+            start = end + 1;
+            if self.buffer[end] == b'\r' {
+                if start == self.buffer.len() {
+                    self.skip_lf = true;
+                } else if self.buffer[start] == b'\n' {
+                    start += 1;
+                }
+            }
+            from = start;
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+        }
+        self.buffer.drain(..start);
+        self.scanned = self.buffer.len();
+        events
+    }
+
+    /// Ends the stream, returning an event whose terminating blank line never
+    /// arrived.
+    ///
+    /// The specification discards such an event; it is kept here so that a
+    /// server omitting the final blank line does not lose its last event. An
+    /// unterminated final line is still discarded.
+    pub(crate) fn finish(&mut self) -> Option<SseEvent> {
+        self.buffer.clear();
+        self.scanned = 0;
+        self.dispatch()
+    }
+
+    /// The reconnection time from the latest `retry` field.
+    pub(crate) fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        let line = if self.started {
+            line
+        } else {
+            self.started = true;
+            line.strip_prefix('\u{feff}').unwrap_or(line)
+        };
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // Comment, e.g. a keep-alive
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                if self.has_data {
+                    self.pending.data.push('\n');
+                }
+                self.pending.data.push_str(value);
+                self.has_data = true;
+            }
+            "event" => self.pending.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => {
+                self.pending.id = (!value.is_empty()).then(|| value.to_string());
+                self.last_event_id = Some(value.to_string());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => {
+                // Unknown fields are ignored
+            }
+        }
+        None
+    }
+
+    /// Completes the pending event. Events without data are dropped.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.pending);
+        std::mem::take(&mut self.has_data).then(|| SseEvent {
+            event: event.event.filter(|name| !name.is_empty()),
+            last_event_id: self.last_event_id.clone(),
+            ..event
+        })
+    }
+}
+
+/// An SSE event whose data has been deserialized.
+#[derive(Debug, Clone)]
+pub(crate) struct SseMessage<T> {
+    pub data: T,
+    /// The SSE `event` field, if set
+    pub event: Option<String>,
+    /// The SSE `id` field, if this event set one
+    pub id: Option<String>,
+    /// The SSE last event ID after this event, carried over from earlier
+    /// events if this one set no `id`; `Some("")` if the server cleared it
+    pub last_event_id: Option<String>,
+    /// The server's requested reconnection time, from the latest `retry` field
+    pub retry: Option<Duration>,
+}
+
+/// Parses an SSE byte stream into a stream of deserialized messages.
 ///
 /// This function handles the low-level SSE protocol parsing:
-/// - Buffers incoming bytes
-/// - Splits on newlines
-/// - Extracts "data: " prefixed lines
-/// - Deserializes JSON payloads
+/// - Buffers incoming bytes and splits them into lines on `\n`, `\r\n` or `\r`
+/// - Collects `data`, `event`, `id` and `retry` fields, ignoring comments
+/// - Dispatches an event at each blank line
+/// - Deserializes each event's data as JSON
+///
+/// Events whose data is `[DONE]` (used by some SSE endpoints) are skipped.
 ///
 /// # Type Parameters
 ///
@@ -31,7 +209,8 @@ use tracing::debug;
 ///
 /// # Returns
 ///
-/// A stream that yields deserialized objects of type `T` or errors
+/// A stream that yields deserialized messages of type `T` with their SSE
+/// metadata, or errors
 ///
 /// # Example
 ///
@@ -40,52 +219,65 @@ use tracing::debug;
 /// let parsed_stream = parse_sse_stream::<MyResponseType>(byte_stream, request_id);
 ///
 /// while let Some(result) = parsed_stream.next().await {
-///     let response = result?;
-///     // Process response...
+///     let message = result?;
+///     // Process message.data...
 /// }
 /// ```
 pub fn parse_sse_stream<T>(
     byte_stream: impl Stream<Item = Result<Bytes, reqwest::Error>> + Send,
     request_id: usize,
-) -> impl Stream<Item = Result<T, GenaiError>> + Send
+) -> impl Stream<Item = Result<SseMessage<T>, GenaiError>> + Send
 where
     T: DeserializeOwned + Send,
 {
     try_stream! {
         futures_util::pin_mut!(byte_stream);
-        let mut buffer = Vec::new();
+        let mut decoder = SseDecoder::new();
 
         while let Some(chunk_result) = byte_stream.next().await {
             let chunk: Bytes = chunk_result?;
-            buffer.extend_from_slice(&chunk);
-
-            while let Some(newline_pos) = buffer.iter().position(|&b| b == b'\n') {
-                let line_bytes = buffer.drain(..=newline_pos).collect::<Vec<u8>>();
-                let line = str::from_utf8(&line_bytes)?.trim_end_matches(|c| c == '\n' || c == '\r');
-
-                if line.starts_with("data:") {
-                    let json_data = line
-                        .strip_prefix("data:")
-                        .expect("Line should start with 'data:' prefix after check");
-                    let json_data = json_data.trim_start();
-
-                    // Skip empty data lines and [DONE] markers (used by some SSE endpoints)
-                    if !json_data.is_empty() && json_data != "[DONE]" {
-                        debug!("SSE raw data: {}", json_data);
-
-                        // LOUD_WIRE: Log SSE chunk
-                        loud_wire::log_sse_chunk(request_id, json_data);
-
-                        let parsed: T = serde_json::from_str(json_data).map_err(|e| {
-                            let context_msg = format_json_parse_error(json_data, e);
-                            GenaiError::Parse(context_msg)
-                        })?;
-                        yield parsed;
-                    }
+            for event in decoder.feed(&chunk) {
+                if let Some(message) = deserialize_event(event, decoder.retry(), request_id)? {
+                    yield message;
                 }
             }
         }
+        let last = match decoder.finish() {
+            Some(event) => deserialize_event(event, decoder.retry(), request_id)?,
+            None => None,
+        };
+        if let Some(message) = last {
+            yield message;
+        }
+    }
+}
+
+/// Deserializes an event's data, returning `None` for empty and `[DONE]` payloads.
+fn deserialize_event<T: DeserializeOwned>(
+    event: SseEvent,
+    retry: Option<Duration>,
+    request_id: usize,
+) -> Result<Option<SseMessage<T>>, GenaiError> {
+    let data = event.data.trim();
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
     }
+    debug!("SSE raw data: {}", data);
+
+    // LOUD_WIRE: Log SSE chunk
+    loud_wire::log_sse_chunk(request_id, data);
+
+    let parsed: T = serde_json::from_str(data).map_err(|e| {
+        let context_msg = format_json_parse_error(data, e);
+        GenaiError::Parse(context_msg)
+    })?;
+    Ok(Some(SseMessage {
+        data: parsed,
+        event: event.event,
+        id: event.id,
+        last_event_id: event.last_event_id,
+        retry,
+    }))
 }
 
 #[cfg(test)]
@@ -112,7 +304,7 @@ mod tests {
         assert!(result.is_some());
 
         let message = result.unwrap().unwrap();
-        assert_eq!(message.text, "Hello");
+        assert_eq!(message.data.text, "Hello");
     }
 
     #[tokio::test]
@@ -125,10 +317,10 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let first = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(first.text, "First");
+        assert_eq!(first.data.text, "First");
 
         let second = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(second.text, "Second");
+        assert_eq!(second.data.text, "Second");
     }
 
     #[tokio::test]
@@ -142,7 +334,7 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let message = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(message.text, "Hello");
+        assert_eq!(message.data.text, "Hello");
     }
 
     #[tokio::test]
@@ -155,7 +347,7 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let message = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(message.text, "Hello");
+        assert_eq!(message.data.text, "Hello");
 
         // Should have no more messages
         assert!(parsed_stream.next().await.is_none());
@@ -171,7 +363,7 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let message = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(message.text, "Hello");
+        assert_eq!(message.data.text, "Hello");
     }
 
     #[tokio::test]
@@ -202,7 +394,7 @@ mod tests {
         let result = parsed_stream.next().await;
         assert!(result.is_some());
         let message = result.unwrap().unwrap();
-        assert_eq!(message.text.len(), 1_000_000);
+        assert_eq!(message.data.text.len(), 1_000_000);
     }
 
     #[tokio::test]
@@ -221,7 +413,7 @@ mod tests {
         while let Some(result) = parsed_stream.next().await {
             assert!(result.is_ok());
             let message = result.unwrap();
-            assert_eq!(message.text, format!("Message {}", count));
+            assert_eq!(message.data.text, format!("Message {}", count));
             count += 1;
         }
 
@@ -242,7 +434,7 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let message = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(message.text, "Hello");
+        assert_eq!(message.data.text, "Hello");
     }
 
     #[tokio::test]
@@ -255,13 +447,13 @@ mod tests {
         pin_mut!(parsed_stream);
 
         let first = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(first.text, "First");
+        assert_eq!(first.data.text, "First");
 
         let second = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(second.text, "Second");
+        assert_eq!(second.data.text, "Second");
 
         let third = parsed_stream.next().await.unwrap().unwrap();
-        assert_eq!(third.text, "Third");
+        assert_eq!(third.data.text, "Third");
     }
 
     #[tokio::test]
@@ -275,6 +467,120 @@ mod tests {
 
         let message = parsed_stream.next().await.unwrap().unwrap();
         // The JSON parser should decode \u sequences to actual Unicode characters
-        assert_eq!(message.text, "Hello 世界 🌍");
+        assert_eq!(message.data.text, "Hello 世界 🌍");
+    }
+
+    fn decode_all(chunks: &[&[u8]]) -> (Vec<SseEvent>, SseDecoder) {
+        let mut decoder = SseDecoder::new();
+        let mut events = Vec::new();
+        for chunk in chunks {
+            events.extend(decoder.feed(chunk));
+        }
+        events.extend(decoder.finish());
+        (events, decoder)
+    }
+
+    #[test]
+    fn test_decoder_joins_multi_line_data() {
+        let (events, _) = decode_all(&[b"data: first\ndata:second\ndata\ndata:  indented\n\n"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "first\nsecond\n\n indented");
+    }
+
+    #[test]
+    fn test_decoder_reads_event_id_and_retry_fields() {
+        let (events, decoder) = decode_all(&[
+            b"event: content.delta\nid: evt_7\nretry: 1500\ndata: {}\n\n",
+            b"data: {}\n\n",
+        ]);
+        assert_eq!(
+            events[0],
+            SseEvent {
+                event: Some("content.delta".to_string()),
+                data: "{}".to_string(),
+                id: Some("evt_7".to_string()),
+                last_event_id: Some("evt_7".to_string()),
+            }
+        );
+        // The event type applies to one event; the last event ID and retry persist
+        assert_eq!(events[1].event, None);
+        assert_eq!(events[1].id, None);
+        assert_eq!(events[1].last_event_id.as_deref(), Some("evt_7"));
+        assert_eq!(decoder.retry(), Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_decoder_empty_id_clears_last_event_id() {
+        let (events, _) = decode_all(&[b"id: evt_1\ndata: a\n\nid\ndata: b\n\ndata: c\n\n"]);
+        assert_eq!(events[0].last_event_id.as_deref(), Some("evt_1"));
+        assert_eq!(events[1].id, None);
+        assert_eq!(events[1].last_event_id.as_deref(), Some(""));
+        assert_eq!(events[2].last_event_id.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_decoder_replaces_invalid_utf8() {
+        let (events, _) = decode_all(&[b"data: caf\xc3\n\ndata: ok\n\n"]);
+        assert_eq!(events[0].data, "caf\u{fffd}");
+        assert_eq!(events[1].data, "ok");
+    }
+
+    #[test]
+    fn test_decoder_ignores_invalid_retry_and_id_with_nul() {
+        let (events, decoder) = decode_all(&[b"retry: 10s\nid: a\0b\ndata: x\n\n"]);
+        assert_eq!(events[0].id, None);
+        assert_eq!(events[0].last_event_id, None);
+        assert_eq!(decoder.retry(), None);
+    }
+
+    #[test]
+    fn test_decoder_handles_lone_cr_line_endings() {
+        let (events, _) = decode_all(&[b"data: one\r\rdata: two\r\r"]);
+        let data: Vec<_> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, ["one", "two"]);
+    }
+
+    #[test]
+    fn test_decoder_handles_crlf_split_across_chunks() {
+        let (events, _) = decode_all(&[b"data: one\r", b"\n\r", b"\ndata: two\r\n\r\n"]);
+        let data: Vec<_> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, ["one", "two"]);
+    }
+
+    #[test]
+    fn test_decoder_skips_comments_and_events_without_data() {
+        let (events, _) = decode_all(&[b": keep-alive\n\nevent: ping\n\n:comment\ndata: x\n\n"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "x");
+        assert_eq!(events[0].event, None);
+    }
+
+    #[test]
+    fn test_decoder_strips_leading_bom() {
+        let (events, _) = decode_all(&["\u{feff}data: x\n\n".as_bytes()]);
+        assert_eq!(events[0].data, "x");
+    }
+
+    #[test]
+    fn test_decoder_dispatches_unterminated_event_at_end() {
+        let (events, _) = decode_all(&[b"data: complete\n", b"data: partial"]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "complete");
+    }
+
+    #[tokio::test]
+    async fn test_parse_sse_stream_exposes_sse_fields() {
+        let data =
+            b"retry: 250\nevent: update\nid: evt_1\ndata: {\"text\":\ndata: \"Hi\"}\n\n".to_vec();
+        let byte_stream = stream::iter(vec![Ok(Bytes::from(data))]);
+
+        let parsed_stream = parse_sse_stream::<TestMessage>(byte_stream, 0);
+        pin_mut!(parsed_stream);
+
+        let message = parsed_stream.next().await.unwrap().unwrap();
+        assert_eq!(message.data.text, "Hi");
+        assert_eq!(message.event.as_deref(), Some("update"));
+        assert_eq!(message.id.as_deref(), Some("evt_1"));
+        assert_eq!(message.retry, Some(Duration::from_millis(250)));
     }
 }
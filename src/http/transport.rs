@@ -0,0 +1,222 @@
+//! Shared connection state for the HTTP layer.
+//!
+//! A [`Transport`] bundles everything needed to address and authenticate a
+//! request: the reqwest client, the base URL, the [`Backend`], the API version,
+//! default headers, the credential provider, any registered middleware and an
+//! optional cassette. API functions in `interactions` and `files` take a `&Transport`
+//! instead of threading these values individually, and send every request
+//! through [`Transport::send()`].
+
+use super::common::{ApiVersion, Backend, Endpoint, construct_endpoint_url};
+use crate::cassette::Cassette;
+use crate::credentials::CredentialProvider;
+use crate::middleware::Middleware;
+use crate::{GenaiError, InteractionRequest, InteractionResponse, StreamEvent};
+use reqwest::header::HeaderMap;
+use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Response};
+use std::sync::Arc;
+
+/// Addressing and authentication for API requests.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    pub(crate) http_client: ReqwestClient,
+    pub(crate) base_url: String,
+    pub(crate) backend: Backend,
+    pub(crate) api_version: ApiVersion,
+    /// Sent with every request, including upload sessions
+    pub(crate) default_headers: HeaderMap,
+    pub(crate) credentials: Arc<dyn CredentialProvider>,
+    pub(crate) middleware: Vec<Arc<dyn Middleware>>,
+    pub(crate) cassette: Option<Cassette>,
+}
+
+impl std::fmt::Debug for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transport")
+            .field("base_url", &self.base_url)
+            .field("backend", &self.backend)
+            .field("api_version", &self.api_version)
+            .field(
+                "default_headers",
+                &self.default_headers.keys().collect::<Vec<_>>(),
+            )
+            .field("credentials", &self.credentials)
+            .field("middleware", &self.middleware)
+            .field("cassette", &self.cassette)
+            .finish()
+    }
+}
+
+impl Transport {
+    pub(crate) fn new(
+        http_client: ReqwestClient,
+        base_url: String,
+        backend: Backend,
+        credentials: Arc<dyn CredentialProvider>,
+    ) -> Self {
+        Self {
+            http_client,
+            base_url,
+            backend,
+            api_version: ApiVersion::default(),
+            default_headers: HeaderMap::new(),
+            credentials,
+            middleware: Vec::new(),
+            cassette: None,
+        }
+    }
+
+    pub(crate) fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    pub(crate) fn with_default_headers(mut self, default_headers: HeaderMap) -> Self {
+        self.default_headers = default_headers;
+        self
+    }
+
+    pub(crate) fn with_middleware(mut self, middleware: Vec<Arc<dyn Middleware>>) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
+    pub(crate) fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Builds the full URL for an endpoint on this transport's backend and API version.
+    pub(crate) fn url(&self, endpoint: Endpoint) -> String {
+        construct_endpoint_url(&self.base_url, &self.backend, &self.api_version, endpoint)
+    }
+
+    /// Starts a request authenticated with a fresh credential from the provider.
+    pub(crate) async fn request(
+        &self,
+        method: Method,
+        url: &str,
+    ) -> Result<RequestBuilder, GenaiError> {
+        let credential = self.credentials.credential().await?;
+        Ok(credential.apply(self.unauthenticated(method, url)))
+    }
+
+    /// Starts a request with the default headers but no credential, for URLs
+    /// that carry their own authorization (e.g., resumable upload sessions).
+    pub(crate) fn unauthenticated(&self, method: Method, url: &str) -> RequestBuilder {
+        self.http_client
+            .request(method, url)
+            .headers(self.default_headers.clone())
+    }
+
+    pub(crate) fn unauthenticated_post(&self, url: &str) -> RequestBuilder {
+        self.unauthenticated(Method::POST, url)
+    }
+
+    pub(crate) async fn get(&self, url: &str) -> Result<RequestBuilder, GenaiError> {
+        self.request(Method::GET, url).await
+    }
+
+    pub(crate) async fn post(&self, url: &str) -> Result<RequestBuilder, GenaiError> {
+        self.request(Method::POST, url).await
+    }
+
+    pub(crate) async fn delete(&self, url: &str) -> Result<RequestBuilder, GenaiError> {
+        self.request(Method::DELETE, url).await
+    }
+
+    /// Sends a request, recording or replaying it when a cassette is attached.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response, GenaiError> {
+        match &self.cassette {
+            Some(cassette) => cassette.send(request, &self.default_headers).await,
+            None => Ok(request.send().await?),
+        }
+    }
+
+    // --- Middleware hooks ---
+
+    /// Runs `on_request` for each middleware and returns the headers they added.
+    pub(crate) async fn prepare_interaction(
+        &self,
+        request: &mut InteractionRequest,
+    ) -> Result<HeaderMap, GenaiError> {
+        let mut headers = HeaderMap::new();
+        for middleware in &self.middleware {
+            middleware.on_request(request, &mut headers).await?;
+        }
+        Ok(headers)
+    }
+
+    pub(crate) fn observe_response_head(&self, response: &reqwest::Response) {
+        for middleware in &self.middleware {
+            middleware.on_response_head(response.status().as_u16(), response.headers());
+        }
+    }
+
+    pub(crate) fn observe_response(&self, response: &InteractionResponse) {
+        for middleware in &self.middleware {
+            middleware.on_response(response);
+        }
+    }
+
+    pub(crate) fn observe_stream_event(&self, event: &StreamEvent) {
+        for middleware in &self.middleware {
+            middleware.on_stream_event(event);
+        }
+    }
+
+    pub(crate) fn observe_error(&self, error: &GenaiError) {
+        for middleware in &self.middleware {
+            middleware.on_error(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::Credential;
+    use crate::http::common::{API_KEY_HEADER, DEFAULT_BASE_URL};
+
+    fn transport(credential: Credential) -> Transport {
+        Transport::new(
+            ReqwestClient::new(),
+            DEFAULT_BASE_URL.to_string(),
+            Backend::GeminiApi,
+            Arc::new(credential),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_api_key_credential_header() {
+        let request = transport(Credential::ApiKey("secret".to_string()))
+            .get("https://example.com")
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(request.headers()[API_KEY_HEADER], "secret");
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bearer_credential_header() {
+        let request = transport(Credential::BearerToken("secret".to_string()))
+            .post("https://example.com")
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(request.headers()["authorization"], "Bearer secret");
+        assert!(request.headers().get(API_KEY_HEADER).is_none());
+    }
+
+    #[test]
+    fn test_debug_redacts_credential() {
+        let debug = format!("{:?}", transport(Credential::ApiKey("secret".to_string())));
+        assert!(debug.contains("[REDACTED]"));
+        assert!(!debug.contains("secret"));
+    }
+}
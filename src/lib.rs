@@ -96,7 +96,7 @@ pub use tools::{
 
 // Wire streaming types (from API)
 pub mod wire_streaming;
-pub use wire_streaming::{InteractionStreamEvent, StreamChunk, StreamEvent};
+pub use wire_streaming::{InteractionStreamEvent, StreamAccumulator, StreamChunk, StreamEvent};
 
 // Files API types
 pub use http::files::{
@@ -111,6 +111,28 @@ pub use http::files::{
 pub mod client;
 pub use client::{Client, ClientBuilder};
 
+pub mod cassette;
+pub use cassette::{Cassette, CassetteMode};
+
+pub mod credentials;
+pub use credentials::{Credential, CredentialProvider};
+pub use http::common::{ApiVersion, Backend};
+
+pub mod retry;
+pub use retry::RetryPolicy;
+
+pub mod cancel_guard;
+pub use cancel_guard::{CancelGuard, CancelOnDrop};
+
+pub mod stall;
+pub use stall::{StallAction, StallPolicy};
+
+pub mod middleware;
+pub use middleware::Middleware;
+
+pub mod rate_limit;
+pub use rate_limit::{RateLimit, RateLimiter};
+
 pub mod request_builder;
 pub use request_builder::{ConversationBuilder, InteractionBuilder};
 
@@ -120,6 +142,13 @@ pub use request_builder::{ConversationBuilder, InteractionBuilder};
 
 pub mod function_calling;
 pub use function_calling::{CallableFunction, FunctionError, ToolService};
+pub use tokio_util::sync::CancellationToken;
+
+pub mod approval;
+pub use approval::{ApprovalDecision, ApprovalPolicy};
+
+pub mod tool_schema;
+pub use tool_schema::ToolSchema;
 
 // =============================================================================
 // Streaming Types for Auto Function Calling
@@ -131,6 +160,27 @@ pub use streaming::{
     AutoFunctionStreamEvent, FunctionExecutionResult, PendingFunctionCall,
 };
 
+// =============================================================================
+// Stream Adapters
+// =============================================================================
+
+mod fan_out;
+
+pub mod stream_ext;
+pub use stream_ext::{DeltaEvent, InteractionStreamExt};
+
+pub mod audio_stream;
+pub use audio_stream::{AudioStreamWriter, PcmFormat};
+
+pub mod partial_json;
+pub use partial_json::{PartialJson, parse_partial_json};
+
+pub mod shared_stream;
+pub use shared_stream::{SharedInteractionStream, SharedStreamItem, SharedStreamSubscriber};
+
+pub mod sse_encoder;
+pub use sse_encoder::{SSE_ERROR_EVENT, SseEncoder, SseEvent};
+
 // =============================================================================
 // Content Constructor Functions
 // =============================================================================
@@ -152,6 +202,13 @@ pub use multimodal::{
     video_from_file_with_mime,
 };
 
+// =============================================================================
+// Test Utilities (feature = "testing")
+// =============================================================================
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 // =============================================================================
 // Test Modules
 // =============================================================================
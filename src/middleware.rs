@@ -0,0 +1,87 @@
+//! Request/response middleware for model calls.
+//!
+//! A [`Middleware`] registered with
+//! [`ClientBuilder::with_middleware()`](crate::ClientBuilder::with_middleware)
+//! sees every interaction the client creates: via `execute()`/`create()`,
+//! `execute_stream()`/`create_stream()`, and each model call made by the
+//! auto-function loops. Use it to add headers, write audit logs, scrub PII
+//! before it leaves the process, or record metrics.
+//!
+//! # Hook order
+//!
+//! For each HTTP attempt (retries run the hooks again):
+//!
+//! 1. [`on_request`](Middleware::on_request) - may modify the request and add headers
+//! 2. [`on_response_head`](Middleware::on_response_head) - HTTP status and headers
+//! 3. [`on_response`](Middleware::on_response) for unary calls, or
+//!    [`on_stream_event`](Middleware::on_stream_event) for each streamed event
+//! 4. [`on_error`](Middleware::on_error) if the call fails
+//!
+//! Middleware run in registration order. An error returned from `on_request`
+//! aborts the call before anything is sent.
+//!
+//! # Example
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use genai_rs::{Client, GenaiError, InteractionRequest, Middleware};
+//! use reqwest::header::{HeaderMap, HeaderValue};
+//!
+//! #[derive(Debug)]
+//! struct TenantHeader(&'static str);
+//!
+//! #[async_trait]
+//! impl Middleware for TenantHeader {
+//!     async fn on_request(
+//!         &self,
+//!         _request: &mut InteractionRequest,
+//!         headers: &mut HeaderMap,
+//!     ) -> Result<(), GenaiError> {
+//!         headers.insert("x-tenant-id", HeaderValue::from_static(self.0));
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let client = Client::builder("api_key".to_string())
+//!     .with_middleware(TenantHeader("acme"))
+//!     .build()?;
+//! # Ok::<(), GenaiError>(())
+//! ```
+
+use crate::{GenaiError, InteractionRequest, InteractionResponse, StreamEvent};
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+
+/// Hooks into the interactions a [`Client`](crate::Client) creates.
+///
+/// Every method has a no-op default, so implementations only override the
+/// hooks they need. See the [module documentation](self) for when each runs.
+#[async_trait]
+pub trait Middleware: Send + Sync + std::fmt::Debug {
+    /// Inspects or modifies an outgoing request before it is sent.
+    ///
+    /// `headers` starts empty and is added to the HTTP request; authentication
+    /// headers are applied separately. Changes to `request` are what the API
+    /// receives (and what `LOUD_WIRE` logs).
+    async fn on_request(
+        &self,
+        _request: &mut InteractionRequest,
+        _headers: &mut HeaderMap,
+    ) -> Result<(), GenaiError> {
+        Ok(())
+    }
+
+    /// Observes the HTTP status and headers of a response, before the body is read.
+    ///
+    /// Runs for error statuses too.
+    fn on_response_head(&self, _status: u16, _headers: &HeaderMap) {}
+
+    /// Observes the parsed response of a non-streaming call.
+    fn on_response(&self, _response: &InteractionResponse) {}
+
+    /// Observes each event of a streaming call.
+    fn on_stream_event(&self, _event: &StreamEvent) {}
+
+    /// Observes an error that ends a call (or one attempt of a retried call).
+    fn on_error(&self, _error: &GenaiError) {}
+}
@@ -0,0 +1,438 @@
+//! Best-effort parsing of incomplete JSON documents.
+//!
+//! Structured output (`with_response_format()`) streams its JSON document as
+//! text deltas. [`PartialJson`] accumulates those deltas and, after each one,
+//! produces a [`Value`] snapshot of the document so far by closing any open
+//! strings, arrays and objects. Values that may still change meaning are left
+//! out: object keys without a value, and numbers or literals cut off at the end
+//! of the text.
+//!
+//! # Example
+//!
+//! ```
+//! use genai_rs::PartialJson;
+//! use serde_json::json;
+//!
+//! let mut partial = PartialJson::new();
+//! partial.push(r#"{"title": "Quarterly rep"#);
+//! assert_eq!(partial.snapshot(), Some(&json!({"title": "Quarterly rep"})));
+//!
+//! partial.push(r#"ort", "tags": ["finance", "q3"#);
+//! assert_eq!(
+//!     partial.snapshot(),
+//!     Some(&json!({"title": "Quarterly report", "tags": ["finance", "q3"]}))
+//! );
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Accumulates text deltas of a JSON document and keeps a best-effort snapshot.
+///
+/// Each [`push()`](Self::push) re-parses the whole document, which keeps the
+/// parser simple and is cheap for structured outputs of typical size.
+#[derive(Clone, Debug, Default)]
+pub struct PartialJson {
+    text: String,
+    snapshot: Option<Value>,
+}
+
+impl PartialJson {
+    /// Creates an empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a text delta and returns the updated snapshot.
+    ///
+    /// Returns `None` until the text starts a JSON value, or if the text is not
+    /// the beginning of a valid JSON document.
+    pub fn push(&mut self, delta: &str) -> Option<&Value> {
+        self.text.push_str(delta);
+        self.snapshot = parse_partial_json(&self.text);
+        self.snapshot.as_ref()
+    }
+
+    /// The snapshot after the latest [`push()`](Self::push).
+    #[must_use]
+    pub fn snapshot(&self) -> Option<&Value> {
+        self.snapshot.as_ref()
+    }
+
+    /// Deserializes the snapshot into `T`.
+    ///
+    /// Intended for types whose fields are all `Option` (or have defaults), so
+    /// that fields the model has not produced yet are simply `None`. Returns
+    /// `None` if there is no snapshot or it does not deserialize into `T`.
+    #[must_use]
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Option<T> {
+        T::deserialize(self.snapshot.as_ref()?).ok()
+    }
+
+    /// The text accumulated so far.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Parses a possibly incomplete JSON document into a best-effort snapshot.
+///
+/// Open strings, arrays and objects are closed. An object key whose value has
+/// not started is dropped, as is a number or literal at the very end of the
+/// text, since more digits or letters may follow. Returns `None` if no value
+/// has started yet or the text is not the beginning of a valid JSON document.
+///
+/// ```
+/// use genai_rs::parse_partial_json;
+/// use serde_json::json;
+///
+/// assert_eq!(
+///     parse_partial_json(r#"{"city": "Par"#),
+///     Some(json!({"city": "Par"}))
+/// );
+/// assert_eq!(
+///     parse_partial_json(r#"{"temps": [21, 2"#),
+///     Some(json!({"temps": [21]}))
+/// );
+/// assert_eq!(parse_partial_json("{]"), None);
+/// ```
+#[must_use]
+pub fn parse_partial_json(text: &str) -> Option<Value> {
+    // A complete document, including a bare number, needs no repair
+    if let Ok(value) = serde_json::from_str(text) {
+        return Some(value);
+    }
+    let mut parser = Parser { text, pos: 0 };
+    match parser.value().ok()? {
+        Parsed::Complete(value) | Parsed::Partial(value) => Some(value),
+        Parsed::Missing => None,
+    }
+}
+
+/// Outcome of parsing one value.
+enum Parsed {
+    /// The value ended before the end of the text
+    Complete(Value),
+    /// The text ended inside the value
+    Partial(Value),
+    /// The text ended before the value started, or inside a number or literal
+    Missing,
+}
+
+/// The text is not the beginning of a valid JSON document.
+struct Invalid;
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        let rest = &self.text.as_bytes()[self.pos..];
+        let ws = rest
+            .iter()
+            .take_while(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+            .count();
+        self.pos += ws;
+        rest.get(ws).copied()
+    }
+
+    fn value(&mut self) -> Result<Parsed, Invalid> {
+        match self.peek() {
+            None => Ok(Parsed::Missing),
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string(),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(Invalid),
+        }
+    }
+
+    fn object(&mut self) -> Result<Parsed, Invalid> {
+        self.pos += 1;
+        let mut map = Map::new();
+        loop {
+            match self.peek() {
+                None => return Ok(Parsed::Partial(Value::Object(map))),
+                Some(b'}') if map.is_empty() => {
+                    self.pos += 1;
+                    return Ok(Parsed::Complete(Value::Object(map)));
+                }
+                Some(b'"') => {}
+                Some(_) => return Err(Invalid),
+            }
+            let key = match self.string()? {
+                Parsed::Complete(Value::String(key)) => key,
+                _ => return Ok(Parsed::Partial(Value::Object(map))),
+            };
+            match self.peek() {
+                None => return Ok(Parsed::Partial(Value::Object(map))),
+                Some(b':') => self.pos += 1,
+                Some(_) => return Err(Invalid),
+            }
+            match self.value()? {
+                Parsed::Complete(value) => {
+                    map.insert(key, value);
+                }
+                Parsed::Partial(value) => {
+                    map.insert(key, value);
+                    return Ok(Parsed::Partial(Value::Object(map)));
+                }
+                Parsed::Missing => return Ok(Parsed::Partial(Value::Object(map))),
+            }
+            match self.peek() {
+                None => return Ok(Parsed::Partial(Value::Object(map))),
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Parsed::Complete(Value::Object(map)));
+                }
+                Some(_) => return Err(Invalid),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Parsed, Invalid> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Parsed::Complete(Value::Array(items)));
+        }
+        loop {
+            match self.value()? {
+                Parsed::Complete(value) => items.push(value),
+                Parsed::Partial(value) => {
+                    items.push(value);
+                    return Ok(Parsed::Partial(Value::Array(items)));
+                }
+                Parsed::Missing => return Ok(Parsed::Partial(Value::Array(items))),
+            }
+            match self.peek() {
+                None => return Ok(Parsed::Partial(Value::Array(items))),
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Parsed::Complete(Value::Array(items)));
+                }
+                Some(_) => return Err(Invalid),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<Parsed, Invalid> {
+        let start = self.pos;
+        let bytes = self.text.as_bytes();
+        let mut i = start + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'"' => {
+                    self.pos = i + 1;
+                    let value =
+                        serde_json::from_str(&self.text[start..self.pos]).map_err(|_| Invalid)?;
+                    return Ok(Parsed::Complete(value));
+                }
+                _ => i += 1,
+            }
+        }
+        self.pos = bytes.len();
+
+        // Drop an unfinished escape sequence (or a lone high surrogate) and
+        // close the string
+        let mut raw = &self.text[start + 1..];
+        for _ in 0..3 {
+            if let Ok(value) = serde_json::from_str(&format!("\"{raw}\"")) {
+                return Ok(Parsed::Partial(value));
+            }
+            match raw.rfind('\\') {
+                Some(escape) => raw = &raw[..escape],
+                None => break,
+            }
+        }
+        Err(Invalid)
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Parsed, Invalid> {
+        let rest = &self.text[self.pos..];
+        if rest.starts_with(word) {
+            self.pos += word.len();
+            Ok(Parsed::Complete(value))
+        } else if word.starts_with(rest) {
+            self.pos = self.text.len();
+            Ok(Parsed::Missing)
+        } else {
+            Err(Invalid)
+        }
+    }
+
+    fn number(&mut self) -> Result<Parsed, Invalid> {
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .bytes()
+            .take_while(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+            .count();
+        if len == rest.len() {
+            // More digits may follow
+            self.pos = self.text.len();
+            return Ok(Parsed::Missing);
+        }
+        let number = rest[..len]
+            .parse::<serde_json::Number>()
+            .map_err(|_| Invalid)?;
+        self.pos += len;
+        Ok(Parsed::Complete(Value::Number(number)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[test]
+    fn test_closes_open_containers_and_strings() {
+        let cases = [
+            (r#"{"a": "hel"#, json!({"a": "hel"})),
+            (r#"{"a": ["x", "y"#, json!({"a": ["x", "y"]})),
+            (
+                r#"{"a": {"b": {"c": 1}, "d": [tr"#,
+                json!({"a": {"b": {"c": 1}, "d": []}}),
+            ),
+            (r#"[{"a": 1}, {"#, json!([{"a": 1}, {}])),
+            (r#"{"#, json!({})),
+            ("[", json!([])),
+            (r#""partial"#, json!("partial")),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_partial_json(text), Some(expected), "input: {text}");
+        }
+    }
+
+    #[test]
+    fn test_drops_keys_without_values() {
+        let cases = [
+            r#"{"done": true, "na"#,
+            r#"{"done": true, "name""#,
+            r#"{"done": true, "name":"#,
+            r#"{"done": true, "name": "#,
+            r#"{"done": true,"#,
+        ];
+        for text in cases {
+            assert_eq!(
+                parse_partial_json(text),
+                Some(json!({"done": true})),
+                "input: {text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_numbers_and_literals_at_end_are_dropped() {
+        assert_eq!(parse_partial_json(r#"{"n": 12"#), Some(json!({})));
+        assert_eq!(parse_partial_json(r#"{"n": 12,"#), Some(json!({"n": 12})));
+        assert_eq!(
+            parse_partial_json(r#"{"n": -1.5e3}"#),
+            Some(json!({"n": -1500.0}))
+        );
+        assert_eq!(parse_partial_json(r#"[true, nu"#), Some(json!([true])));
+        assert_eq!(
+            parse_partial_json(r#"[true, null"#),
+            Some(json!([true, null]))
+        );
+        // A complete top-level scalar parses as is
+        assert_eq!(parse_partial_json("42"), Some(json!(42)));
+        assert_eq!(parse_partial_json("4"), Some(json!(4)));
+        assert_eq!(parse_partial_json("tr"), None);
+    }
+
+    #[test]
+    fn test_unfinished_escapes_are_dropped() {
+        assert_eq!(parse_partial_json(r#"["a\"#), Some(json!(["a"])));
+        assert_eq!(parse_partial_json(r#"["a\n"#), Some(json!(["a\n"])));
+        assert_eq!(parse_partial_json(r#"["a\u00"#), Some(json!(["a"])));
+        assert_eq!(parse_partial_json(r#"["aé"#), Some(json!(["aé"])));
+        assert_eq!(parse_partial_json(r#"["\ud83c"#), Some(json!([""])));
+        assert_eq!(parse_partial_json(r#"["🌍"#), Some(json!(["🌍"])));
+    }
+
+    #[test]
+    fn test_invalid_prefixes_return_none() {
+        for text in [
+            "",
+            "   ",
+            "{]",
+            r#"{"a" 1}"#,
+            "[1 2]",
+            "nope",
+            r#"{"a": tx"#,
+        ] {
+            assert_eq!(parse_partial_json(text), None, "input: {text}");
+        }
+    }
+
+    #[test]
+    fn test_every_prefix_of_a_document_parses() {
+        let document = json!({
+            "title": "Report \"Q3\" ✓",
+            "scores": [1, 2.5, -3e2],
+            "meta": {"ok": true, "none": null, "nested": [[], {}]},
+        })
+        .to_string();
+
+        let mut previous = None;
+        for end in (0..=document.len()).filter(|&end| document.is_char_boundary(end)) {
+            let snapshot = parse_partial_json(&document[..end]);
+            if end > 0 {
+                assert!(snapshot.is_some(), "prefix {:?}", &document[..end]);
+            }
+            previous = snapshot.or(previous);
+        }
+        assert_eq!(previous.unwrap().to_string(), document);
+    }
+
+    #[test]
+    fn test_typed_snapshot_with_optional_fields() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Report {
+            title: Option<String>,
+            pages: Option<u32>,
+            tags: Option<Vec<String>>,
+        }
+
+        let mut partial = PartialJson::new();
+        assert_eq!(partial.push(""), None);
+        assert_eq!(partial.deserialize::<Report>(), None);
+
+        partial.push(r#"{"title": "Q3", "pages": 1"#);
+        assert_eq!(
+            partial.deserialize::<Report>(),
+            Some(Report {
+                title: Some("Q3".to_string()),
+                pages: None,
+                tags: None,
+            })
+        );
+
+        partial.push(r#"2, "tags": ["fin"#);
+        assert_eq!(
+            partial.deserialize::<Report>(),
+            Some(Report {
+                title: Some("Q3".to_string()),
+                pages: Some(12),
+                tags: Some(vec!["fin".to_string()]),
+            })
+        );
+        assert_eq!(
+            partial.text(),
+            r#"{"title": "Q3", "pages": 12, "tags": ["fin"#
+        );
+    }
+}
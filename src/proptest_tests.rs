@@ -10,6 +10,8 @@ use super::content::{
     Annotation, CodeExecutionLanguage, Content, FileSearchResultItem, GoogleSearchResultItem,
     Resolution, UrlContextResultItem,
 };
+use super::http::sse_parser::{SseDecoder, SseEvent};
+use super::partial_json::parse_partial_json;
 use super::request::{
     AgentConfig, DeepResearchConfig, DynamicConfig, Role, ThinkingLevel, ThinkingSummaries, Turn,
     TurnContent,
@@ -1238,3 +1240,128 @@ proptest! {
         prop_assert_eq!(json, restored_json);
     }
 }
+
+// =============================================================================
+// SSE Decoder Tests
+// =============================================================================
+
+fn arb_sse_event() -> impl Strategy<Value = SseEvent> {
+    (
+        prop::option::of("[a-z][a-z._]{0,15}"),
+        // Any text without CR; LF separates data lines
+        "[^\r]{0,40}",
+        prop::option::of("[^\r\n\0]{1,20}"),
+    )
+        .prop_map(|(event, data, id)| SseEvent {
+            event,
+            data,
+            id,
+            last_event_id: None,
+        })
+}
+
+/// Serializes events in the SSE wire format using the given line ending.
+fn encode_sse_events(events: &[SseEvent], line_ending: &str) -> Vec<u8> {
+    let mut out = String::new();
+    for event in events {
+        out.push_str(": comment");
+        out.push_str(line_ending);
+        if let Some(name) = &event.event {
+            out.push_str(&format!("event: {name}{line_ending}"));
+        }
+        if let Some(id) = &event.id {
+            out.push_str(&format!("id: {id}{line_ending}"));
+        }
+        for line in event.data.split('\n') {
+            out.push_str(&format!("data: {line}{line_ending}"));
+        }
+        out.push_str(line_ending);
+    }
+    out.into_bytes()
+}
+
+proptest! {
+    /// Test that events decode unchanged for any line ending style and any
+    /// chunking of the byte stream.
+    #[test]
+    fn sse_decoder_roundtrip(
+        events in prop::collection::vec(arb_sse_event(), 0..8),
+        line_ending in prop::sample::select(vec!["\n", "\r\n", "\r"]),
+        cuts in prop::collection::vec(any::<prop::sample::Index>(), 0..12),
+    ) {
+        let bytes = encode_sse_events(&events, line_ending);
+        let mut cuts: Vec<usize> = cuts.iter().map(|cut| cut.index(bytes.len() + 1)).collect();
+        cuts.sort_unstable();
+
+        let mut decoder = SseDecoder::new();
+        let mut decoded = Vec::new();
+        let mut start = 0;
+        for end in cuts.into_iter().chain([bytes.len()]) {
+            decoded.extend(decoder.feed(&bytes[start..end]));
+            start = end;
+        }
+        decoded.extend(decoder.finish());
+
+        // Each event reports the latest ID so far
+        let mut last_event_id = None;
+        let expected: Vec<_> = events
+            .into_iter()
+            .map(|event| {
+                if event.id.is_some() {
+                    last_event_id.clone_from(&event.id);
+                }
+                SseEvent { last_event_id: last_event_id.clone(), ..event }
+            })
+            .collect();
+        prop_assert_eq!(decoded, expected);
+    }
+
+    /// Test that arbitrary bytes never panic the decoder.
+    #[test]
+    fn sse_decoder_handles_arbitrary_bytes(
+        chunks in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..64), 0..8),
+    ) {
+        let mut decoder = SseDecoder::new();
+        for chunk in &chunks {
+            decoder.feed(chunk);
+        }
+        let _ = decoder.finish();
+    }
+}
+
+// =============================================================================
+// Partial JSON Tests
+// =============================================================================
+
+fn arb_nested_json() -> impl Strategy<Value = serde_json::Value> {
+    arb_json_value().prop_recursive(3, 24, 4, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..4).prop_map(serde_json::Value::Array),
+            prop::collection::btree_map("[a-z\"\\\\ ]{0,6}", inner, 0..4)
+                .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+proptest! {
+    /// Test that every prefix of a document parses without panicking, that
+    /// prefixes of containers always give a snapshot, and that the complete
+    /// document gives the original value.
+    #[test]
+    fn partial_json_prefixes(value in arb_nested_json(), pretty in any::<bool>()) {
+        let document = if pretty {
+            serde_json::to_string_pretty(&value).expect("Serialization should succeed")
+        } else {
+            value.to_string()
+        };
+        let is_container = value.is_array() || value.is_object();
+
+        for end in (1..document.len()).filter(|&end| document.is_char_boundary(end)) {
+            let snapshot = parse_partial_json(&document[..end]);
+            if is_container {
+                prop_assert!(snapshot.is_some(), "no snapshot for {:?}", &document[..end]);
+            }
+        }
+        prop_assert_eq!(parse_partial_json(&document), Some(value));
+    }
+}
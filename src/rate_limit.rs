@@ -0,0 +1,414 @@
+//! Client-side rate limiting for requests-per-minute and tokens-per-minute quotas.
+//!
+//! A [`RateLimiter`] set on [`ClientBuilder`](crate::ClientBuilder) makes each
+//! model call wait for capacity before it is sent, so a process sharing quota
+//! across many workers stays under its budget instead of discovering the limit
+//! through 429 responses.
+//!
+//! Limits are tracked with token buckets keyed by model (or agent). Each bucket
+//! holds up to one minute's worth of capacity and refills continuously.
+//!
+//! - **RPM**: every attempt sent to the API (including retries) takes one request.
+//! - **TPM**: after each response, `UsageMetadata::total_tokens` is debited. With a
+//!   [token estimator](RateLimiter::with_token_estimator), the estimate is debited
+//!   up front and corrected once the actual usage is known. A bucket may go into
+//!   debt when a response uses more tokens than were available; later requests
+//!   for that model wait until it has refilled.
+//!
+//! Clones of a `RateLimiter` share the same buckets, as do all clones of a
+//! [`Client`](crate::Client) built with it, so one process respects a single budget.
+//!
+//! # Example
+//!
+//! ```
+//! use genai_rs::{Client, RateLimit, RateLimiter};
+//!
+//! let limiter = RateLimiter::new(
+//!     RateLimit::new()
+//!         .with_requests_per_minute(60)
+//!         .with_tokens_per_minute(100_000),
+//! )
+//! .with_model_limit("gemini-3-pro-preview", RateLimit::new().with_requests_per_minute(10))
+//! // Rough estimate: ~4 characters per token
+//! .with_token_estimator(|request| {
+//!     serde_json::to_string(&request.input).map_or(0, |s| (s.len() / 4) as u32)
+//! });
+//!
+//! let client = Client::builder("api_key".to_string())
+//!     .with_rate_limiter(limiter)
+//!     .build()?;
+//! # Ok::<(), genai_rs::GenaiError>(())
+//! ```
+
+use crate::{InteractionRequest, UsageMetadata};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Bucket key used for requests that name neither a model nor an agent.
+const DEFAULT_KEY: &str = "default";
+
+/// Per-minute quotas for one model.
+///
+/// An unset limit is not enforced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    requests_per_minute: Option<u32>,
+    tokens_per_minute: Option<u32>,
+}
+
+impl RateLimit {
+    /// Creates a limit with no quotas set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            requests_per_minute: None,
+            tokens_per_minute: None,
+        }
+    }
+
+    /// Sets the maximum number of requests per minute.
+    #[must_use]
+    pub const fn with_requests_per_minute(mut self, rpm: u32) -> Self {
+        self.requests_per_minute = Some(rpm);
+        self
+    }
+
+    /// Sets the maximum number of tokens (input plus output) per minute.
+    #[must_use]
+    pub const fn with_tokens_per_minute(mut self, tpm: u32) -> Self {
+        self.tokens_per_minute = Some(tpm);
+        self
+    }
+}
+
+type TokenEstimator = Arc<dyn Fn(&InteractionRequest) -> u32 + Send + Sync>;
+
+/// Token-bucket rate limiter for model calls, keyed by model.
+///
+/// See the [module documentation](self) for how limits are applied.
+#[derive(Clone)]
+pub struct RateLimiter {
+    default_limit: RateLimit,
+    model_limits: HashMap<String, RateLimit>,
+    estimator: Option<TokenEstimator>,
+    buckets: Arc<Mutex<HashMap<String, ModelBuckets>>>,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("default_limit", &self.default_limit)
+            .field("model_limits", &self.model_limits)
+            .field("has_estimator", &self.estimator.is_some())
+            .finish()
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter that applies `limit` to every model without its own limit.
+    ///
+    /// Each model gets separate buckets, even when they share the default limit.
+    #[must_use]
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            default_limit: limit,
+            model_limits: HashMap::new(),
+            estimator: None,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Overrides the limit for one model (or agent).
+    ///
+    /// Set limits before requests are made; buckets are sized when a model is
+    /// first used.
+    #[must_use]
+    pub fn with_model_limit(mut self, model: impl Into<String>, limit: RateLimit) -> Self {
+        self.model_limits.insert(model.into(), limit);
+        self
+    }
+
+    /// Sets a function that estimates a request's total token usage.
+    ///
+    /// The estimate is debited from the TPM bucket before the request is sent,
+    /// so concurrent requests don't all start against the same remaining budget.
+    /// Once the response arrives, the difference to the actual usage is settled.
+    #[must_use]
+    pub fn with_token_estimator<F>(mut self, estimator: F) -> Self
+    where
+        F: Fn(&InteractionRequest) -> u32 + Send + Sync + 'static,
+    {
+        self.estimator = Some(Arc::new(estimator));
+        self
+    }
+
+    fn limit_for(&self, key: &str) -> RateLimit {
+        self.model_limits
+            .get(key)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// Waits until `request` fits within its model's limits, then reserves capacity.
+    pub(crate) async fn acquire(&self, request: &InteractionRequest) -> RateLimitPermit {
+        let key = request
+            .model
+            .as_deref()
+            .or(request.agent.as_deref())
+            .unwrap_or(DEFAULT_KEY)
+            .to_string();
+        let estimate = self.estimator.as_ref().map_or(0, |f| f(request));
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+                let now = Instant::now();
+                let entry = buckets
+                    .entry(key.clone())
+                    .or_insert_with(|| ModelBuckets::new(self.limit_for(&key), now));
+                entry.try_take(estimate, now)
+            };
+            match wait {
+                None => break,
+                Some(wait) => {
+                    tracing::debug!(model = %key, ?wait, "Waiting for rate limit capacity");
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+
+        RateLimitPermit {
+            buckets: self.buckets.clone(),
+            key,
+            estimate,
+        }
+    }
+}
+
+/// Capacity reserved for one request, settled against actual usage.
+#[must_use = "call settle() with the response usage"]
+pub(crate) struct RateLimitPermit {
+    buckets: Arc<Mutex<HashMap<String, ModelBuckets>>>,
+    key: String,
+    estimate: u32,
+}
+
+impl RateLimitPermit {
+    /// Debits the actual token usage, correcting the pre-debited estimate.
+    ///
+    /// Without usage (e.g., the request failed), the estimate stands.
+    pub(crate) fn settle(self, usage: Option<&UsageMetadata>) {
+        let Some(total) = usage.and_then(|u| u.total_tokens) else {
+            return;
+        };
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+        if let Some(tokens) = buckets.get_mut(&self.key).and_then(|b| b.tokens.as_mut()) {
+            tokens.refill(Instant::now());
+            tokens.take(f64::from(total) - f64::from(self.estimate));
+        }
+    }
+}
+
+struct ModelBuckets {
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+}
+
+impl ModelBuckets {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            requests: limit.requests_per_minute.map(|n| Bucket::new(n, now)),
+            tokens: limit.tokens_per_minute.map(|n| Bucket::new(n, now)),
+        }
+    }
+
+    /// Takes one request and `estimate` tokens if both are available,
+    /// otherwise returns how long to wait before trying again.
+    fn try_take(&mut self, estimate: u32, now: Instant) -> Option<Duration> {
+        let mut wait = Duration::ZERO;
+        if let Some(requests) = &mut self.requests {
+            requests.refill(now);
+            wait = wait.max(requests.wait_for(1.0));
+        }
+        if let Some(tokens) = &mut self.tokens {
+            tokens.refill(now);
+            wait = wait.max(tokens.wait_for(f64::from(estimate)));
+        }
+        if !wait.is_zero() {
+            return Some(wait);
+        }
+        if let Some(requests) = &mut self.requests {
+            requests.take(1.0);
+        }
+        if let Some(tokens) = &mut self.tokens {
+            tokens.take(f64::from(estimate));
+        }
+        None
+    }
+}
+
+/// A bucket holding up to one minute of capacity, refilled continuously.
+///
+/// The level may go negative when actual usage exceeds what was reserved.
+struct Bucket {
+    capacity: f64,
+    level: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        let capacity = f64::from(per_minute.max(1));
+        Self {
+            capacity,
+            level: capacity,
+            updated: now,
+        }
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.capacity / 60.0
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.level = (self.level + elapsed * self.refill_per_sec()).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Time until `amount` (capped at capacity) is available.
+    fn wait_for(&self, amount: f64) -> Duration {
+        let needed = amount.min(self.capacity) - self.level;
+        if needed <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(needed / self.refill_per_sec())
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.level = (self.level - amount).min(self.capacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(model: &str) -> InteractionRequest {
+        InteractionRequest {
+            model: Some(model.to_string()),
+            agent: None,
+            agent_config: None,
+            input: crate::InteractionInput::Text("Hello".to_string()),
+            previous_interaction_id: None,
+            tools: None,
+            response_modalities: None,
+            response_format: None,
+            response_mime_type: None,
+            generation_config: None,
+            stream: None,
+            background: None,
+            store: None,
+            system_instruction: None,
+        }
+    }
+
+    fn usage(total: u32) -> UsageMetadata {
+        UsageMetadata {
+            total_tokens: Some(total),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_per_minute_waits_for_refill() {
+        let limiter = RateLimiter::new(RateLimit::new().with_requests_per_minute(2));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire(&request("m")).await.settle(None);
+        }
+
+        // Two requests fit in the initial burst; the third waits for 1/2 minute
+        assert_eq!(start.elapsed(), Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_models_have_separate_buckets() {
+        let limiter = RateLimiter::new(RateLimit::new().with_requests_per_minute(1))
+            .with_model_limit("fast", RateLimit::new().with_requests_per_minute(100));
+        let start = Instant::now();
+
+        limiter.acquire(&request("a")).await.settle(None);
+        limiter.acquire(&request("b")).await.settle(None);
+        for _ in 0..50 {
+            limiter.acquire(&request("fast")).await.settle(None);
+        }
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokens_debited_from_usage() {
+        let limiter = RateLimiter::new(RateLimit::new().with_tokens_per_minute(600));
+        let start = Instant::now();
+
+        // 900 tokens against a 600 budget puts the bucket 300 in debt
+        limiter
+            .acquire(&request("m"))
+            .await
+            .settle(Some(&usage(900)));
+        limiter.acquire(&request("m")).await.settle(None);
+
+        // Refill is 10 tokens/sec, so clearing the debt takes 30s
+        assert_eq!(start.elapsed(), Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_estimate_is_pre_debited_and_corrected() {
+        let limiter = RateLimiter::new(RateLimit::new().with_tokens_per_minute(600))
+            .with_token_estimator(|_| 400);
+        let start = Instant::now();
+
+        let first = limiter.acquire(&request("m")).await;
+        // Only 200 left, so the second request waits for 200 more (20s)
+        let second = limiter.acquire(&request("m")).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(20));
+
+        // Actual usage was lower than estimated: 300 tokens are credited back
+        first.settle(Some(&usage(100)));
+        second.settle(Some(&usage(100)));
+        limiter.acquire(&request("m")).await.settle(None);
+        assert_eq!(start.elapsed(), Duration::from_secs(20));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clones_share_buckets() {
+        let limiter = RateLimiter::new(RateLimit::new().with_requests_per_minute(1));
+        let clone = limiter.clone();
+        let start = Instant::now();
+
+        limiter.acquire(&request("m")).await.settle(None);
+        clone.acquire(&request("m")).await.settle(None);
+
+        assert_eq!(start.elapsed(), Duration::from_secs(60));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_estimate_larger_than_capacity_does_not_block_forever() {
+        let limiter = RateLimiter::new(RateLimit::new().with_tokens_per_minute(100))
+            .with_token_estimator(|_| 1_000);
+
+        limiter.acquire(&request("m")).await.settle(None);
+    }
+
+    #[test]
+    fn test_debug_omits_estimator() {
+        let limiter = RateLimiter::new(RateLimit::new()).with_token_estimator(|_| 1);
+        let debug = format!("{limiter:?}");
+        assert!(debug.contains("has_estimator: true"));
+    }
+}
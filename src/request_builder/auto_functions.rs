@@ -6,17 +6,19 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use crate::{InteractionInput, InteractionResponse, StreamChunk, UsageMetadata};
+use crate::{InteractionInput, InteractionResponse, StreamChunk, Turn, UsageMetadata};
 use futures_util::StreamExt;
 use futures_util::stream::BoxStream;
 use serde_json::{Value, json};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, warn};
 
 use crate::Content;
 use crate::GenaiError;
 use crate::ToolService;
+use crate::approval::{ApprovalDecision, ApprovalPolicy};
 use crate::function_calling::{CallableFunction, FunctionRegistry, get_global_function_registry};
 use crate::streaming::{
     AutoFunctionResult, AutoFunctionStreamChunk, AutoFunctionStreamEvent, FunctionExecutionResult,
@@ -28,6 +30,9 @@ use super::InteractionBuilder;
 /// Default maximum iterations for auto function calling.
 pub(crate) const DEFAULT_MAX_FUNCTION_CALL_LOOPS: usize = 5;
 
+/// Default number of function calls from one turn that execute concurrently.
+pub(crate) const DEFAULT_MAX_PARALLEL_FUNCTION_CALLS: usize = 8;
+
 /// Validates that a function call has a call_id and returns it.
 fn validate_call_id(call_id: Option<&str>, function_name: &str) -> Result<String, GenaiError> {
     call_id
@@ -112,42 +117,26 @@ fn auto_discover_tools(
 
 /// Executes a function by looking it up in the service map first, then the global registry.
 ///
-/// Returns the function result as JSON. Errors are converted to JSON error objects
-/// rather than failing the entire operation, allowing the model to recover gracefully.
+/// Returns the function result as JSON and whether the call timed out. Errors are
+/// converted to JSON error objects rather than failing the entire operation,
+/// allowing the model to recover gracefully.
+///
+/// The function's own [`timeout()`](CallableFunction::timeout) takes precedence
+/// over `default_timeout`. On timeout, `cancel` is cancelled and the call is dropped.
 async fn execute_function(
     name: &str,
     args: Value,
+    default_timeout: Option<Duration>,
+    cancel: CancellationToken,
     service_functions: &HashMap<String, Arc<dyn CallableFunction>>,
     function_registry: &FunctionRegistry,
-) -> Value {
+) -> (Value, bool) {
     // Function lookup order: tool service first (for dependency-injected functions),
     // then global registry (for #[tool] macro functions).
-    if let Some(function) = service_functions.get(name) {
-        // Found in tool service (dependency-injected)
-        match function.call(args).await {
-            Ok(result) => result,
-            Err(e) => {
-                warn!(
-                    "Function execution failed (recoverable): function='{}', error='{}'. \
-                     The error will be sent to the model, which may retry or adapt.",
-                    name, e
-                );
-                json!({ "error": e.to_string() })
-            }
-        }
+    let function: &dyn CallableFunction = if let Some(function) = service_functions.get(name) {
+        function.as_ref()
     } else if let Some(function) = function_registry.get(name) {
-        // Found in global registry (#[tool] macro)
-        match function.call(args).await {
-            Ok(result) => result,
-            Err(e) => {
-                warn!(
-                    "Function execution failed (recoverable): function='{}', error='{}'. \
-                     The error will be sent to the model, which may retry or adapt.",
-                    name, e
-                );
-                json!({ "error": e.to_string() })
-            }
-        }
+        function
     } else {
         // Function not found anywhere - could be a typo in declarations or missing #[tool] macro.
         // We inform the model rather than failing, allowing it to adapt or use other functions.
@@ -155,10 +144,165 @@ async fn execute_function(
             "Function not found in registry or tool service: function='{}'. Informing model.",
             name
         );
-        json!({ "error": format!("Function '{}' is not available or not found.", name) })
+        return (
+            json!({ "error": format!("Function '{}' is not available or not found.", name) }),
+            false,
+        );
+    };
+
+    let call = function.call_with_cancellation(args, cancel.clone());
+    let outcome = match function.timeout().or(default_timeout) {
+        Some(limit) => match tokio::time::timeout(limit, call).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                cancel.cancel();
+                warn!(
+                    "Function execution timed out (recoverable): function='{}', timeout={:?}. \
+                     The error will be sent to the model, which may retry or adapt.",
+                    name, limit
+                );
+                return (
+                    json!({
+                        "error": format!("Function '{}' timed out after {:?}", name, limit),
+                        "timed_out": true,
+                    }),
+                    true,
+                );
+            }
+        },
+        None => call.await,
+    };
+
+    match outcome {
+        Ok(result) => (result, false),
+        Err(e) => {
+            warn!(
+                "Function execution failed (recoverable): function='{}', error='{}'. \
+                 The error will be sent to the model, which may retry or adapt.",
+                name, e
+            );
+            (json!({ "error": e.to_string() }), false)
+        }
+    }
+}
+
+/// A function call after review by the approval policy.
+enum ReviewedCall {
+    /// `(call_id, name, args)` to execute, with any modified arguments applied
+    Approved(String, String, Value),
+    /// Recorded as an error result without executing
+    Denied(FunctionExecutionResult),
+}
+
+/// Applies the approval policy's decision to a `(call_id, name, args)` call.
+///
+/// Without a policy every call is approved as requested.
+async fn review_function_call(
+    policy: Option<&dyn ApprovalPolicy>,
+    (call_id, name, args): (String, String, Value),
+) -> ReviewedCall {
+    let Some(policy) = policy else {
+        return ReviewedCall::Approved(call_id, name, args);
+    };
+    let decision = policy
+        .review(&PendingFunctionCall::new(&name, &call_id, args.clone()))
+        .await;
+    match decision {
+        ApprovalDecision::Approve => ReviewedCall::Approved(call_id, name, args),
+        ApprovalDecision::ModifyArgs(modified) => {
+            debug!("Approval policy modified arguments for function '{}'", name);
+            ReviewedCall::Approved(call_id, name, modified)
+        }
+        ApprovalDecision::Deny { reason } => {
+            debug!(
+                "Approval policy denied function '{}': {}. Informing model.",
+                name, reason
+            );
+            let result = json!({
+                "error": format!("Function '{}' was denied: {}", name, reason),
+                "denied": true,
+            });
+            ReviewedCall::Denied(
+                FunctionExecutionResult::new(name, call_id, args, result, Duration::ZERO)
+                    .with_denied(true),
+            )
+        }
     }
 }
 
+/// Executes the function calls from one model turn, up to `max_parallel` at a time.
+///
+/// Each approved call gets a child of `cancel`; denied calls pass through as
+/// their recorded results. Results are returned in call order regardless of
+/// which function finishes first, and each `duration` covers only that
+/// function's own execution.
+async fn execute_function_calls(
+    calls: Vec<ReviewedCall>,
+    max_parallel: usize,
+    function_timeout: Option<Duration>,
+    cancel: &CancellationToken,
+    service_functions: &HashMap<String, Arc<dyn CallableFunction>>,
+    function_registry: &FunctionRegistry,
+) -> Vec<FunctionExecutionResult> {
+    debug!(
+        "Executing {} function call(s), up to {} at a time",
+        calls.len(),
+        max_parallel
+    );
+    // Owned items keep the futures free of higher-ranked borrows, which
+    // `async_stream` cannot prove `Send`
+    futures_util::stream::iter(calls)
+        .map(|call| {
+            let cancel = cancel.child_token();
+            async move {
+                let (call_id, name, args) = match call {
+                    ReviewedCall::Approved(call_id, name, args) => (call_id, name, args),
+                    ReviewedCall::Denied(result) => return result,
+                };
+                let start = Instant::now();
+                let (result, timed_out) = execute_function(
+                    &name,
+                    args.clone(),
+                    function_timeout,
+                    cancel,
+                    service_functions,
+                    function_registry,
+                )
+                .await;
+                let duration = start.elapsed();
+                debug!("Function '{}' executed in {:?}", name, duration);
+                FunctionExecutionResult::new(name, call_id, args, result, duration)
+                    .with_timed_out(timed_out)
+            }
+        })
+        .buffered(max_parallel.max(1))
+        .collect()
+        .await
+}
+
+/// Converts a request's input into the opening turns of a stateless transcript.
+fn input_turns(input: &InteractionInput) -> Vec<Turn> {
+    match input {
+        InteractionInput::Text(text) => vec![Turn::user(text.as_str())],
+        InteractionInput::Content(content) => vec![Turn::user(content.clone())],
+        InteractionInput::Turns(turns) => turns.clone(),
+    }
+}
+
+/// Converts executions into the function result content sent back to the model.
+fn function_results_content(executions: &[FunctionExecutionResult]) -> Vec<Content> {
+    executions
+        .iter()
+        .map(|execution| {
+            Content::function_result(
+                execution.name.clone(),
+                execution.call_id.clone(),
+                execution.result.clone(),
+            )
+        })
+        .collect()
+}
+
 impl<'a> InteractionBuilder<'a> {
     /// Creates interaction with automatic function call handling.
     ///
@@ -184,10 +328,39 @@ impl<'a> InteractionBuilder<'a> {
     ///
     /// See <https://ai.google.dev/gemini-api/docs/thought-signatures> for more details.
     ///
-    /// # Runtime Validation
+    /// # Stateless Mode
+    ///
+    /// With [`with_store_disabled()`](InteractionBuilder::with_store_disabled), nothing is
+    /// stored server-side and turns are not chained. Instead, each round resends the whole
+    /// conversation as [`Turn`]s: the original input, every model turn with its function
+    /// calls and thought signatures, and every function result turn. The conversation,
+    /// ending with the final model turn, is returned in
+    /// [`AutoFunctionResult::transcript`] for you to persist and pass to
+    /// [`with_history()`](InteractionBuilder::with_history) on the next turn.
+    ///
+    /// ```no_run
+    /// # use genai_rs::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("key".to_string());
+    /// let result = client.interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("What's the weather in Tokyo?")
+    ///     .with_store_disabled()
+    ///     .create_with_auto_functions()
+    ///     .await?;
     ///
-    /// This method requires storage to be enabled. If you've called `with_store_disabled()`,
-    /// this method will return an error with a helpful message explaining why and how to fix it.
+    /// let history = result.transcript.unwrap_or_default();
+    /// let follow_up = client.interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_history(history)
+    ///     .with_text("And in Osaka?")
+    ///     .with_store_disabled()
+    ///     .create_with_auto_functions()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
     /// # Example
     /// ```no_run
@@ -253,7 +426,8 @@ impl<'a> InteractionBuilder<'a> {
     /// **per-API-call**, not to the total operation or function execution time. Each round
     /// of model interaction must complete within the timeout, but:
     ///
-    /// - Function execution time is **not** counted against the timeout
+    /// - Function execution time is **not** counted against the timeout; limit each
+    ///   call with [`with_function_timeout()`](InteractionBuilder::with_function_timeout)
     /// - Multiple API calls may occur (one per function-calling round)
     /// - For a total timeout, wrap the call in `tokio::time::timeout()`
     ///
@@ -295,15 +469,24 @@ impl<'a> InteractionBuilder<'a> {
     ///   a partial `AutoFunctionResult`. Use `previous_interaction_id` to continue.
     /// - `max_function_call_loops` is set to 0 (invalid configuration)
     pub async fn create_with_auto_functions(self) -> Result<AutoFunctionResult, GenaiError> {
-        // Runtime validation: auto-functions require storage
-        self.validate_for_auto_functions()?;
-
         let client = self.client;
         let timeout = self.timeout;
         let max_loops = self.max_function_call_loops;
+        let max_parallel = self.max_parallel_function_calls;
+        let function_timeout = self.function_timeout;
+        let approval_policy = self.approval_policy.clone();
         let tool_service = self.tool_service.clone();
+        let retry_policy = self.effective_retry_policy();
         let mut request = self.build()?;
 
+        // Without storage, resend the whole conversation each round
+        let stateless = request.store == Some(false);
+        let mut transcript = stateless.then(|| input_turns(&request.input));
+
+        // Cancels in-flight functions' tokens if this future is dropped
+        let cancel = CancellationToken::new();
+        let _cancel_on_drop = cancel.clone().drop_guard();
+
         // Track all function executions for the result
         let mut all_executions: Vec<FunctionExecutionResult> = Vec::new();
 
@@ -333,19 +516,24 @@ impl<'a> InteractionBuilder<'a> {
             // Apply per-API-call timeout if set (function execution time not included)
             let response = match timeout {
                 Some(duration) => {
-                    let future = client.execute(request.clone());
+                    let future =
+                        client.execute_with_retry_policy(request.clone(), retry_policy.as_ref());
                     tokio::time::timeout(duration, future).await.map_err(|_| {
                         warn!("Auto-function API call timed out after {:?}", duration);
                         GenaiError::Timeout(duration)
                     })??
                 }
-                None => client.execute(request.clone()).await?,
+                None => {
+                    client
+                        .execute_with_retry_policy(request.clone(), retry_policy.as_ref())
+                        .await?
+                }
             };
 
-            // When store != false (validated at function entry), the API should always
-            // return an interaction ID. Return an error if the API violates this contract,
-            // as continuing would silently lose conversation context.
-            if response.id.is_none() {
+            // When store != false, the API should always return an interaction ID.
+            // Return an error if the API violates this contract, as continuing would
+            // silently lose conversation context.
+            if !stateless && response.id.is_none() {
                 return Err(GenaiError::MalformedResponse(
                     "Response missing interaction ID. Auto-function calling requires stored \
                      interactions (store != false) to maintain conversation context."
@@ -364,6 +552,9 @@ impl<'a> InteractionBuilder<'a> {
             // If no function calls, we're done!
             if function_calls.is_empty() {
                 debug!("No function calls in response, completing auto-function loop");
+                if let Some(transcript) = transcript.as_mut() {
+                    transcript.push(Turn::model(response.outputs.clone()));
+                }
                 // Create final response with accumulated usage across all API calls
                 let final_response = InteractionResponse {
                     usage: Some(accumulated_usage),
@@ -373,54 +564,52 @@ impl<'a> InteractionBuilder<'a> {
                     response: final_response,
                     executions: all_executions,
                     reached_max_loops: false,
+                    transcript,
                 });
             }
 
-            // Build function results for next iteration
-            let mut function_results = Vec::new();
-            debug!("Executing {} function call(s)", function_calls.len());
-
-            for call in function_calls {
-                // Validate that we have a call_id (required by API)
+            // Validate that every call has a call_id (required by API) before executing any
+            let mut calls = Vec::with_capacity(function_calls.len());
+            for call in &function_calls {
                 let call_id = validate_call_id(call.id, call.name)?;
+                calls.push((call_id, call.name.to_string(), call.args.clone()));
+            }
 
-                // Execute the function with timing
-                let start = Instant::now();
-                let result = execute_function(
-                    call.name,
-                    call.args.clone(),
-                    &service_functions,
-                    function_registry,
-                )
-                .await;
-                let duration = start.elapsed();
-                debug!("Function '{}' executed in {:?}", call.name, duration);
-
-                // Track execution for the result
-                all_executions.push(FunctionExecutionResult::new(
-                    call.name,
-                    &call_id,
-                    call.args.clone(),
-                    result.clone(),
-                    duration,
-                ));
-
-                // Add function result (only the result, not the call - server has it via previous_interaction_id)
-                function_results.push(Content::function_result(
-                    call.name.to_string(),
-                    call_id,
-                    result,
-                ));
+            // Review every call before executing any, in the order requested
+            let mut reviewed = Vec::with_capacity(calls.len());
+            for call in calls {
+                reviewed.push(review_function_call(approval_policy.as_deref(), call).await);
             }
 
+            let executions = execute_function_calls(
+                reviewed,
+                max_parallel,
+                function_timeout,
+                &cancel,
+                &service_functions,
+                function_registry,
+            )
+            .await;
+
+            // Send only the results, not the calls - server has them via previous_interaction_id
+            let function_results = function_results_content(&executions);
+            all_executions.extend(executions);
+
             // Save this response before moving to next iteration
             // (in case we hit max loops, we want to return the last response)
             last_response = Some(response.clone());
 
-            // Create new request with function results
-            // The server maintains function call context via previous_interaction_id
-            request.previous_interaction_id = response.id;
-            request.input = InteractionInput::Content(function_results);
+            if let Some(transcript) = transcript.as_mut() {
+                // Resend the model's turn (with thought signatures) and the results
+                transcript.push(Turn::model(response.outputs));
+                transcript.push(Turn::user(function_results));
+                request.input = InteractionInput::Turns(transcript.clone());
+            } else {
+                // Create new request with function results
+                // The server maintains function call context via previous_interaction_id
+                request.previous_interaction_id = response.id;
+                request.input = InteractionInput::Content(function_results);
+            }
         }
 
         // Max loops reached - return partial result with whatever we have
@@ -450,6 +639,7 @@ impl<'a> InteractionBuilder<'a> {
             response: final_response,
             executions: all_executions,
             reached_max_loops: true,
+            transcript,
         })
     }
 
@@ -460,8 +650,11 @@ impl<'a> InteractionBuilder<'a> {
     /// [`AutoFunctionStreamChunk`] events that include:
     ///
     /// - `Delta`: Incremental content from the model (text, thoughts, etc.)
+    /// - `AwaitingApproval`: A function call waiting on the approval policy, if one
+    ///   was set with [`with_approval_policy()`](InteractionBuilder::with_approval_policy)
     /// - `ExecutingFunctions`: Notification when function calls are about to execute
     /// - `FunctionResults`: Results from executed functions
+    /// - `Transcript`: The full conversation, only with `with_store_disabled()`
     /// - `Complete`: Final response when no more function calls are needed
     ///
     /// The stream automatically handles multiple function calling rounds, streaming
@@ -517,13 +710,23 @@ impl<'a> InteractionBuilder<'a> {
     /// The `AutoFunctionResultAccumulator` handles `MaxLoopsReached` automatically
     /// and returns an `AutoFunctionResult` with `reached_max_loops: true`.
     ///
+    /// # Stateless Mode
+    ///
+    /// With [`with_store_disabled()`](InteractionBuilder::with_store_disabled), each round
+    /// resends the whole conversation instead of chaining interactions, as described for
+    /// [`create_with_auto_functions()`](InteractionBuilder::create_with_auto_functions).
+    /// A `Transcript` chunk with the full conversation is yielded just before the
+    /// `Complete` or `MaxLoopsReached` chunk, and `AutoFunctionResultAccumulator`
+    /// copies it into [`AutoFunctionResult::transcript`].
+    ///
     /// # Timeout Behavior
     ///
     /// If [`with_timeout()`](InteractionBuilder::with_timeout) was set, the timeout applies
     /// **per-chunk**, not to the total stream or function execution time. Each chunk must
     /// arrive within the timeout (detecting stalled connections), but:
     ///
-    /// - Function execution time is **not** counted against the timeout
+    /// - Function execution time is **not** counted against the timeout; limit each
+    ///   call with [`with_function_timeout()`](InteractionBuilder::with_function_timeout)
     /// - Multiple streaming rounds may occur (one per function-calling round)
     /// - For a total timeout, wrap the stream consumption in `tokio::time::timeout()`
     ///
@@ -566,20 +769,28 @@ impl<'a> InteractionBuilder<'a> {
     pub fn create_stream_with_auto_functions(
         self,
     ) -> BoxStream<'a, Result<AutoFunctionStreamEvent, GenaiError>> {
-        // Runtime validation: auto-functions require storage
-        // We do this early so errors are returned immediately, not mid-stream
-        if let Err(e) = self.validate_for_auto_functions() {
-            return Box::pin(futures_util::stream::once(async move { Err(e) }));
-        }
-
         let client = self.client;
         let max_loops = self.max_function_call_loops;
+        let max_parallel = self.max_parallel_function_calls;
+        let function_timeout = self.function_timeout;
+        let approval_policy = self.approval_policy.clone();
         let tool_service = self.tool_service.clone();
         let timeout = self.timeout;
+        let retry_policy = self.effective_retry_policy();
+        let resume_policy = self.resume_policy.clone();
+        let stall_policy = self.stall_policy.clone();
 
         Box::pin(async_stream::try_stream! {
             let mut request = self.build()?;
 
+            // Without storage, resend the whole conversation each round
+            let stateless = request.store == Some(false);
+            let mut transcript = stateless.then(|| input_turns(&request.input));
+
+            // Cancels in-flight functions' tokens if this stream is dropped
+            let cancel = CancellationToken::new();
+            let _cancel_on_drop = cancel.clone().drop_guard();
+
             // Build a map of service-provided functions for lookup during execution
             let service_functions = build_service_function_map(&tool_service);
 
@@ -602,7 +813,12 @@ impl<'a> InteractionBuilder<'a> {
                 request.stream = Some(true);
 
                 // Stream this iteration's response
-                let mut stream = client.execute_stream(request.clone());
+                let mut stream = client.execute_stream_with_retry_policy(
+                    request.clone(),
+                    retry_policy.clone(),
+                    resume_policy.clone(),
+                    stall_policy.clone(),
+                );
                 let mut complete_response: Option<InteractionResponse> = None;
                 // Accumulate function calls from deltas (streaming API may not include them in Complete)
                 let mut accumulated_calls: Vec<(Option<String>, String, serde_json::Value)> = Vec::new();
@@ -672,10 +888,10 @@ impl<'a> InteractionBuilder<'a> {
                     accumulated_usage.accumulate(usage);
                 }
 
-                // When store != false (validated at function entry), the API should always
-                // return an interaction ID. Return an error if the API violates this contract,
-                // as continuing would silently lose conversation context.
-                if response.id.is_none() {
+                // When store != false, the API should always return an interaction ID.
+                // Return an error if the API violates this contract, as continuing would
+                // silently lose conversation context.
+                if !stateless && response.id.is_none() {
                     Err(GenaiError::MalformedResponse(
                         "Response missing interaction ID. Auto-function calling requires stored \
                          interactions (store != false) to maintain conversation context."
@@ -698,6 +914,14 @@ impl<'a> InteractionBuilder<'a> {
                 // If no function calls, we're done!
                 if !has_function_calls {
                     debug!("No function calls in response, completing auto-function streaming loop");
+                    if let Some(mut transcript) = transcript.take() {
+                        transcript.push(Turn::model(response.outputs.clone()));
+                        // Transcript is client-generated, no API event_id
+                        yield AutoFunctionStreamEvent::new(
+                            AutoFunctionStreamChunk::Transcript(transcript),
+                            None,
+                        );
+                    }
                     // Create final response with accumulated usage across all API calls
                     let final_response = InteractionResponse {
                         usage: Some(accumulated_usage),
@@ -729,58 +953,61 @@ impl<'a> InteractionBuilder<'a> {
                     calls
                 };
 
+                // The model's turn to resend in stateless mode. The streamed
+                // Complete response carries every delta of the turn, including
+                // thoughts and their signatures, whether or not it had an index.
+                let model_turn: Option<Vec<Content>> = stateless.then(|| response.outputs.clone());
+
+                // Review every call before executing any, letting the consumer
+                // prompt the user for each (client-generated, no API event_id)
+                let mut reviewed = Vec::with_capacity(calls_to_execute.len());
+                for (call_id, name, args) in calls_to_execute {
+                    if approval_policy.is_some() {
+                        yield AutoFunctionStreamEvent::new(
+                            AutoFunctionStreamChunk::AwaitingApproval(
+                                PendingFunctionCall::new(&name, &call_id, args.clone()),
+                            ),
+                            None,
+                        );
+                    }
+                    reviewed.push(
+                        review_function_call(approval_policy.as_deref(), (call_id, name, args)).await,
+                    );
+                }
+
                 // Signal that we're executing functions with pending call info
-                debug!("Executing {} function call(s)", calls_to_execute.len());
-                let pending_calls: Vec<PendingFunctionCall> = calls_to_execute
+                let pending_calls: Vec<PendingFunctionCall> = reviewed
                     .iter()
-                    .map(|(call_id, name, args)| PendingFunctionCall::new(name, call_id, args.clone()))
+                    .filter_map(|call| match call {
+                        ReviewedCall::Approved(call_id, name, args) => {
+                            Some(PendingFunctionCall::new(name, call_id, args.clone()))
+                        }
+                        ReviewedCall::Denied(_) => None,
+                    })
                     .collect();
-                // ExecutingFunctions is client-generated, no API event_id
-                yield AutoFunctionStreamEvent::new(
-                    AutoFunctionStreamChunk::ExecutingFunctions {
-                        response: response.clone(),
-                        pending_calls,
-                    },
-                    None,
-                );
-
-                // Build function results for next iteration
-                let mut function_results_content = Vec::new();
-                let mut execution_results = Vec::new();
-
-                for (call_id, name, args) in &calls_to_execute {
-                    // Execute the function with timing
-                    let start = Instant::now();
-                    let result = execute_function(
-                        name,
-                        args.clone(),
-                        &service_functions,
-                        function_registry,
-                    )
-                    .await;
-                    let duration = start.elapsed();
-                    debug!(
-                        "Function '{}' executed in {:?}",
-                        name, duration
+                if !pending_calls.is_empty() {
+                    // ExecutingFunctions is client-generated, no API event_id
+                    yield AutoFunctionStreamEvent::new(
+                        AutoFunctionStreamChunk::ExecutingFunctions {
+                            response: response.clone(),
+                            pending_calls,
+                        },
+                        None,
                     );
-
-                    // Track result for yielding
-                    execution_results.push(FunctionExecutionResult::new(
-                        name.clone(),
-                        call_id.clone(),
-                        args.clone(),
-                        result.clone(),
-                        duration,
-                    ));
-
-                    // Add function result content for API
-                    function_results_content.push(Content::function_result(
-                        name.clone(),
-                        call_id.clone(),
-                        result,
-                    ));
                 }
 
+                // Build function results for next iteration
+                let execution_results = execute_function_calls(
+                    reviewed,
+                    max_parallel,
+                    function_timeout,
+                    &cancel,
+                    &service_functions,
+                    function_registry,
+                )
+                .await;
+                let function_results_content = function_results_content(&execution_results);
+
                 // Yield function results (client-generated, no API event_id)
                 yield AutoFunctionStreamEvent::new(
                     AutoFunctionStreamChunk::FunctionResults(execution_results),
@@ -791,9 +1018,16 @@ impl<'a> InteractionBuilder<'a> {
                 // (in case we hit max loops, we want to return the last response)
                 last_response = Some(response.clone());
 
-                // Create new request with function results
-                request.previous_interaction_id = response.id;
-                request.input = InteractionInput::Content(function_results_content);
+                if let (Some(transcript), Some(model_turn)) = (transcript.as_mut(), model_turn) {
+                    // Resend the model's turn (with thought signatures) and the results
+                    transcript.push(Turn::model(model_turn));
+                    transcript.push(Turn::user(function_results_content));
+                    request.input = InteractionInput::Turns(transcript.clone());
+                } else {
+                    // Create new request with function results
+                    request.previous_interaction_id = response.id;
+                    request.input = InteractionInput::Content(function_results_content);
+                }
             }
 
             // Max loops reached - yield partial result with the last response
@@ -818,6 +1052,13 @@ impl<'a> InteractionBuilder<'a> {
                 ..response
             };
 
+            if let Some(transcript) = transcript.take() {
+                yield AutoFunctionStreamEvent::new(
+                    AutoFunctionStreamChunk::Transcript(transcript),
+                    None,
+                );
+            }
+
             // MaxLoopsReached is client-generated (loop limit hit), no API event_id
             yield AutoFunctionStreamEvent::new(
                 AutoFunctionStreamChunk::MaxLoopsReached(final_response),
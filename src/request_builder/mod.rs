@@ -1,10 +1,14 @@
 mod auto_functions;
 
-use auto_functions::DEFAULT_MAX_FUNCTION_CALL_LOOPS;
+use auto_functions::{DEFAULT_MAX_FUNCTION_CALL_LOOPS, DEFAULT_MAX_PARALLEL_FUNCTION_CALLS};
 
 use crate::GenaiError;
+use crate::approval::ApprovalPolicy;
+use crate::cancel_guard::CancelOnDrop;
 use crate::client::Client;
 use crate::function_calling::ToolService;
+use crate::retry::RetryPolicy;
+use crate::stall::StallPolicy;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::debug;
@@ -28,7 +32,6 @@ use futures_util::{StreamExt, stream::BoxStream};
 /// The following combinations are invalid and will return an error:
 /// - `with_store_disabled()` + `with_previous_interaction()`: chained interactions require storage
 /// - `with_store_disabled()` + `with_background(true)`: background execution requires storage
-/// - `with_store_disabled()` + `create_with_auto_functions()`: auto-function calling requires storage
 ///
 /// # Examples
 ///
@@ -96,10 +99,24 @@ pub struct InteractionBuilder<'a> {
     system_instruction: Option<InteractionInput>,
     /// Maximum iterations for auto function calling loop
     max_function_call_loops: usize,
+    /// Maximum function calls from one turn executed concurrently
+    max_parallel_function_calls: usize,
+    /// Default execution timeout for each auto-executed function call
+    function_timeout: Option<Duration>,
+    /// Reviews each auto-executed function call before it runs
+    approval_policy: Option<Arc<dyn ApprovalPolicy>>,
     /// Tool service for dependency-injected functions
     tool_service: Option<Arc<dyn ToolService>>,
     /// Optional timeout for the request
     timeout: Option<Duration>,
+    /// Per-request retry policy (overrides the client's policy)
+    retry_policy: Option<RetryPolicy>,
+    /// Backoff for resuming dropped streams (None = errors end the stream)
+    resume_policy: Option<RetryPolicy>,
+    /// Idle thresholds for streams (None = only `timeout` applies)
+    stall_policy: Option<StallPolicy>,
+    /// Whether `create_stream()` cancels the interaction when dropped early
+    cancel_on_drop: bool,
 }
 
 impl std::fmt::Debug for InteractionBuilder<'_> {
@@ -122,8 +139,18 @@ impl std::fmt::Debug for InteractionBuilder<'_> {
             .field("store", &self.store)
             .field("system_instruction", &self.system_instruction)
             .field("max_function_call_loops", &self.max_function_call_loops)
+            .field(
+                "max_parallel_function_calls",
+                &self.max_parallel_function_calls,
+            )
+            .field("function_timeout", &self.function_timeout)
+            .field("approval_policy", &self.approval_policy)
             .field("tool_service", &self.tool_service.as_ref().map(|_| "..."))
             .field("timeout", &self.timeout)
+            .field("retry_policy", &self.retry_policy)
+            .field("resume_policy", &self.resume_policy)
+            .field("stall_policy", &self.stall_policy)
+            .field("cancel_on_drop", &self.cancel_on_drop)
             .finish()
     }
 }
@@ -154,11 +181,26 @@ impl<'a> InteractionBuilder<'a> {
             store: None,
             system_instruction: None,
             max_function_call_loops: DEFAULT_MAX_FUNCTION_CALL_LOOPS,
+            max_parallel_function_calls: DEFAULT_MAX_PARALLEL_FUNCTION_CALLS,
+            function_timeout: None,
+            approval_policy: None,
             tool_service: None,
             timeout: None,
+            retry_policy: None,
+            resume_policy: None,
+            stall_policy: None,
+            cancel_on_drop: false,
         }
     }
 
+    /// Returns the retry policy for this request: the per-request override if
+    /// set, otherwise the client's default.
+    fn effective_retry_policy(&self) -> Option<RetryPolicy> {
+        self.retry_policy
+            .clone()
+            .or_else(|| self.client.retry_policy.clone())
+    }
+
     /// Validates the builder configuration against API constraints.
     ///
     /// This is called automatically by terminal methods (`build()`, `create()`, etc.).
@@ -194,25 +236,6 @@ impl<'a> InteractionBuilder<'a> {
         Ok(())
     }
 
-    /// Validates for auto-function calling (stricter than general validation).
-    fn validate_for_auto_functions(&self) -> Result<(), GenaiError> {
-        // Base validation first
-        self.validate()?;
-
-        // Auto-functions require storage to maintain context across function calls
-        if self.store == Some(false) {
-            return Err(GenaiError::InvalidInput(
-                "create_with_auto_functions() requires storage to maintain conversation context \
-                 across multiple function execution rounds. \
-                 Solution: Remove .with_store_disabled() to enable storage, \
-                 or use create() for single-turn function handling."
-                    .to_string(),
-            ));
-        }
-
-        Ok(())
-    }
-
     /// References a previous interaction for stateful conversations.
     ///
     /// The interaction will have access to the context from the previous interaction.
@@ -242,9 +265,12 @@ impl<'a> InteractionBuilder<'a> {
     /// This is incompatible with:
     /// - `with_previous_interaction()`: chained interactions require storage
     /// - `with_background(true)`: background execution requires storage
-    /// - `create_with_auto_functions()`: auto-function calling requires storage
     ///
     /// Combining these will return an error from `build()` or `create()`.
+    ///
+    /// `create_with_auto_functions()` and `create_stream_with_auto_functions()`
+    /// still work: they resend the full conversation each round and return it
+    /// as a transcript.
     #[must_use]
     pub fn with_store_disabled(mut self) -> Self {
         self.store = Some(false);
@@ -1660,6 +1686,137 @@ impl<'a> InteractionBuilder<'a> {
         self
     }
 
+    /// Sets how many function calls from one model turn execute concurrently
+    /// in `create_with_auto_functions()` and `create_stream_with_auto_functions()`.
+    ///
+    /// When the model requests several functions at once, they run in parallel
+    /// up to this limit, so a turn takes about as long as its slowest call.
+    /// Results are always sent back and reported in the order the model
+    /// requested them. Default is 8; use 1 to execute calls one at a time.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use genai_rs::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder("api_key".to_string()).build()?;
+    ///
+    /// let result = client.interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("Compare the weather in Tokyo, Paris and Lima")
+    ///     .with_max_parallel_function_calls(2)  // At most 2 calls in flight
+    ///     .create_with_auto_functions()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_parallel_function_calls(mut self, max_parallel: usize) -> Self {
+        if max_parallel == 0 {
+            tracing::warn!(
+                "max_parallel_function_calls set to 0 - function calls will execute one at a time"
+            );
+        }
+        self.max_parallel_function_calls = max_parallel.max(1);
+        self
+    }
+
+    /// Sets how long each auto-executed function call may run.
+    ///
+    /// Applies to `create_with_auto_functions()` and
+    /// `create_stream_with_auto_functions()`. A call that exceeds it is
+    /// dropped, its [`CancellationToken`](crate::CancellationToken) is
+    /// cancelled, and the model receives
+    /// `{"error": "Function '...' timed out after ...", "timed_out": true}`
+    /// so it can retry or adapt. The [`FunctionExecutionResult`](crate::FunctionExecutionResult)
+    /// has `timed_out` set.
+    ///
+    /// A function's own [`timeout()`](crate::CallableFunction::timeout), e.g.
+    /// from `#[tool(timeout_secs = 10)]`, takes precedence. By default calls
+    /// run without a limit.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use genai_rs::Client;
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder("api_key".to_string()).build()?;
+    ///
+    /// let result = client.interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("Fetch the latest headlines")
+    ///     .with_function_timeout(Duration::from_secs(20))
+    ///     .create_with_auto_functions()
+    ///     .await?;
+    ///
+    /// for execution in result.executions.iter().filter(|e| e.timed_out) {
+    ///     eprintln!("{} timed out", execution.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_function_timeout(mut self, timeout: Duration) -> Self {
+        self.function_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a policy that approves, denies, or rewrites each auto-executed function call.
+    ///
+    /// Applies to `create_with_auto_functions()` and
+    /// `create_stream_with_auto_functions()`. Every call the model requests is
+    /// passed to [`ApprovalPolicy::review()`] before execution. A denied call
+    /// is not executed: the model receives
+    /// `{"error": "Function '...' was denied: <reason>", "denied": true}`
+    /// and the [`FunctionExecutionResult`](crate::FunctionExecutionResult)
+    /// has `denied` set. The streaming variant yields
+    /// [`AwaitingApproval`](crate::AutoFunctionStreamChunk::AwaitingApproval)
+    /// before each review.
+    ///
+    /// See the [`approval`](crate::approval) module for details.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use async_trait::async_trait;
+    /// # use genai_rs::{ApprovalDecision, ApprovalPolicy, Client, PendingFunctionCall};
+    /// #[derive(Debug)]
+    /// struct NoDeletes;
+    ///
+    /// #[async_trait]
+    /// impl ApprovalPolicy for NoDeletes {
+    ///     async fn review(&self, call: &PendingFunctionCall) -> ApprovalDecision {
+    ///         if call.name == "delete_record" {
+    ///             ApprovalDecision::deny("Deleting records requires a human")
+    ///         } else {
+    ///             ApprovalDecision::Approve
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::builder("api_key".to_string()).build()?;
+    ///
+    /// let result = client.interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("Clean up the stale records")
+    ///     .with_approval_policy(NoDeletes)
+    ///     .create_with_auto_functions()
+    ///     .await?;
+    ///
+    /// for execution in result.executions.iter().filter(|e| e.denied) {
+    ///     eprintln!("{} was denied", execution.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_approval_policy(mut self, policy: impl ApprovalPolicy + 'static) -> Self {
+        self.approval_policy = Some(Arc::new(policy));
+        self
+    }
+
     /// Sets a timeout for the request.
     ///
     /// If the request takes longer than the specified duration, it will be
@@ -1706,6 +1863,178 @@ impl<'a> InteractionBuilder<'a> {
         self
     }
 
+    /// Sets the retry policy for this request, overriding the client's policy.
+    ///
+    /// Applies to `create()`, the initial connection of `create_stream()`, and each
+    /// model call made by the auto-function methods. Function executions are never
+    /// retried. Pass [`RetryPolicy::none()`] to disable retries for this request.
+    ///
+    /// When combined with [`with_timeout()`](Self::with_timeout), the timeout for
+    /// `create()` covers all attempts including backoff delays.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use genai_rs::{Client, RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("api-key".to_string());
+    ///
+    /// let response = client
+    ///     .interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("Hello!")
+    ///     .with_retry_policy(
+    ///         RetryPolicy::new()
+    ///             .with_max_attempts(5)
+    ///             .with_initial_backoff(Duration::from_secs(1)),
+    ///     )
+    ///     .create()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Automatically resumes streams whose connection drops mid-generation.
+    ///
+    /// Applies to [`create_stream()`](Self::create_stream) and
+    /// [`create_stream_with_auto_functions()`](Self::create_stream_with_auto_functions).
+    /// The stream tracks the interaction ID from [`StreamChunk::Start`] and the
+    /// last received `event_id`. When a retryable error (see
+    /// [`GenaiError::is_retryable()`]) interrupts it, the stream reconnects via
+    /// [`Client::get_interaction_stream()`](crate::Client::get_interaction_stream)
+    /// from that event, waiting between attempts per `policy`, and continues
+    /// yielding events. Events already yielded are never repeated, and the
+    /// final [`StreamChunk::Complete`] still contains all accumulated outputs.
+    ///
+    /// `policy` limits the attempts per disconnect; the count resets once a new
+    /// event arrives. The error is yielded if the policy gives up or the
+    /// connection drops before the interaction ID is known. Resuming requires a
+    /// stored interaction, so it has no effect with `with_store_disabled()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use genai_rs::{Client, RetryPolicy};
+    /// use futures_util::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("api-key".to_string());
+    ///
+    /// let mut stream = client
+    ///     .interaction()
+    ///     .with_model("gemini-3-flash-preview")
+    ///     .with_text("Write a long story")
+    ///     .with_auto_resume(RetryPolicy::new().with_max_attempts(5))
+    ///     .create_stream();
+    ///
+    /// while let Some(event) = stream.next().await {
+    ///     let event = event?;
+    ///     // Deltas continue seamlessly across reconnects
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_auto_resume(mut self, policy: RetryPolicy) -> Self {
+        self.resume_policy = Some(policy);
+        self
+    }
+
+    /// Detects streams that go quiet for too long and recovers them.
+    ///
+    /// Applies to [`create_stream()`](Self::create_stream) and
+    /// [`create_stream_with_auto_functions()`](Self::create_stream_with_auto_functions).
+    /// Unlike the per-chunk [`with_timeout()`](Self::with_timeout), the policy
+    /// allows a longer silence between status updates than between content
+    /// deltas, and on a stall it can probe the interaction with
+    /// [`Client::get_interaction()`](crate::Client::get_interaction) or
+    /// reconnect via [`Client::get_interaction_stream()`](crate::Client::get_interaction_stream)
+    /// from the last received event instead of failing. See [`StallPolicy`] for
+    /// the thresholds and [`StallAction`](crate::StallAction) for the actions.
+    ///
+    /// Reconnects back off per the [`with_auto_resume()`](Self::with_auto_resume)
+    /// policy if one is set. A `with_timeout()` still applies on top, so leave it
+    /// unset or longer than both thresholds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use genai_rs::{Client, StallAction, StallPolicy};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() {
+    /// let client = Client::new("api-key".to_string());
+    ///
+    /// let stream = client
+    ///     .interaction()
+    ///     .with_agent("deep-research-pro-preview-12-2025")
+    ///     .with_text("Survey recent battery chemistry research")
+    ///     .with_background(true)
+    ///     .with_stall_policy(
+    ///         StallPolicy::new()
+    ///             .with_status_idle(Duration::from_secs(600))
+    ///             .with_content_idle(Duration::from_secs(60))
+    ///             .with_action(StallAction::Probe),
+    ///     )
+    ///     .create_stream();
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_policy(mut self, policy: StallPolicy) -> Self {
+        self.stall_policy = Some(policy);
+        self
+    }
+
+    /// Cancels the interaction if the stream from
+    /// [`create_stream()`](Self::create_stream) is dropped before it completes.
+    ///
+    /// Intended for [`with_background(true)`](Self::with_background) agent
+    /// interactions, which otherwise keep running on the server after the
+    /// consumer goes away. The stream remembers the interaction ID from
+    /// [`StreamChunk::Start`] and, if dropped before a `Complete` or `Error`
+    /// event, calls [`Client::cancel_interaction()`](crate::Client::cancel_interaction)
+    /// in a spawned task. See [`CancelOnDrop`](crate::CancelOnDrop) to guard a
+    /// stream from `get_interaction_stream()`, and [`CancelGuard`](crate::CancelGuard)
+    /// for non-streaming background interactions.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use genai_rs::Client;
+    ///
+    /// # async fn example() -> Result<(), genai_rs::GenaiError> {
+    /// let client = Client::new("api-key".to_string());
+    ///
+    /// let mut stream = client
+    ///     .interaction()
+    ///     .with_agent("deep-research-pro-preview-12-2025")
+    ///     .with_text("Research AI safety")
+    ///     .with_background(true)
+    ///     .with_cancel_on_drop()
+    ///     .create_stream();
+    ///
+    /// // Stop after the first event: the server-side job is cancelled too
+    /// let first = stream.next().await;
+    /// drop(stream);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_cancel_on_drop(mut self) -> Self {
+        self.cancel_on_drop = true;
+        self
+    }
+
     /// Creates the interaction and returns the response.
     ///
     /// # Errors
@@ -1718,9 +2047,10 @@ impl<'a> InteractionBuilder<'a> {
     pub async fn create(self) -> Result<InteractionResponse, GenaiError> {
         let client = self.client;
         let timeout = self.timeout;
+        let retry_policy = self.effective_retry_policy();
         let request = self.build()?;
 
-        let future = client.execute(request);
+        let future = client.execute_with_retry_policy(request, retry_policy.as_ref());
 
         match timeout {
             Some(duration) => tokio::time::timeout(duration, future).await.map_err(|_| {
@@ -1815,10 +2145,19 @@ impl<'a> InteractionBuilder<'a> {
     pub fn create_stream(self) -> BoxStream<'a, Result<StreamEvent, GenaiError>> {
         let client = self.client;
         let timeout = self.timeout;
-        Box::pin(async_stream::try_stream! {
+        let retry_policy = self.effective_retry_policy();
+        let resume_policy = self.resume_policy.clone();
+        let stall_policy = self.stall_policy.clone();
+        let cancel_on_drop = self.cancel_on_drop;
+        let stream = Box::pin(async_stream::try_stream! {
             let mut request = self.build()?;
             request.stream = Some(true);
-            let mut stream = client.execute_stream(request);
+            let mut stream = client.execute_stream_with_retry_policy(
+                request,
+                retry_policy,
+                resume_policy,
+                stall_policy,
+            );
 
             loop {
                 let next_chunk = stream.next();
@@ -1843,7 +2182,12 @@ impl<'a> InteractionBuilder<'a> {
                     None => break,
                 }
             }
-        })
+        });
+        if cancel_on_drop {
+            Box::pin(CancelOnDrop::new(client, stream))
+        } else {
+            stream
+        }
     }
 
     /// Builds the [`InteractionRequest`] without executing it.
@@ -335,6 +335,30 @@ fn test_interaction_builder_with_max_function_call_loops() {
     assert_eq!(builder.max_function_call_loops, 1);
 }
 
+#[test]
+fn test_interaction_builder_with_max_parallel_function_calls() {
+    let client = create_test_client();
+
+    let builder = client.interaction().with_model("gemini-3-flash-preview");
+    assert_eq!(
+        builder.max_parallel_function_calls,
+        super::auto_functions::DEFAULT_MAX_PARALLEL_FUNCTION_CALLS
+    );
+
+    let builder = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_max_parallel_function_calls(3);
+    assert_eq!(builder.max_parallel_function_calls, 3);
+
+    // Zero falls back to sequential execution
+    let builder = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_max_parallel_function_calls(0);
+    assert_eq!(builder.max_parallel_function_calls, 1);
+}
+
 // --- Builder State Tests ---
 //
 // These tests verify runtime validation of API constraints.
@@ -456,12 +480,13 @@ fn test_builder_can_set_background() {
 // 1. store=false cannot combine with:
 //    - with_previous_interaction() - chaining requires storage
 //    - with_background(true) - background requires storage
-//    - create_with_auto_functions() - auto-function loop requires storage
-//    - create_stream_with_auto_functions() - auto-function loop requires storage
+//
+// The auto-function loops accept store=false and run statelessly, resending the
+// full conversation each round.
 
 #[tokio::test]
-async fn test_auto_functions_rejects_store_disabled() {
-    // Auto-function execution requires storage to maintain conversation context
+async fn test_auto_functions_allows_store_disabled() {
+    // Stateless mode: validation passes, the actual API call fails (invalid key)
     let client = create_test_client();
     let func = FunctionDeclaration::builder("test_func")
         .description("Test function")
@@ -472,26 +497,23 @@ async fn test_auto_functions_rejects_store_disabled() {
         .with_model("gemini-3-flash-preview")
         .with_text("Test")
         .add_function(func)
-        .with_store_disabled() // This should be rejected
+        .with_store_disabled()
         .create_with_auto_functions()
         .await;
 
-    // Should fail with validation error, not API error
     assert!(result.is_err());
     let err = result.unwrap_err();
     assert!(
-        err.to_string()
-            .contains("create_with_auto_functions() requires storage"),
-        "Expected auto-functions+store error, got: {}",
+        !matches!(err, crate::GenaiError::InvalidInput(_)),
+        "Should not be an InvalidInput error (validation passed), got: {:?}",
         err
     );
 }
 
 #[tokio::test]
-async fn test_stream_auto_functions_rejects_store_disabled() {
+async fn test_stream_auto_functions_allows_store_disabled() {
     use futures_util::StreamExt;
 
-    // Streaming auto-function execution also requires storage
     let client = create_test_client();
     let func = FunctionDeclaration::builder("test_func")
         .description("Test function")
@@ -502,19 +524,17 @@ async fn test_stream_auto_functions_rejects_store_disabled() {
         .with_model("gemini-3-flash-preview")
         .with_text("Test")
         .add_function(func)
-        .with_store_disabled() // This should be rejected
+        .with_store_disabled()
         .create_stream_with_auto_functions();
 
-    // Should return an error stream immediately
-    let first_item = stream.next().await;
-    assert!(first_item.is_some(), "Stream should have at least one item");
-    let result = first_item.unwrap();
-    assert!(result.is_err(), "First stream item should be an error");
-    let err = result.unwrap_err();
+    let err = stream
+        .next()
+        .await
+        .expect("Stream should have at least one item")
+        .expect_err("First stream item should be an API error");
     assert!(
-        err.to_string()
-            .contains("create_with_auto_functions() requires storage"),
-        "Expected auto-functions+store error, got: {}",
+        !matches!(err, crate::GenaiError::InvalidInput(_)),
+        "Should not be an InvalidInput error (validation passed), got: {:?}",
         err
     );
 }
@@ -0,0 +1,463 @@
+//! Built-in retry policy for transient API failures.
+//!
+//! A [`RetryPolicy`] set on [`ClientBuilder`](crate::ClientBuilder) applies to
+//! every request made by the client. Individual interactions can override it via
+//! [`InteractionBuilder::with_retry_policy()`](crate::InteractionBuilder::with_retry_policy).
+//!
+//! Retries are attempted only for errors where [`GenaiError::is_retryable()`]
+//! returns `true` (network errors, timeouts, 429 and 5xx responses). The delay
+//! between attempts grows exponentially, is randomized with jitter, and defers
+//! to the server's `Retry-After` header when present.
+//!
+//! # Scope
+//!
+//! | Operation | Retried |
+//! |-----------|---------|
+//! | `execute()` / `create()` | Entire request |
+//! | `execute_stream()` / `create_stream()` | Initial connection only |
+//! | `get_interaction()` / `get_interaction_stream()` | Entire request / initial connection |
+//! | Files API (upload, get, list, delete) | Entire operation |
+//! | Auto-function loops | Each model call (functions are never re-executed) |
+//!
+//! Once a stream has started yielding events, errors are surfaced to the caller
+//! rather than retried, since retrying would restart generation from scratch.
+//! To continue a dropped stream from its last event instead, opt in with
+//! [`InteractionBuilder::with_auto_resume()`](crate::InteractionBuilder::with_auto_resume).
+//! To recover streams that go quiet without an error, see [`crate::stall`].
+
+use crate::GenaiError;
+use std::future::Future;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Default total number of attempts (the initial request plus two retries).
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Default upper bound for the computed backoff delay.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Default upper bound for honoring a server-provided `Retry-After` delay.
+pub const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Configures automatic retries for transient failures.
+///
+/// # Backoff
+///
+/// The delay before retry `n` (1-based) is `initial_backoff * multiplier^(n-1)`,
+/// capped at `max_backoff`. With jitter enabled (the default), the delay is
+/// randomized within `[delay / 2, delay]` to avoid synchronized retries across
+/// clients.
+///
+/// # Retry-After
+///
+/// When the API returns a `Retry-After` header (typically with 429 responses),
+/// that delay is used instead of the computed backoff. If the server asks for a
+/// longer wait than `max_retry_after`, the error is returned immediately rather
+/// than blocking the caller for that long.
+///
+/// # Example
+///
+/// ```
+/// use genai_rs::{Client, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let client = Client::builder("api_key".to_string())
+///     .with_retry_policy(
+///         RetryPolicy::new()
+///             .with_max_attempts(5)
+///             .with_initial_backoff(Duration::from_millis(200))
+///             .with_max_backoff(Duration::from_secs(10)),
+///     )
+///     .build()?;
+/// # Ok::<(), genai_rs::GenaiError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    respect_retry_after: bool,
+    max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            multiplier: 2.0,
+            jitter: true,
+            respect_retry_after: true,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the default settings: 3 attempts, 500ms initial
+    /// backoff doubling up to 30s, jitter enabled, and `Retry-After` honored
+    /// up to 60s.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that never retries.
+    ///
+    /// Useful for overriding a client-wide policy on a single request, e.g. for
+    /// non-idempotent operations.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the total number of attempts, including the initial request.
+    ///
+    /// Values below 1 are treated as 1 (no retries).
+    #[must_use]
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    #[must_use]
+    pub const fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the upper bound for the computed backoff delay.
+    #[must_use]
+    pub const fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Sets the factor the backoff grows by after each retry (default: 2.0).
+    ///
+    /// Values below 1.0 are treated as 1.0 (constant backoff).
+    #[must_use]
+    pub const fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enables or disables randomized jitter (default: enabled).
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Controls whether a server-provided `Retry-After` delay replaces the
+    /// computed backoff (default: enabled).
+    #[must_use]
+    pub const fn with_respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Sets the longest `Retry-After` delay the client is willing to wait.
+    ///
+    /// If the server asks for a longer delay, the error is returned without retrying.
+    #[must_use]
+    pub const fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Returns the total number of attempts, including the initial request.
+    #[must_use]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.max(1)
+    }
+
+    /// Computes the exponential backoff (before jitter) for the given retry number.
+    fn backoff(&self, retry: u32) -> Duration {
+        let multiplier = self.multiplier.max(1.0);
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let secs = self.initial_backoff.as_secs_f64() * multiplier.powi(exponent);
+        if secs.is_finite() && secs < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max_backoff
+        }
+    }
+
+    /// Returns how long to wait before retrying after a failed `attempt` (1-based),
+    /// or `None` if the error should be returned to the caller.
+    pub(crate) fn delay_before_retry(&self, attempt: u32, error: &GenaiError) -> Option<Duration> {
+        if attempt >= self.max_attempts() || !error.is_retryable() {
+            return None;
+        }
+
+        if self.respect_retry_after
+            && let Some(retry_after) = error.retry_after()
+        {
+            return (retry_after <= self.max_retry_after).then_some(retry_after);
+        }
+
+        let backoff = self.backoff(attempt);
+        if self.jitter {
+            Some(backoff.mul_f64(0.5 + 0.5 * random_fraction()))
+        } else {
+            Some(backoff)
+        }
+    }
+}
+
+/// Returns a pseudo-random value in `[0, 1)`.
+///
+/// Jitter only needs to decorrelate clients, so the randomly-keyed std hasher
+/// is sufficient and avoids pulling in an RNG dependency.
+fn random_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos()),
+    );
+    // Use the top 53 bits so the result is exactly representable as an f64
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Runs `operation` until it succeeds, fails with a non-retryable error, or the
+/// policy's attempts are exhausted.
+///
+/// With no policy the operation runs exactly once. Each attempt runs inside a
+/// `retry_attempt` tracing span carrying the operation name and attempt number.
+pub(crate) async fn with_retry<T, F, Fut>(
+    policy: Option<&RetryPolicy>,
+    operation: &'static str,
+    mut f: F,
+) -> Result<T, GenaiError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, GenaiError>>,
+{
+    let max_attempts = policy.map_or(1, RetryPolicy::max_attempts);
+    let mut attempt = 1;
+
+    loop {
+        let span = tracing::debug_span!("retry_attempt", operation, attempt, max_attempts);
+        let error = match f().instrument(span).await {
+            Ok(value) => {
+                if attempt > 1 {
+                    tracing::debug!("{operation} succeeded on attempt {attempt}/{max_attempts}");
+                }
+                return Ok(value);
+            }
+            Err(error) => error,
+        };
+
+        let Some(delay) = policy.and_then(|p| p.delay_before_retry(attempt, &error)) else {
+            if attempt > 1 {
+                tracing::warn!(
+                    "{operation} failed after {attempt}/{max_attempts} attempts: {error}"
+                );
+            }
+            return Err(error);
+        };
+
+        tracing::warn!(
+            "{operation} attempt {attempt}/{max_attempts} failed, retrying in {delay:?}: {error}"
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn api_error(status_code: u16, retry_after: Option<Duration>) -> GenaiError {
+        GenaiError::Api {
+            status_code,
+            message: "test".to_string(),
+            request_id: None,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts(), DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(policy.initial_backoff, DEFAULT_INITIAL_BACKOFF);
+        assert_eq!(policy.max_backoff, DEFAULT_MAX_BACKOFF);
+        assert!(policy.jitter);
+        assert!(policy.respect_retry_after);
+    }
+
+    #[test]
+    fn test_none_policy_never_retries() {
+        let policy = RetryPolicy::none();
+        assert_eq!(policy.max_attempts(), 1);
+        assert_eq!(policy.delay_before_retry(1, &api_error(503, None)), None);
+    }
+
+    #[test]
+    fn test_zero_attempts_treated_as_one() {
+        assert_eq!(RetryPolicy::new().with_max_attempts(0).max_attempts(), 1);
+    }
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .with_max_attempts(10)
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_secs(1))
+            .with_jitter(false);
+        let error = api_error(500, None);
+
+        assert_eq!(
+            policy.delay_before_retry(1, &error),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.delay_before_retry(2, &error),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.delay_before_retry(3, &error),
+            Some(Duration::from_millis(400))
+        );
+        // Capped at max_backoff
+        assert_eq!(
+            policy.delay_before_retry(5, &error),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy::new()
+            .with_max_attempts(10)
+            .with_initial_backoff(Duration::from_millis(1000));
+        let error = api_error(500, None);
+
+        for _ in 0..100 {
+            let delay = policy.delay_before_retry(1, &error).unwrap();
+            assert!(delay >= Duration::from_millis(500), "{delay:?} below bound");
+            assert!(
+                delay <= Duration::from_millis(1000),
+                "{delay:?} above bound"
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_retryable_errors_are_not_retried() {
+        let policy = RetryPolicy::new();
+        assert_eq!(policy.delay_before_retry(1, &api_error(400, None)), None);
+        assert_eq!(
+            policy.delay_before_retry(1, &GenaiError::InvalidInput("bad".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_attempts_exhausted() {
+        let policy = RetryPolicy::new().with_max_attempts(3);
+        let error = api_error(503, None);
+        assert!(policy.delay_before_retry(2, &error).is_some());
+        assert_eq!(policy.delay_before_retry(3, &error), None);
+    }
+
+    #[test]
+    fn test_retry_after_replaces_backoff() {
+        let policy = RetryPolicy::new();
+        let error = api_error(429, Some(Duration::from_secs(7)));
+        assert_eq!(
+            policy.delay_before_retry(1, &error),
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn test_retry_after_exceeding_cap_gives_up() {
+        let policy = RetryPolicy::new().with_max_retry_after(Duration::from_secs(5));
+        let error = api_error(429, Some(Duration::from_secs(120)));
+        assert_eq!(policy.delay_before_retry(1, &error), None);
+    }
+
+    #[test]
+    fn test_retry_after_ignored_when_disabled() {
+        let policy = RetryPolicy::new()
+            .with_respect_retry_after(false)
+            .with_initial_backoff(Duration::from_millis(10))
+            .with_jitter(false);
+        let error = api_error(429, Some(Duration::from_secs(120)));
+        assert_eq!(
+            policy.delay_before_retry(1, &error),
+            Some(Duration::from_millis(10))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_retry_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy::new().with_max_attempts(3);
+
+        let result = with_retry(Some(&policy), "test", || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(api_error(503, None))
+            } else {
+                Ok("done")
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_retry_returns_last_error_when_exhausted() {
+        let calls = AtomicU32::new(0);
+        let policy = RetryPolicy::new().with_max_attempts(2);
+
+        let result: Result<(), _> = with_retry(Some(&policy), "test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(api_error(500, None))
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(GenaiError::Api {
+                status_code: 500,
+                ..
+            })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_without_policy_runs_once() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), _> = with_retry(None, "test", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(api_error(503, None))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,373 @@
+//! Fan-out of one interaction stream to multiple subscribers.
+//!
+//! A stream from `create_stream()` can only be consumed once. Wrapping it in a
+//! [`SharedInteractionStream`] lets any number of [`SharedStreamSubscriber`]s
+//! each receive every event, e.g. several websocket clients plus an audit
+//! logger. Subscribers that join late first receive a bounded replay of the
+//! most recent events. The response accumulated from the events is available
+//! from [`response()`](SharedInteractionStream::response) once
+//! [`StreamChunk::Complete`](crate::StreamChunk::Complete) arrives.
+//!
+//! The underlying stream is polled by whichever subscriber needs the next
+//! event, so no task is spawned. Each subscriber buffers the events it has not
+//! consumed yet; a subscriber that stops polling without being dropped grows
+//! its buffer until the stream ends.
+//!
+//! Errors are shared as `Arc<GenaiError>`, since every subscriber receives the
+//! same error.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use genai_rs::{Client, SharedInteractionStream};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::new("your-api-key".to_string());
+//!
+//! let stream = client
+//!     .interaction()
+//!     .with_model("gemini-3-flash-preview")
+//!     .with_text("Tell me a story")
+//!     .create_stream();
+//! let shared = SharedInteractionStream::new(stream, 64);
+//!
+//! let mut audit = shared.subscribe();
+//! let mut viewer = shared.subscribe();
+//! let log_events = async {
+//!     while let Some(event) = audit.next().await {
+//!         println!("audit: {:?}", event.map(|e| e.event_id));
+//!     }
+//! };
+//! let render = async {
+//!     while let Some(Ok(event)) = viewer.next().await {
+//!         if let Some(text) = event.delta().and_then(|d| d.as_text()) {
+//!             print!("{text}");
+//!         }
+//!     }
+//! };
+//! futures_util::join!(log_events, render);
+//!
+//! let response = shared.response().expect("stream completed");
+//! println!("\n{} outputs", response.outputs.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::{Stream, StreamExt};
+
+use crate::fan_out::{Distribute, FanOut};
+use crate::{GenaiError, InteractionResponse, StreamAccumulator, StreamChunk, StreamEvent};
+
+/// An event as delivered to each subscriber.
+pub type SharedStreamItem = Result<StreamEvent, Arc<GenaiError>>;
+
+/// A handle that fans one interaction stream out to many subscribers.
+///
+/// Cloning the handle is cheap; all clones share the same stream, replay
+/// buffer and accumulated response. The underlying stream is dropped once the
+/// last handle and subscriber are gone.
+#[derive(Clone)]
+pub struct SharedInteractionStream<'a> {
+    shared: Arc<FanOut<'a, Subscribers>>,
+}
+
+impl std::fmt::Debug for SharedInteractionStream<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.shared.lock();
+        let subscribers = &state.consumers;
+        f.debug_struct("SharedInteractionStream")
+            .field("subscribers", &subscribers.queues.len())
+            .field("replay", &subscribers.replay.len())
+            .field("replay_capacity", &subscribers.replay_capacity)
+            .field("ended", &state.is_ended())
+            .finish()
+    }
+}
+
+impl<'a> SharedInteractionStream<'a> {
+    /// Wraps `stream`, keeping up to `replay_capacity` of the most recent events
+    /// for subscribers that join late.
+    ///
+    /// With a capacity of 0, a subscriber only receives events that arrive
+    /// after it subscribed.
+    #[must_use]
+    pub fn new<S>(stream: S, replay_capacity: usize) -> Self
+    where
+        S: Stream<Item = Result<StreamEvent, GenaiError>> + Send + 'a,
+    {
+        Self {
+            shared: Arc::new(FanOut::new(
+                stream.boxed(),
+                Subscribers {
+                    replay: VecDeque::new(),
+                    replay_capacity,
+                    queues: HashMap::new(),
+                    next_id: 0,
+                    accumulator: StreamAccumulator::new(),
+                    response: None,
+                },
+            )),
+        }
+    }
+
+    /// Adds a subscriber.
+    ///
+    /// The subscriber first yields the replay buffer (the most recent events,
+    /// oldest first), then every later event. If the stream has already ended,
+    /// it yields the replay buffer and ends.
+    #[must_use]
+    pub fn subscribe(&self) -> SharedStreamSubscriber<'a> {
+        let mut state = self.shared.lock();
+        let subscribers = &mut state.consumers;
+        let id = subscribers.next_id;
+        subscribers.next_id += 1;
+        let replay = subscribers.replay.clone();
+        subscribers.queues.insert(id, replay);
+        drop(state);
+        SharedStreamSubscriber {
+            shared: Arc::clone(&self.shared),
+            id,
+        }
+    }
+
+    /// Returns the number of live subscribers.
+    #[must_use]
+    pub fn subscriber_count(&self) -> usize {
+        self.shared.lock().consumers.queues.len()
+    }
+
+    /// Returns the accumulated response once a `Complete` event has arrived.
+    ///
+    /// Outputs are merged from the deltas of all events seen so far, so the
+    /// response is complete even if the stream was resumed along the way.
+    #[must_use]
+    pub fn response(&self) -> Option<InteractionResponse> {
+        self.shared.lock().consumers.response.clone()
+    }
+
+    /// Waits for the `Complete` event and returns the accumulated response.
+    ///
+    /// Drives the stream if no subscriber is doing so. Returns `None` if the
+    /// stream ends without completing, e.g. after an error.
+    pub async fn wait_for_response(&self) -> Option<InteractionResponse> {
+        let mut subscriber = self.subscribe();
+        loop {
+            if let Some(response) = self.response() {
+                return Some(response);
+            }
+            let _ = subscriber.next().await?;
+        }
+    }
+}
+
+/// Per-subscriber queues, the replay buffer and the accumulated response.
+struct Subscribers {
+    replay: VecDeque<SharedStreamItem>,
+    replay_capacity: usize,
+    /// Undelivered events per subscriber
+    queues: HashMap<usize, VecDeque<SharedStreamItem>>,
+    next_id: usize,
+    accumulator: StreamAccumulator,
+    response: Option<InteractionResponse>,
+}
+
+impl Distribute for Subscribers {
+    type Item = Result<StreamEvent, GenaiError>;
+    type Output = SharedStreamItem;
+
+    /// Records an event from the source and queues it for every subscriber.
+    fn distribute(&mut self, item: Self::Item) {
+        let item = item.map_err(Arc::new);
+        if let Ok(event) = &item {
+            self.accumulator.push(event);
+            if matches!(event.chunk, StreamChunk::Complete(_)) {
+                self.response = Some(self.accumulator.snapshot());
+            }
+        }
+        for queue in self.queues.values_mut() {
+            queue.push_back(item.clone());
+        }
+        if self.replay_capacity > 0 {
+            if self.replay.len() == self.replay_capacity {
+                self.replay.pop_front();
+            }
+            self.replay.push_back(item);
+        }
+    }
+
+    fn take(&mut self, consumer: usize) -> Option<SharedStreamItem> {
+        self.queues.get_mut(&consumer).and_then(VecDeque::pop_front)
+    }
+}
+
+/// One subscriber of a [`SharedInteractionStream`].
+///
+/// Yields every event of the shared stream, starting with the replay buffer
+/// at the time it subscribed.
+pub struct SharedStreamSubscriber<'a> {
+    shared: Arc<FanOut<'a, Subscribers>>,
+    id: usize,
+}
+
+impl std::fmt::Debug for SharedStreamSubscriber<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buffered = self
+            .shared
+            .lock()
+            .consumers
+            .queues
+            .get(&self.id)
+            .map(VecDeque::len);
+        f.debug_struct("SharedStreamSubscriber")
+            .field("id", &self.id)
+            .field("buffered", &buffered)
+            .finish()
+    }
+}
+
+impl Stream for SharedStreamSubscriber<'_> {
+    type Item = SharedStreamItem;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.shared.poll_next(self.id, cx)
+    }
+}
+
+impl Drop for SharedStreamSubscriber<'_> {
+    fn drop(&mut self) {
+        self.shared.lock().consumers.queues.remove(&self.id);
+        self.shared.leave(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Content, InteractionStatus};
+    use futures_util::stream;
+    use serde_json::json;
+
+    fn text(text: &str, id: usize) -> Result<StreamEvent, GenaiError> {
+        Ok(StreamEvent::new(
+            StreamChunk::Delta(Content::Text {
+                text: Some(text.to_string()),
+                annotations: None,
+            }),
+            Some(format!("evt_{id}")),
+        )
+        .with_index(Some(0)))
+    }
+
+    fn complete() -> Result<StreamEvent, GenaiError> {
+        let response = serde_json::from_value(json!({"id": "int-1", "status": "completed"}))
+            .expect("valid response");
+        Ok(StreamEvent::new(
+            StreamChunk::Complete(response),
+            Some("evt_done".to_string()),
+        ))
+    }
+
+    fn ids(items: &[SharedStreamItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| item.as_ref().unwrap().event_id.clone().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_every_subscriber_receives_every_event() {
+        let shared = SharedInteractionStream::new(
+            stream::iter(vec![text("Hel", 1), text("lo", 2), complete()]),
+            0,
+        );
+        let first = shared.subscribe();
+        let second = shared.subscribe();
+        assert_eq!(shared.subscriber_count(), 2);
+
+        let (first, second): (Vec<_>, Vec<_>) =
+            futures_util::join!(first.collect(), second.collect());
+
+        assert_eq!(ids(&first), ["evt_1", "evt_2", "evt_done"]);
+        assert_eq!(ids(&second), ids(&first));
+        let response = shared.response().unwrap();
+        assert_eq!(response.status, InteractionStatus::Completed);
+        assert_eq!(response.as_text(), Some("Hello"));
+    }
+
+    #[tokio::test]
+    async fn test_late_subscriber_gets_bounded_replay() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let shared = SharedInteractionStream::new(stream::poll_fn(move |cx| rx.poll_recv(cx)), 2);
+        let mut early = shared.subscribe();
+
+        for id in 1..=3 {
+            tx.send(text("x", id)).unwrap();
+            early.next().await.unwrap().unwrap();
+        }
+        let late = shared.subscribe();
+        tx.send(text("y", 4)).unwrap();
+        drop(tx);
+
+        let late: Vec<_> = late.collect().await;
+        assert_eq!(ids(&late), ["evt_2", "evt_3", "evt_4"]);
+        let rest: Vec<_> = early.collect().await;
+        assert_eq!(ids(&rest), ["evt_4"]);
+        assert!(shared.response().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared() {
+        let shared = SharedInteractionStream::new(
+            stream::iter(vec![
+                text("a", 1),
+                Err(GenaiError::Internal("dropped".to_string())),
+            ]),
+            8,
+        );
+        let first: Vec<_> = shared.subscribe().collect().await;
+        let second: Vec<_> = shared.subscribe().collect().await;
+
+        for items in [&first, &second] {
+            assert_eq!(items.len(), 2);
+            assert!(matches!(
+                items[1].as_ref().unwrap_err().as_ref(),
+                GenaiError::Internal(message) if message == "dropped"
+            ));
+        }
+        assert!(shared.wait_for_response().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_subscriber_does_not_stall_others() {
+        let shared =
+            SharedInteractionStream::new(stream::iter(vec![text("a", 1), text("b", 2)]), 0);
+        let mut dropped = shared.subscribe();
+        let kept = shared.subscribe();
+        dropped.next().await.unwrap().unwrap();
+        drop(dropped);
+
+        assert_eq!(shared.subscriber_count(), 1);
+        let rest: Vec<_> = kept.collect().await;
+        assert_eq!(ids(&rest), ["evt_1", "evt_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_response_drives_the_stream() {
+        let shared = SharedInteractionStream::new(
+            stream::iter(vec![text("Hi", 1), complete(), text("late", 2)]),
+            4,
+        );
+        let response = shared.wait_for_response().await.unwrap();
+        assert_eq!(response.as_text(), Some("Hi"));
+
+        // A subscriber joining afterwards still sees the replayed events
+        let replayed: Vec<_> = shared.subscribe().collect().await;
+        assert_eq!(ids(&replayed), ["evt_1", "evt_done", "evt_2"]);
+    }
+}
@@ -0,0 +1,374 @@
+//! Re-encoding interaction streams as Server-Sent Events.
+//!
+//! A backend that sits between a browser and the API can parse the upstream
+//! stream with `create_stream()` and forward it with [`SseEncoder`], which
+//! writes each [`StreamEvent`] or [`AutoFunctionStreamEvent`] as an SSE frame
+//! using the crate's own serde wire format. The output is a plain
+//! `Stream<Item = Bytes>`, so it plugs into any HTTP framework's streaming
+//! body.
+//!
+//! Each frame carries the event's `event_id` in the SSE `id` field, so a
+//! browser `EventSource` reconnects with a `Last-Event-ID` header that the
+//! backend can pass to [`Client::get_interaction_stream()`](crate::Client::get_interaction_stream).
+//! Events without an ID are written without an `id` field; the client keeps
+//! the previous `Last-Event-ID` for them, as the SSE specification requires.
+//! Errors are written as a final `stream_error` event (not `error`, which
+//! `EventSource` reserves for connection failures).
+//!
+//! # Frame Format
+//!
+//! ```text
+//! id: evt_2
+//! data: {"chunk_type":"delta","data":{"type":"text","text":"Hello"},"event_id":"evt_2","index":0}
+//!
+//! event: stream_error
+//! data: {"message":"Request timed out after 30s","retryable":true}
+//!
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use genai_rs::{Client, SseEncoder};
+//!
+//! # fn example(client: &Client) {
+//! let stream = client
+//!     .interaction()
+//!     .with_model("gemini-3-flash-preview")
+//!     .with_text("Hello")
+//!     .create_stream();
+//!
+//! // Hand this to your framework as a `text/event-stream` body
+//! let body = SseEncoder::new().encode_stream(stream);
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use serde_json::json;
+use tracing::warn;
+
+use crate::{AutoFunctionStreamEvent, GenaiError, StreamEvent};
+
+/// SSE event name for errors, distinct from `EventSource`'s own `error` event.
+pub const SSE_ERROR_EVENT: &str = "stream_error";
+
+/// An event that [`SseEncoder`] can write as an SSE frame.
+///
+/// Implemented for [`StreamEvent`] and [`AutoFunctionStreamEvent`].
+pub trait SseEvent: Serialize {
+    /// The ID written to the SSE `id` field, if any.
+    fn sse_id(&self) -> Option<&str>;
+}
+
+impl SseEvent for StreamEvent {
+    fn sse_id(&self) -> Option<&str> {
+        self.event_id.as_deref()
+    }
+}
+
+impl SseEvent for AutoFunctionStreamEvent {
+    fn sse_id(&self) -> Option<&str> {
+        self.event_id.as_deref()
+    }
+}
+
+/// Writes interaction stream events as SSE frames.
+///
+/// # Example
+///
+/// ```
+/// use genai_rs::{SseEncoder, StreamChunk, StreamEvent};
+///
+/// let event = StreamEvent::new(
+///     StreamChunk::ContentStop { index: 0 },
+///     Some("evt_3".to_string()),
+/// );
+/// let frame = SseEncoder::new().encode(&event)?;
+/// assert!(frame.starts_with(b"id: evt_3\ndata: {"));
+/// assert!(frame.ends_with(b"}\n\n"));
+/// # Ok::<(), genai_rs::GenaiError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SseEncoder {
+    retry: Option<Duration>,
+}
+
+impl SseEncoder {
+    /// Creates an encoder with no `retry` field.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `retry` as the client's reconnection time before the first event.
+    #[must_use]
+    pub const fn with_retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Encodes one event as an SSE frame.
+    ///
+    /// The `id` field is omitted if the event has no ID, or if the ID contains
+    /// a line break or NUL, which SSE cannot carry. A client keeps the last ID
+    /// it received for such a frame, so its `Last-Event-ID` still points at the
+    /// previous event.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenaiError::Json`] if the event cannot be serialized.
+    pub fn encode<E: SseEvent>(&self, event: &E) -> Result<Bytes, GenaiError> {
+        let data = serde_json::to_string(event)?;
+        let mut frame = String::with_capacity(data.len() + 32);
+        if let Some(id) = event.sse_id() {
+            if id.contains(['\n', '\r', '\0']) {
+                warn!("Omitting SSE id that cannot be encoded: {id:?}");
+            } else {
+                frame.push_str("id: ");
+                frame.push_str(id);
+                frame.push('\n');
+            }
+        }
+        push_data(&mut frame, &data);
+        Ok(Bytes::from(frame))
+    }
+
+    /// Encodes an error as a `stream_error` event with its message, whether it
+    /// is retryable, and the HTTP status for API errors.
+    #[must_use]
+    pub fn encode_error(&self, error: &GenaiError) -> Bytes {
+        let mut data = json!({
+            "message": error.to_string(),
+            "retryable": error.is_retryable(),
+        });
+        if let GenaiError::Api { status_code, .. } = error {
+            data["status_code"] = json!(status_code);
+        }
+        let mut frame = format!("event: {SSE_ERROR_EVENT}\n");
+        push_data(&mut frame, &data.to_string());
+        Bytes::from(frame)
+    }
+
+    /// Encodes a whole stream.
+    ///
+    /// Yields the `retry` field first if one is set, then one frame per event.
+    /// An error is encoded with [`encode_error()`](Self::encode_error) and ends
+    /// the output, as does an event that fails to serialize.
+    pub fn encode_stream<'a, S, E>(&self, stream: S) -> BoxStream<'a, Bytes>
+    where
+        S: Stream<Item = Result<E, GenaiError>> + Send + 'a,
+        E: SseEvent + Send + 'a,
+    {
+        let encoder = self.clone();
+        let retry = self
+            .retry
+            .map(|retry| Bytes::from(format!("retry: {}\n\n", retry.as_millis())));
+        let frames = stream
+            .scan(false, move |failed, item| {
+                if *failed {
+                    return futures_util::future::ready(None);
+                }
+                let frame = match item.and_then(|event| encoder.encode(&event)) {
+                    Ok(frame) => frame,
+                    Err(e) => {
+                        *failed = true;
+                        encoder.encode_error(&e)
+                    }
+                };
+                futures_util::future::ready(Some(frame))
+            })
+            .boxed();
+        match retry {
+            Some(retry) => futures_util::stream::once(async move { retry })
+                .chain(frames)
+                .boxed(),
+            None => frames,
+        }
+    }
+}
+
+/// Appends `data` as `data:` lines and the blank line that ends the event.
+fn push_data(frame: &mut String, data: &str) {
+    // Serialized JSON has no raw line breaks, but split defensively so that
+    // a payload never ends the event early
+    for line in data.split('\n') {
+        frame.push_str("data: ");
+        frame.push_str(line.trim_end_matches('\r'));
+        frame.push('\n');
+    }
+    frame.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::sse_parser::{SseMessage, parse_sse_stream};
+    use crate::{AutoFunctionStreamChunk, Content, InteractionResponse, StreamChunk};
+    use futures_util::stream;
+    use serde::de::DeserializeOwned;
+
+    fn response(status: &str) -> InteractionResponse {
+        serde_json::from_value(json!({"id": "int-1", "status": status})).unwrap()
+    }
+
+    fn text(text: &str) -> Content {
+        Content::Text {
+            text: Some(text.to_string()),
+            annotations: None,
+        }
+    }
+
+    fn events() -> Vec<StreamEvent> {
+        vec![
+            StreamEvent::new(
+                StreamChunk::Start {
+                    interaction: response("in_progress"),
+                },
+                Some("evt_1".to_string()),
+            ),
+            StreamEvent::new(
+                StreamChunk::Delta(text("Hi\nthere")),
+                Some("evt_2".to_string()),
+            )
+            .with_index(Some(0)),
+            StreamEvent::new(StreamChunk::ContentStop { index: 0 }, None),
+            StreamEvent::new(
+                StreamChunk::Complete(response("completed")),
+                Some("evt_4".to_string()),
+            ),
+        ]
+    }
+
+    /// Feeds `frames` to the client's SSE parser, split into small chunks.
+    async fn parse<T: DeserializeOwned + Send>(frames: Vec<Bytes>) -> Vec<SseMessage<T>> {
+        let bytes: Vec<u8> = frames.concat();
+        let chunks: Vec<Result<Bytes, reqwest::Error>> = bytes
+            .chunks(7)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        parse_sse_stream::<T>(stream::iter(chunks), 0)
+            .map(Result::unwrap)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_stream_events_round_trip() {
+        let frames: Vec<_> = SseEncoder::new()
+            .encode_stream(stream::iter(events().into_iter().map(Ok)))
+            .collect()
+            .await;
+        let messages = parse::<StreamEvent>(frames).await;
+
+        assert_eq!(messages.len(), 4);
+        for (message, original) in messages.iter().zip(events()) {
+            // `id` is the frame's own ID, not the carried-over last event ID
+            assert_eq!(message.id, original.event_id);
+            assert_eq!(message.event, None);
+            assert_eq!(
+                serde_json::to_value(&message.data).unwrap(),
+                serde_json::to_value(&original).unwrap()
+            );
+        }
+        assert_eq!(
+            messages[1].data.delta().unwrap().as_text(),
+            Some("Hi\nthere")
+        );
+
+        // The id-less `ContentStop` frame inherits the previous Last-Event-ID
+        let last_ids: Vec<_> = messages
+            .iter()
+            .map(|message| message.last_event_id.as_deref())
+            .collect();
+        assert_eq!(
+            last_ids,
+            [Some("evt_1"), Some("evt_2"), Some("evt_2"), Some("evt_4")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auto_function_events_round_trip() {
+        let originals = vec![
+            AutoFunctionStreamEvent::new(
+                AutoFunctionStreamChunk::Delta(text("Checking")),
+                Some("evt_1".to_string()),
+            ),
+            AutoFunctionStreamEvent::new(
+                AutoFunctionStreamChunk::Complete(response("completed")),
+                None,
+            ),
+        ];
+        let encoder = SseEncoder::new();
+        let frames = originals
+            .iter()
+            .map(|event| encoder.encode(event).unwrap())
+            .collect();
+        let messages = parse::<AutoFunctionStreamEvent>(frames).await;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id.as_deref(), Some("evt_1"));
+        assert_eq!(messages[1].id, None);
+        assert_eq!(messages[1].last_event_id.as_deref(), Some("evt_1"));
+        for (message, original) in messages.iter().zip(&originals) {
+            assert_eq!(
+                serde_json::to_value(&message.data).unwrap(),
+                serde_json::to_value(original).unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_field_and_error_end_the_stream() {
+        let mut items: Vec<Result<StreamEvent, GenaiError>> =
+            events().into_iter().take(2).map(Ok).collect();
+        items.push(Err(GenaiError::Timeout(Duration::from_secs(30))));
+        items.push(Ok(events().remove(3)));
+
+        let frames: Vec<_> = SseEncoder::new()
+            .with_retry(Duration::from_millis(2500))
+            .encode_stream(stream::iter(items))
+            .collect()
+            .await;
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(&frames[0][..], b"retry: 2500\n\n");
+        let messages = parse::<serde_json::Value>(frames).await;
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[2].event.as_deref(), Some(SSE_ERROR_EVENT));
+        assert_eq!(messages[2].retry, Some(Duration::from_millis(2500)));
+        assert_eq!(
+            messages[2].data,
+            json!({"message": "Request timed out after 30s", "retryable": true})
+        );
+    }
+
+    #[test]
+    fn test_unencodable_id_is_omitted() {
+        let event = StreamEvent::new(
+            StreamChunk::ContentStop { index: 0 },
+            Some("evt\n1".to_string()),
+        );
+        let frame = SseEncoder::new().encode(&event).unwrap();
+        assert!(frame.starts_with(b"data: "));
+    }
+
+    #[test]
+    fn test_api_error_includes_status_code() {
+        let error = GenaiError::Api {
+            status_code: 429,
+            message: "Slow down".to_string(),
+            request_id: None,
+            retry_after: None,
+        };
+        let frame = SseEncoder::new().encode_error(&error);
+        let text = std::str::from_utf8(&frame).unwrap();
+        assert!(text.starts_with("event: stream_error\ndata: {"));
+        assert!(text.contains(r#""status_code":429"#));
+        assert!(text.ends_with("}\n\n"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Stall detection for streaming interactions.
+//!
+//! A per-chunk [`with_timeout()`](crate::InteractionBuilder::with_timeout)
+//! cannot tell a deep-research or thinking stream that legitimately goes quiet
+//! from a dead TCP connection that will never deliver another byte. A
+//! [`StallPolicy`], set with
+//! [`InteractionBuilder::with_stall_policy()`](crate::InteractionBuilder::with_stall_policy),
+//! uses two idle thresholds instead:
+//!
+//! | Phase | Threshold | Applies |
+//! |-------|-----------|---------|
+//! | Between status updates | `status_idle` | Before content starts and after each `content.stop` |
+//! | Between content deltas | `content_idle` | From `content.start` or a delta until `content.stop` |
+//!
+//! Every event, including `interaction.status_update`, counts as a heartbeat
+//! and restarts the timer. When a threshold elapses, the [`StallAction`]
+//! decides whether to probe the interaction, reconnect from the last event, or
+//! fail with [`GenaiError::Timeout`](crate::GenaiError::Timeout).
+
+use std::time::Duration;
+
+/// Default idle threshold between status updates.
+pub const DEFAULT_STATUS_IDLE: Duration = Duration::from_secs(120);
+
+/// Default idle threshold between content deltas.
+pub const DEFAULT_CONTENT_IDLE: Duration = Duration::from_secs(30);
+
+/// What a stream does when no event arrives within the idle threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StallAction {
+    /// Checks the interaction with `get_interaction()`. While it is still in
+    /// progress, the silence is treated as legitimate and the stream keeps
+    /// waiting; otherwise (or if the probe fails) the stream reconnects.
+    Probe,
+    /// Reconnects via `get_interaction_stream()` from the last received event.
+    #[default]
+    Reconnect,
+    /// Ends the stream with [`GenaiError::Timeout`](crate::GenaiError::Timeout).
+    Fail,
+}
+
+/// Configures stall detection for streams.
+///
+/// Reconnects back off per the policy given to
+/// [`with_auto_resume()`](crate::InteractionBuilder::with_auto_resume), or
+/// [`RetryPolicy::default()`](crate::RetryPolicy) without one, and give up with
+/// [`GenaiError::Timeout`](crate::GenaiError::Timeout) once that policy is
+/// exhausted. Reconnecting needs the interaction ID from
+/// [`StreamChunk::Start`](crate::StreamChunk::Start); a stall before it arrives
+/// ends the stream with the timeout error.
+///
+/// # Example
+///
+/// ```
+/// use genai_rs::{StallAction, StallPolicy};
+/// use std::time::Duration;
+///
+/// // Deep research: long quiet phases, but content should keep flowing
+/// let policy = StallPolicy::new()
+///     .with_status_idle(Duration::from_secs(600))
+///     .with_content_idle(Duration::from_secs(60))
+///     .with_action(StallAction::Probe);
+/// assert_eq!(policy.status_idle(), Duration::from_secs(600));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StallPolicy {
+    status_idle: Duration,
+    content_idle: Duration,
+    action: StallAction,
+}
+
+impl Default for StallPolicy {
+    fn default() -> Self {
+        Self {
+            status_idle: DEFAULT_STATUS_IDLE,
+            content_idle: DEFAULT_CONTENT_IDLE,
+            action: StallAction::default(),
+        }
+    }
+}
+
+impl StallPolicy {
+    /// Creates a policy with the default settings: 120s between status updates,
+    /// 30s between content deltas, and [`StallAction::Reconnect`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long the stream may go without any event while no content is
+    /// being generated.
+    #[must_use]
+    pub const fn with_status_idle(mut self, idle: Duration) -> Self {
+        self.status_idle = idle;
+        self
+    }
+
+    /// Sets how long the stream may go without an event while a content block
+    /// is open.
+    #[must_use]
+    pub const fn with_content_idle(mut self, idle: Duration) -> Self {
+        self.content_idle = idle;
+        self
+    }
+
+    /// Sets what happens when a threshold elapses.
+    #[must_use]
+    pub const fn with_action(mut self, action: StallAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Returns the idle threshold between status updates.
+    #[must_use]
+    pub const fn status_idle(&self) -> Duration {
+        self.status_idle
+    }
+
+    /// Returns the idle threshold between content deltas.
+    #[must_use]
+    pub const fn content_idle(&self) -> Duration {
+        self.content_idle
+    }
+
+    /// Returns the action taken on a stall.
+    #[must_use]
+    pub const fn action(&self) -> StallAction {
+        self.action
+    }
+
+    /// The threshold for the current phase of the stream.
+    pub(crate) const fn idle_threshold(&self, in_content: bool) -> Duration {
+        if in_content {
+            self.content_idle
+        } else {
+            self.status_idle
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let policy = StallPolicy::new();
+        assert_eq!(policy.status_idle(), DEFAULT_STATUS_IDLE);
+        assert_eq!(policy.content_idle(), DEFAULT_CONTENT_IDLE);
+        assert_eq!(policy.action(), StallAction::Reconnect);
+    }
+
+    #[test]
+    fn test_idle_threshold_follows_phase() {
+        let policy = StallPolicy::new()
+            .with_status_idle(Duration::from_secs(300))
+            .with_content_idle(Duration::from_secs(5));
+        assert_eq!(policy.idle_threshold(false), Duration::from_secs(300));
+        assert_eq!(policy.idle_threshold(true), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,547 @@
+//! Typed adapters over interaction streams.
+//!
+//! [`InteractionStreamExt`] turns a stream of [`StreamEvent`]s or
+//! [`AutoFunctionStreamEvent`]s into the pieces a frontend usually renders
+//! separately: answer text, thought signatures, function calls, and snapshots
+//! of a structured-output JSON document as it streams in. It works on
+//! the streams returned by `create_stream()`, `create_stream_with_auto_functions()`
+//! and `get_interaction_stream()`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use genai_rs::{Client, InteractionStreamExt};
+//!
+//! # async fn example() -> Result<(), genai_rs::GenaiError> {
+//! let client = Client::new("your-api-key".to_string());
+//!
+//! let (mut text, mut events) = client
+//!     .interaction()
+//!     .with_model("gemini-3-flash-preview")
+//!     .with_text("Explain SSE in one paragraph")
+//!     .create_stream()
+//!     .split_text();
+//!
+//! let render_text = async {
+//!     while let Some(delta) = text.next().await {
+//!         print!("{delta}");
+//!     }
+//! };
+//! let watch_events = async {
+//!     while let Some(event) = events.next().await {
+//!         if event?.is_complete() {
+//!             println!("\n[done]");
+//!         }
+//!     }
+//!     Ok::<_, genai_rs::GenaiError>(())
+//! };
+//! let ((), result) = futures_util::join!(render_text, watch_events);
+//! result?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::fan_out::{Distribute, FanOut};
+use crate::{
+    AutoFunctionStreamEvent, Content, GenaiError, OwnedFunctionCallInfo, PartialJson, StreamEvent,
+};
+
+/// A stream event that may carry a content delta.
+///
+/// Implemented for [`StreamEvent`] and [`AutoFunctionStreamEvent`], so the
+/// adapters in [`InteractionStreamExt`] work on both kinds of stream.
+pub trait DeltaEvent {
+    /// Returns the content if this event is a delta.
+    fn delta(&self) -> Option<&Content>;
+}
+
+impl DeltaEvent for StreamEvent {
+    fn delta(&self) -> Option<&Content> {
+        StreamEvent::delta(self)
+    }
+}
+
+impl DeltaEvent for AutoFunctionStreamEvent {
+    fn delta(&self) -> Option<&Content> {
+        AutoFunctionStreamEvent::delta(self)
+    }
+}
+
+/// Adapters that pick typed items out of an interaction stream.
+///
+/// The delta adapters yield only matching deltas and drop every other event,
+/// while errors are passed through. Use [`split_text()`](Self::split_text) to
+/// keep the other events as well.
+pub trait InteractionStreamExt<E>: Stream<Item = Result<E, GenaiError>> + Sized
+where
+    E: DeltaEvent,
+{
+    /// Yields the text of each text delta.
+    fn text_deltas<'a>(self) -> BoxStream<'a, Result<String, GenaiError>>
+    where
+        Self: Send + 'a,
+    {
+        filter_deltas(self, |content| content.as_text().map(str::to_string))
+    }
+
+    /// Yields the signature of each thought delta.
+    ///
+    /// The API does not expose thought text, only signatures, so this is useful
+    /// for showing that the model is reasoning rather than what it reasons.
+    fn thought_deltas<'a>(self) -> BoxStream<'a, Result<String, GenaiError>>
+    where
+        Self: Send + 'a,
+    {
+        filter_deltas(self, |content| match content {
+            Content::Thought {
+                signature: Some(signature),
+            }
+            | Content::ThoughtSignature { signature } => Some(signature.clone()),
+            _ => None,
+        })
+    }
+
+    /// Yields each function call delta.
+    ///
+    /// Deltas are passed on as received. When a model streams arguments in
+    /// pieces, `args` holds a fragment of the JSON text; the final `Complete`
+    /// response has the merged call.
+    fn function_call_deltas<'a>(self) -> BoxStream<'a, Result<OwnedFunctionCallInfo, GenaiError>>
+    where
+        Self: Send + 'a,
+    {
+        filter_deltas(self, |content| match content {
+            Content::FunctionCall { id, name, args } => Some(OwnedFunctionCallInfo {
+                id: id.clone(),
+                name: name.clone(),
+                args: args.clone(),
+            }),
+            _ => None,
+        })
+    }
+
+    /// Yields a best-effort snapshot of a JSON document streamed as text.
+    ///
+    /// For structured output (`with_response_format()`): after each text
+    /// delta, the text so far is parsed with [`PartialJson`], and the snapshot
+    /// is yielded if it changed. See [`parse_partial_json()`](crate::parse_partial_json)
+    /// for how incomplete values are treated.
+    fn json_snapshots<'a>(self) -> BoxStream<'a, Result<Value, GenaiError>>
+    where
+        Self: Send + 'a,
+    {
+        let mut partial = PartialJson::new();
+        let mut last = None;
+        self.text_deltas()
+            .filter_map(move |item| {
+                let snapshot = match item {
+                    Ok(text) => match partial.push(&text) {
+                        Some(snapshot) if last.as_ref() != Some(snapshot) => {
+                            last = Some(snapshot.clone());
+                            Some(Ok(snapshot.clone()))
+                        }
+                        _ => None,
+                    },
+                    Err(e) => Some(Err(e)),
+                };
+                futures_util::future::ready(snapshot)
+            })
+            .boxed()
+    }
+
+    /// Like [`json_snapshots()`](Self::json_snapshots), but deserializes each
+    /// snapshot into `T`.
+    ///
+    /// Give `T` all-`Option` fields so that snapshots missing some fields still
+    /// deserialize. Snapshots that do not deserialize are skipped.
+    fn partial_json<'a, T>(self) -> BoxStream<'a, Result<T, GenaiError>>
+    where
+        Self: Send + 'a,
+        T: DeserializeOwned + Send + 'a,
+    {
+        self.json_snapshots()
+            .filter_map(|item| {
+                futures_util::future::ready(match item {
+                    Ok(snapshot) => T::deserialize(snapshot).ok().map(Ok),
+                    Err(e) => Some(Err(e)),
+                })
+            })
+            .boxed()
+    }
+
+    /// Splits the stream into text deltas and all remaining events.
+    ///
+    /// The first stream yields the text of each text delta; the second yields
+    /// every other event, including errors. Both halves are driven by whichever
+    /// one is polled, so they can be consumed concurrently or one after the
+    /// other. Events for a half that has not been polled yet are buffered until
+    /// it is, and dropping a half discards its events.
+    fn split_text<'a>(self) -> (BoxStream<'a, String>, BoxStream<'a, Result<E, GenaiError>>)
+    where
+        Self: Send + 'a,
+        E: Send + 'a,
+    {
+        let shared = Arc::new(FanOut::new(
+            self.boxed(),
+            SplitQueues {
+                text: VecDeque::new(),
+                events: VecDeque::new(),
+                text_open: true,
+                events_open: true,
+            },
+        ));
+        let text = SplitHalf {
+            split: Arc::clone(&shared),
+            consumer: TEXT,
+        }
+        .filter_map(|item| async move {
+            match item {
+                Routed::Text(text) => Some(text),
+                Routed::Event(_) => None,
+            }
+        });
+        let events = SplitHalf {
+            split: shared,
+            consumer: EVENTS,
+        }
+        .filter_map(|item| async move {
+            match item {
+                Routed::Event(event) => Some(event),
+                Routed::Text(_) => None,
+            }
+        });
+        (text.boxed(), events.boxed())
+    }
+}
+
+impl<S, E> InteractionStreamExt<E> for S
+where
+    S: Stream<Item = Result<E, GenaiError>>,
+    E: DeltaEvent,
+{
+}
+
+fn filter_deltas<'a, S, E, T>(
+    stream: S,
+    map: impl Fn(&Content) -> Option<T> + Send + 'a,
+) -> BoxStream<'a, Result<T, GenaiError>>
+where
+    S: Stream<Item = Result<E, GenaiError>> + Send + 'a,
+    E: DeltaEvent,
+    T: Send + 'a,
+{
+    stream
+        .filter_map(move |item| {
+            let mapped = match item {
+                Ok(event) => event.delta().and_then(&map).map(Ok),
+                Err(e) => Some(Err(e)),
+            };
+            futures_util::future::ready(mapped)
+        })
+        .boxed()
+}
+
+/// Consumer id of the text half of [`split_text()`](InteractionStreamExt::split_text).
+const TEXT: usize = 0;
+/// Consumer id of the events half.
+const EVENTS: usize = 1;
+
+enum Routed<E> {
+    Text(String),
+    Event(Result<E, GenaiError>),
+}
+
+/// Queues for the two halves of a split stream.
+struct SplitQueues<E> {
+    text: VecDeque<String>,
+    events: VecDeque<Result<E, GenaiError>>,
+    text_open: bool,
+    events_open: bool,
+}
+
+impl<E: DeltaEvent> Distribute for SplitQueues<E> {
+    type Item = Result<E, GenaiError>;
+    type Output = Routed<E>;
+
+    fn distribute(&mut self, item: Self::Item) {
+        match item {
+            Ok(event) => match event.delta().and_then(Content::as_text) {
+                Some(text) => {
+                    if self.text_open {
+                        self.text.push_back(text.to_string());
+                    }
+                }
+                None => {
+                    if self.events_open {
+                        self.events.push_back(Ok(event));
+                    }
+                }
+            },
+            Err(e) => {
+                if self.events_open {
+                    self.events.push_back(Err(e));
+                }
+            }
+        }
+    }
+
+    fn take(&mut self, consumer: usize) -> Option<Routed<E>> {
+        if consumer == TEXT {
+            self.text.pop_front().map(Routed::Text)
+        } else {
+            self.events.pop_front().map(Routed::Event)
+        }
+    }
+}
+
+struct SplitHalf<'a, E: DeltaEvent> {
+    split: Arc<FanOut<'a, SplitQueues<E>>>,
+    consumer: usize,
+}
+
+impl<E: DeltaEvent> Stream for SplitHalf<'_, E> {
+    type Item = Routed<E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.split.poll_next(self.consumer, cx)
+    }
+}
+
+impl<E: DeltaEvent> Drop for SplitHalf<'_, E> {
+    fn drop(&mut self) {
+        let mut state = self.split.lock();
+        let queues = &mut state.consumers;
+        if self.consumer == TEXT {
+            queues.text_open = false;
+            queues.text.clear();
+        } else {
+            queues.events_open = false;
+            queues.events.clear();
+        }
+        drop(state);
+        self.split.leave(self.consumer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AutoFunctionStreamChunk, InteractionResponse, StreamChunk};
+    use futures_util::stream;
+    use serde_json::json;
+
+    fn delta(content: Content) -> Result<StreamEvent, GenaiError> {
+        Ok(StreamEvent::new(StreamChunk::Delta(content), None))
+    }
+
+    fn text(text: &str) -> Result<StreamEvent, GenaiError> {
+        delta(Content::Text {
+            text: Some(text.to_string()),
+            annotations: None,
+        })
+    }
+
+    fn complete() -> Result<StreamEvent, GenaiError> {
+        let response: InteractionResponse = serde_json::from_value(json!({
+            "id": "int-1",
+            "status": "completed",
+            "outputs": [],
+        }))
+        .unwrap();
+        Ok(StreamEvent::new(StreamChunk::Complete(response), None))
+    }
+
+    fn sample() -> Vec<Result<StreamEvent, GenaiError>> {
+        vec![
+            delta(Content::Thought {
+                signature: Some("sig-1".to_string()),
+            }),
+            text("Hel"),
+            delta(Content::FunctionCall {
+                id: Some("call-1".to_string()),
+                name: "lookup".to_string(),
+                args: json!({"q": "x"}),
+            }),
+            text("lo"),
+            delta(Content::ThoughtSignature {
+                signature: "sig-2".to_string(),
+            }),
+            complete(),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_text_deltas() {
+        let text: Vec<_> = stream::iter(sample())
+            .text_deltas()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(text, ["Hel", "lo"]);
+    }
+
+    #[tokio::test]
+    async fn test_thought_deltas() {
+        let signatures: Vec<_> = stream::iter(sample())
+            .thought_deltas()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(signatures, ["sig-1", "sig-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_function_call_deltas() {
+        let calls: Vec<_> = stream::iter(sample())
+            .function_call_deltas()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id.as_deref(), Some("call-1"));
+        assert_eq!(calls[0].name, "lookup");
+        assert_eq!(calls[0].args, json!({"q": "x"}));
+    }
+
+    #[tokio::test]
+    async fn test_delta_adapters_pass_errors_through() {
+        let items = vec![
+            text("a"),
+            Err(GenaiError::InvalidInput("boom".to_string())),
+            text("b"),
+        ];
+        let results: Vec<_> = stream::iter(items).text_deltas().collect().await;
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(GenaiError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_adapters_work_on_auto_function_streams() {
+        let items: Vec<Result<_, GenaiError>> = vec![
+            Ok(AutoFunctionStreamEvent::new(
+                AutoFunctionStreamChunk::Delta(Content::Text {
+                    text: Some("Hi".to_string()),
+                    annotations: None,
+                }),
+                None,
+            )),
+            Ok(AutoFunctionStreamEvent::new(
+                AutoFunctionStreamChunk::FunctionResults(vec![]),
+                None,
+            )),
+        ];
+        let text: Vec<_> = stream::iter(items)
+            .text_deltas()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(text, ["Hi"]);
+    }
+
+    #[tokio::test]
+    async fn test_json_snapshots_yield_changed_snapshots() {
+        let items = vec![
+            text(r#"{"title": "Q"#),
+            text("3"),
+            text(r#"", "pages": 1"#),
+            text("2}"),
+        ];
+        let snapshots: Vec<_> = stream::iter(items)
+            .json_snapshots()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            snapshots,
+            [
+                json!({"title": "Q"}),
+                json!({"title": "Q3"}),
+                json!({"title": "Q3", "pages": 12}),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_json_deserializes_typed_snapshots() {
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Report {
+            title: Option<String>,
+            pages: Option<u32>,
+        }
+
+        let items = vec![text(r#"{"title": "Q3", "pa"#), text(r#"ges": 12}"#)];
+        let reports: Vec<Report> = stream::iter(items)
+            .partial_json()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            reports,
+            [
+                Report {
+                    title: Some("Q3".to_string()),
+                    pages: None
+                },
+                Report {
+                    title: Some("Q3".to_string()),
+                    pages: Some(12)
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_text_consumed_sequentially() {
+        let (text, events) = stream::iter(sample()).split_text();
+
+        // Draining the text half first buffers the events for the other half
+        let text: Vec<_> = text.collect().await;
+        let events: Vec<_> = events.map(Result::unwrap).collect().await;
+
+        assert_eq!(text, ["Hel", "lo"]);
+        assert_eq!(events.len(), 4);
+        assert!(events[0].delta().is_some_and(Content::is_thought));
+        assert!(events[3].is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_split_text_consumed_concurrently() {
+        let (source_tx, source_rx) = tokio::sync::mpsc::unbounded_channel();
+        let source = stream::unfold(source_rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        });
+        let (text, events) = source.split_text();
+
+        let consume_text = tokio::spawn(text.collect::<Vec<_>>());
+        let consume_events = tokio::spawn(events.map(Result::unwrap).collect::<Vec<_>>());
+        for item in sample() {
+            source_tx.send(item).unwrap();
+            tokio::task::yield_now().await;
+        }
+        drop(source_tx);
+
+        assert_eq!(consume_text.await.unwrap(), ["Hel", "lo"]);
+        assert_eq!(consume_events.await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_split_text_dropped_half_does_not_block_other() {
+        let (text, events) = stream::iter(sample()).split_text();
+        drop(events);
+
+        let text: Vec<_> = text.collect().await;
+        assert_eq!(text, ["Hel", "lo"]);
+    }
+}
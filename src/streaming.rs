@@ -52,7 +52,7 @@
 
 use std::time::Duration;
 
-use crate::{Content, InteractionResponse};
+use crate::{Content, InteractionResponse, Turn};
 use serde::{Deserialize, Serialize};
 
 /// A function call that is about to be executed.
@@ -119,11 +119,20 @@ pub enum AutoFunctionStreamChunk {
     /// Incremental content from the model (text, thoughts, etc.)
     Delta(Content),
 
+    /// A function call is waiting on the approval policy.
+    ///
+    /// Yielded before each call is passed to the policy set with
+    /// `with_approval_policy()`, so a UI can prompt the user. The stream
+    /// continues once the policy returns its decision.
+    AwaitingApproval(PendingFunctionCall),
+
     /// Function calls detected, about to execute.
     ///
     /// This event is yielded when the model requests function calls and
     /// before the functions are executed. The `pending_calls` field contains
-    /// the function calls that are about to be executed.
+    /// the function calls that are about to be executed. With an approval
+    /// policy, denied calls are left out and modified arguments are shown;
+    /// the event is skipped if every call was denied.
     ///
     /// **Note**: In streaming mode, function calls arrive incrementally via
     /// `Delta` chunks. The `pending_calls` list is built from accumulated deltas
@@ -162,6 +171,15 @@ pub enum AutoFunctionStreamChunk {
     /// all partial results.
     MaxLoopsReached(InteractionResponse),
 
+    /// Full conversation transcript of a stateless auto-function loop.
+    ///
+    /// Yielded just before `Complete` or `MaxLoopsReached` when storage is
+    /// disabled with `with_store_disabled()`. Holds the original input turns
+    /// followed by each model turn and function result turn, ending with the
+    /// final model turn. Persist it and pass it to `with_history()` to
+    /// continue the conversation.
+    Transcript(Vec<Turn>),
+
     /// Unknown event type (for forward compatibility).
     ///
     /// This variant is used when deserializing JSON that contains an unrecognized
@@ -197,6 +215,15 @@ impl AutoFunctionStreamChunk {
         matches!(self, Self::Complete(_))
     }
 
+    /// Returns the content if this chunk is a Delta variant.
+    #[must_use]
+    pub const fn delta(&self) -> Option<&Content> {
+        match self {
+            Self::Delta(content) => Some(content),
+            _ => None,
+        }
+    }
+
     /// Returns the chunk type name if this is an unknown chunk type.
     ///
     /// Returns `None` for known chunk types.
@@ -234,6 +261,12 @@ impl Serialize for AutoFunctionStreamChunk {
                 map.serialize_entry("data", content)?;
                 map.end()
             }
+            Self::AwaitingApproval(call) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("chunk_type", "awaiting_approval")?;
+                map.serialize_entry("data", call)?;
+                map.end()
+            }
             Self::ExecutingFunctions {
                 response,
                 pending_calls,
@@ -266,6 +299,12 @@ impl Serialize for AutoFunctionStreamChunk {
                 map.serialize_entry("data", response)?;
                 map.end()
             }
+            Self::Transcript(turns) => {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("chunk_type", "transcript")?;
+                map.serialize_entry("data", turns)?;
+                map.end()
+            }
             Self::Unknown { chunk_type, data } => {
                 let mut map = serializer.serialize_map(None)?;
                 map.serialize_entry("chunk_type", chunk_type)?;
@@ -330,6 +369,16 @@ impl<'de> Deserialize<'de> for AutoFunctionStreamChunk {
                 })?;
                 Ok(Self::Delta(content))
             }
+            "awaiting_approval" => {
+                let data = extract_data_field(&value, "AwaitingApproval");
+                let call: PendingFunctionCall = serde_json::from_value(data).map_err(|e| {
+                    serde::de::Error::custom(format!(
+                        "Failed to deserialize AutoFunctionStreamChunk::AwaitingApproval data: {}",
+                        e
+                    ))
+                })?;
+                Ok(Self::AwaitingApproval(call))
+            }
             "executing_functions" => {
                 let data = extract_data_field(&value, "ExecutingFunctions");
 
@@ -392,6 +441,16 @@ impl<'de> Deserialize<'de> for AutoFunctionStreamChunk {
                 })?;
                 Ok(Self::MaxLoopsReached(response))
             }
+            "transcript" => {
+                let data = extract_data_field(&value, "Transcript");
+                let turns: Vec<Turn> = serde_json::from_value(data).map_err(|e| {
+                    serde::de::Error::custom(format!(
+                        "Failed to deserialize AutoFunctionStreamChunk::Transcript data: {}",
+                        e
+                    ))
+                })?;
+                Ok(Self::Transcript(turns))
+            }
             other => {
                 tracing::warn!(
                     "Encountered unknown AutoFunctionStreamChunk type '{}'. \
@@ -501,6 +560,12 @@ impl AutoFunctionStreamEvent {
         self.chunk.is_complete()
     }
 
+    /// Returns the content if the inner chunk is a Delta variant.
+    #[must_use]
+    pub const fn delta(&self) -> Option<&Content> {
+        self.chunk.delta()
+    }
+
     /// Check if the inner chunk is an Unknown variant.
     #[must_use]
     pub const fn is_unknown(&self) -> bool {
@@ -591,6 +656,18 @@ pub struct FunctionExecutionResult {
     /// How long the function took to execute
     #[serde(with = "duration_millis")]
     pub duration: Duration,
+    /// Whether the call was stopped by its execution timeout.
+    ///
+    /// A timed-out call's `result` is an error object sent to the model in
+    /// place of the function's output.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub timed_out: bool,
+    /// Whether the call was denied by the approval policy and never executed.
+    ///
+    /// A denied call's `result` is an error object carrying the denial reason,
+    /// and its `duration` is zero.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub denied: bool,
 }
 
 impl FunctionExecutionResult {
@@ -609,14 +686,32 @@ impl FunctionExecutionResult {
             args,
             result,
             duration,
+            timed_out: false,
+            denied: false,
         }
     }
 
+    /// Marks this execution as stopped by its timeout.
+    #[must_use]
+    pub const fn with_timed_out(mut self, timed_out: bool) -> Self {
+        self.timed_out = timed_out;
+        self
+    }
+
+    /// Marks this call as denied by the approval policy.
+    #[must_use]
+    pub const fn with_denied(mut self, denied: bool) -> Self {
+        self.denied = denied;
+        self
+    }
+
     /// Returns true if this execution resulted in an error.
     ///
     /// Errors occur when:
     /// - The function was not found in the registry or tool service
     /// - The function execution failed (panicked or returned an error)
+    /// - The function exceeded its execution timeout
+    /// - The approval policy denied the call
     ///
     /// # Example
     ///
@@ -740,6 +835,15 @@ pub struct AutoFunctionResult {
     /// partial results that may still be useful.
     #[serde(default)]
     pub reached_max_loops: bool,
+    /// Full conversation transcript, set only when storage was disabled.
+    ///
+    /// With `with_store_disabled()`, the loop resends the whole conversation
+    /// each round instead of chaining interactions on the server. This holds
+    /// the input turns, each model turn (including thought signatures) and
+    /// function result turn, and the final model turn, for the caller to
+    /// persist and pass to `with_history()` later.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<Vec<Turn>>,
 }
 
 impl AutoFunctionResult {
@@ -822,6 +926,7 @@ impl AutoFunctionResult {
 #[derive(Clone, Debug, Default)]
 pub struct AutoFunctionResultAccumulator {
     executions: Vec<FunctionExecutionResult>,
+    transcript: Option<Vec<Turn>>,
 }
 
 impl AutoFunctionResultAccumulator {
@@ -840,7 +945,7 @@ impl AutoFunctionResultAccumulator {
     /// Returns `None` for all other chunk types.
     ///
     /// The accumulator collects all `FunctionResults` chunks and combines them
-    /// with the final response.
+    /// with the final response, along with the `Transcript` of a stateless loop.
     #[must_use]
     #[allow(unreachable_patterns)] // Handle future variants from #[non_exhaustive] enum
     pub fn push(&mut self, chunk: AutoFunctionStreamChunk) -> Option<AutoFunctionResult> {
@@ -849,17 +954,24 @@ impl AutoFunctionResultAccumulator {
                 self.executions.extend(results);
                 None
             }
+            AutoFunctionStreamChunk::Transcript(turns) => {
+                self.transcript = Some(turns);
+                None
+            }
             AutoFunctionStreamChunk::Complete(response) => Some(AutoFunctionResult {
                 response,
                 executions: std::mem::take(&mut self.executions),
                 reached_max_loops: false,
+                transcript: self.transcript.take(),
             }),
             AutoFunctionStreamChunk::MaxLoopsReached(response) => Some(AutoFunctionResult {
                 response,
                 executions: std::mem::take(&mut self.executions),
                 reached_max_loops: true,
+                transcript: self.transcript.take(),
             }),
             AutoFunctionStreamChunk::Delta(_)
+            | AutoFunctionStreamChunk::AwaitingApproval(_)
             | AutoFunctionStreamChunk::ExecutingFunctions { .. } => None,
             // Handle future variants gracefully
             _ => None,
@@ -877,6 +989,7 @@ impl AutoFunctionResultAccumulator {
     /// Resets the accumulator to its initial empty state.
     pub fn reset(&mut self) {
         self.executions.clear();
+        self.transcript = None;
     }
 }
 
@@ -916,6 +1029,8 @@ mod tests {
             args: json!({}),
             result: json!({"ok": true}),
             duration: Duration::from_millis(10),
+            timed_out: false,
+            denied: false,
         }]);
 
         // Note: ExecutingFunctions and Complete require InteractionResponse which is harder to construct in tests
@@ -947,6 +1062,54 @@ mod tests {
         assert_eq!(deserialized, result);
     }
 
+    #[test]
+    fn test_function_execution_result_timed_out_roundtrip() {
+        let result = FunctionExecutionResult::new(
+            "slow",
+            "call-1",
+            json!({}),
+            json!({"error": "Function 'slow' timed out after 1s", "timed_out": true}),
+            Duration::from_secs(1),
+        );
+        // Omitted while false, so older serialized results still parse
+        assert!(
+            serde_json::to_value(&result)
+                .unwrap()
+                .get("timed_out")
+                .is_none()
+        );
+
+        let result = result.with_timed_out(true);
+        assert!(result.is_error());
+        let json_str = serde_json::to_string(&result).unwrap();
+        let deserialized: FunctionExecutionResult = serde_json::from_str(&json_str).unwrap();
+        assert!(deserialized.timed_out);
+    }
+
+    #[test]
+    fn test_function_execution_result_denied_roundtrip() {
+        let result = FunctionExecutionResult::new(
+            "send_email",
+            "call-1",
+            json!({"to": "a@example.com"}),
+            json!({"error": "Function 'send_email' was denied: not now", "denied": true}),
+            Duration::ZERO,
+        );
+        assert!(
+            serde_json::to_value(&result)
+                .unwrap()
+                .get("denied")
+                .is_none()
+        );
+
+        let result = result.with_denied(true);
+        assert!(result.is_error());
+        let deserialized: FunctionExecutionResult =
+            serde_json::from_value(serde_json::to_value(&result).unwrap()).unwrap();
+        assert!(deserialized.denied);
+        assert!(!deserialized.timed_out);
+    }
+
     #[test]
     fn test_auto_function_stream_chunk_serialization_roundtrip() {
         // Test Delta variant roundtrip
@@ -1088,6 +1251,7 @@ mod tests {
                 ),
             ],
             reached_max_loops: false,
+            transcript: None,
         };
 
         // Serialize
@@ -1195,6 +1359,7 @@ mod tests {
                 Duration::from_millis(50),
             )],
             reached_max_loops: true,
+            transcript: None,
         };
 
         // Serialize
@@ -1345,6 +1510,50 @@ mod tests {
         assert_eq!(result.response.id.as_deref(), Some("max-loops-response"));
     }
 
+    #[test]
+    fn test_accumulator_keeps_transcript() {
+        let transcript = vec![
+            Turn::user("Weather in Paris?"),
+            Turn::model(vec![Content::function_call_with_id(
+                Some("call-1"),
+                "get_weather",
+                json!({"city": "Paris"}),
+            )]),
+            Turn::user(vec![Content::function_result(
+                "get_weather",
+                "call-1",
+                json!({"temp": 18}),
+            )]),
+            Turn::model("It's 18°C in Paris."),
+        ];
+        let chunk = AutoFunctionStreamChunk::Transcript(transcript.clone());
+
+        let value = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(value["chunk_type"], "transcript");
+        let chunk: AutoFunctionStreamChunk = serde_json::from_value(value).unwrap();
+
+        let mut accumulator = AutoFunctionResultAccumulator::new();
+        assert!(accumulator.push(chunk).is_none());
+        let response: crate::InteractionResponse =
+            serde_json::from_value(json!({"id": "int-2", "status": "completed"})).unwrap();
+        let result = accumulator
+            .push(AutoFunctionStreamChunk::Complete(response))
+            .unwrap();
+        assert_eq!(result.transcript, Some(transcript));
+
+        // Omitted from serialized results when absent
+        let result = AutoFunctionResult {
+            transcript: None,
+            ..result
+        };
+        assert!(
+            serde_json::to_value(&result)
+                .unwrap()
+                .get("transcript")
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_auto_function_stream_event_with_event_id_roundtrip() {
         let event = AutoFunctionStreamEvent::new(
@@ -1444,6 +1653,24 @@ mod tests {
         assert_eq!(deserialized, call);
     }
 
+    #[test]
+    fn test_awaiting_approval_chunk_roundtrip() {
+        let call = PendingFunctionCall::new("delete_record", "call-9", json!({"id": 42}));
+        let chunk = AutoFunctionStreamChunk::AwaitingApproval(call.clone());
+
+        let value = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(value["chunk_type"], "awaiting_approval");
+        assert_eq!(value["data"]["name"], "delete_record");
+
+        let deserialized: AutoFunctionStreamChunk = serde_json::from_value(value).unwrap();
+        match deserialized {
+            AutoFunctionStreamChunk::AwaitingApproval(deserialized) => {
+                assert_eq!(deserialized, call);
+            }
+            other => panic!("Expected AwaitingApproval, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_executing_functions_new_format_roundtrip() {
         use crate::InteractionStatus;
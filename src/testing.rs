@@ -0,0 +1,672 @@
+//! Test utilities for code built on [`Client`].
+//!
+//! Requires the `testing` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! genai-rs = { version = "...", features = ["testing"] }
+//! ```
+//!
+//! [`MockServer`] binds a local port and answers the client's HTTP requests
+//! from a script of [`MockResponse`]s, so tests can exercise
+//! [`InteractionBuilder`](crate::InteractionBuilder), streaming, retries and
+//! auto-function loops without an API key. Every request is recorded as a
+//! [`ReceivedRequest`] for assertions.
+//!
+//! # Example
+//!
+//! ```
+//! use genai_rs::testing::{MockResponse, MockServer};
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let server = MockServer::start().await;
+//! server
+//!     .enqueue(MockResponse::function_call("int-1", "call-1", "get_weather", json!({"city": "Paris"})))
+//!     .enqueue(MockResponse::text("int-2", "It's sunny in Paris."));
+//!
+//! let client = server.client();
+//! let first = client
+//!     .interaction()
+//!     .with_model("gemini-3-flash-preview")
+//!     .with_text("Weather in Paris?")
+//!     .create()
+//!     .await
+//!     .unwrap();
+//! assert_eq!(first.function_calls().len(), 1);
+//!
+//! server.assert_request_count(1);
+//! let request = server.requests()[0].json().unwrap();
+//! assert_eq!(request["model"], "gemini-3-flash-preview");
+//! # }
+//! ```
+
+use crate::{Client, ClientBuilder};
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// A request received by a [`MockServer`].
+#[derive(Clone, Debug)]
+pub struct ReceivedRequest {
+    /// HTTP method (e.g., `POST`)
+    pub method: String,
+    /// Path including the query string (e.g., `/v1beta/interactions?alt=sse`)
+    pub path: String,
+    /// Request headers; names are lowercased
+    pub headers: Vec<(String, String)>,
+    /// Raw request body
+    pub body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    /// Returns the first value of a header (case-insensitive name match).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the body as UTF-8 text (lossy).
+    #[must_use]
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Parses the body as JSON, or returns `None` if it is not valid JSON.
+    #[must_use]
+    pub fn json(&self) -> Option<Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+/// A scripted response for a [`MockServer`] to send.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    event_delay: Option<Duration>,
+    disconnect_after_events: Option<usize>,
+    stall_after_events: Option<usize>,
+}
+
+impl MockResponse {
+    /// A JSON response with the given status and raw body.
+    #[must_use]
+    pub fn json(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.into().into_bytes(),
+            event_delay: None,
+            disconnect_after_events: None,
+            stall_after_events: None,
+        }
+    }
+
+    /// A `text/event-stream` response with the given raw SSE body.
+    #[must_use]
+    pub fn sse(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/event-stream".to_string())],
+            body: body.into().into_bytes(),
+            event_delay: None,
+            disconnect_after_events: None,
+            stall_after_events: None,
+        }
+    }
+
+    /// An SSE response with one `data:` frame per event.
+    #[must_use]
+    pub fn sse_events(events: impl IntoIterator<Item = Value>) -> Self {
+        Self::sse(
+            events
+                .into_iter()
+                .map(|event| format!("data: {event}\n\n"))
+                .collect::<String>(),
+        )
+    }
+
+    /// A completed interaction whose only output is `text`.
+    #[must_use]
+    pub fn text(interaction_id: &str, text: &str) -> Self {
+        Self::json(
+            200,
+            json!({
+                "id": interaction_id,
+                "status": "completed",
+                "outputs": [{"type": "text", "text": text}],
+            })
+            .to_string(),
+        )
+    }
+
+    /// An interaction that requires action: the model calls one function.
+    #[must_use]
+    pub fn function_call(interaction_id: &str, call_id: &str, name: &str, args: Value) -> Self {
+        Self::function_calls(interaction_id, [(call_id, name, args)])
+    }
+
+    /// An interaction that requires action: the model calls several functions
+    /// in parallel. Each call is `(call_id, name, args)`.
+    #[must_use]
+    pub fn function_calls<'a>(
+        interaction_id: &str,
+        calls: impl IntoIterator<Item = (&'a str, &'a str, Value)>,
+    ) -> Self {
+        Self::json(
+            200,
+            json!({
+                "id": interaction_id,
+                "status": "requires_action",
+                "outputs": function_call_outputs(calls),
+            })
+            .to_string(),
+        )
+    }
+
+    /// A streamed interaction: `interaction.start`, one `content.delta` per
+    /// text chunk, then `interaction.complete`. Events carry ids `evt_1`, `evt_2`, ...
+    #[must_use]
+    pub fn text_stream(interaction_id: &str, chunks: &[&str]) -> Self {
+        let deltas = chunks
+            .iter()
+            .map(|text| content_delta(0, json!({"type": "text", "text": text})));
+        Self::sse_events(numbered(stream_events(interaction_id, deltas, "completed")))
+    }
+
+    /// A streamed interaction that ends in `requires_action` after the model
+    /// calls one function.
+    #[must_use]
+    pub fn function_call_stream(
+        interaction_id: &str,
+        call_id: &str,
+        name: &str,
+        args: Value,
+    ) -> Self {
+        Self::function_calls_stream(interaction_id, [(call_id, name, args)])
+    }
+
+    /// A streamed interaction that ends in `requires_action` after the model
+    /// calls several functions in parallel, one `content.delta` per call at
+    /// indexes 0, 1, ...
+    #[must_use]
+    pub fn function_calls_stream<'a>(
+        interaction_id: &str,
+        calls: impl IntoIterator<Item = (&'a str, &'a str, Value)>,
+    ) -> Self {
+        // Each call is its own content block
+        let deltas = function_call_outputs(calls)
+            .into_iter()
+            .enumerate()
+            .map(|(index, call)| content_delta(index, call));
+        Self::sse_events(numbered(stream_events(
+            interaction_id,
+            deltas,
+            "requires_action",
+        )))
+    }
+
+    /// An API error response in the Google error format.
+    #[must_use]
+    pub fn error(status: u16, message: &str) -> Self {
+        Self::json(
+            status,
+            json!({"error": {"code": status, "message": message}}).to_string(),
+        )
+    }
+
+    /// Adds a response header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Adds a `Retry-After` header (whole seconds).
+    #[must_use]
+    pub fn with_retry_after(self, delay: Duration) -> Self {
+        self.with_header("Retry-After", delay.as_secs().to_string())
+    }
+
+    /// Waits `delay` before sending each SSE event (or the body of a
+    /// non-streaming response).
+    #[must_use]
+    pub fn with_event_delay(mut self, delay: Duration) -> Self {
+        self.event_delay = Some(delay);
+        self
+    }
+
+    /// Closes the connection after sending the first `events` SSE events,
+    /// simulating a dropped stream. The client sees an incomplete body.
+    #[must_use]
+    pub fn disconnect_after_events(mut self, events: usize) -> Self {
+        self.disconnect_after_events = Some(events);
+        self
+    }
+
+    /// Stops sending after the first `events` SSE events but keeps the
+    /// connection open until the client closes it, simulating a stalled stream.
+    #[must_use]
+    pub fn stall_after_events(mut self, events: usize) -> Self {
+        self.stall_after_events = Some(events);
+        self
+    }
+
+    /// Splits the body into the chunks written to the socket: one per SSE
+    /// event for streams, otherwise the whole body.
+    fn chunks(&self) -> Vec<&[u8]> {
+        let is_sse = self
+            .headers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case("content-type") && v == "text/event-stream");
+        if !is_sse {
+            return vec![&self.body];
+        }
+        let mut chunks = Vec::new();
+        let mut rest = self.body.as_slice();
+        while let Some(pos) = find(rest, b"\n\n") {
+            chunks.push(&rest[..pos + 2]);
+            rest = &rest[pos + 2..];
+        }
+        if !rest.is_empty() {
+            chunks.push(rest);
+        }
+        chunks
+    }
+}
+
+fn function_call_outputs<'a>(
+    calls: impl IntoIterator<Item = (&'a str, &'a str, Value)>,
+) -> Vec<Value> {
+    calls
+        .into_iter()
+        .map(|(id, name, args)| {
+            json!({"type": "function_call", "id": id, "name": name, "arguments": args})
+        })
+        .collect()
+}
+
+fn content_delta(index: usize, delta: Value) -> Value {
+    json!({"event_type": "content.delta", "index": index, "delta": delta})
+}
+
+fn stream_events(
+    interaction_id: &str,
+    deltas: impl Iterator<Item = Value>,
+    final_status: &str,
+) -> Vec<Value> {
+    let mut events = vec![json!({
+        "event_type": "interaction.start",
+        "interaction": {"id": interaction_id, "status": "in_progress"},
+    })];
+    events.extend(deltas);
+    events.push(json!({
+        "event_type": "interaction.complete",
+        "interaction": {"id": interaction_id, "status": final_status},
+    }));
+    events
+}
+
+fn numbered(mut events: Vec<Value>) -> Vec<Value> {
+    for (i, event) in events.iter_mut().enumerate() {
+        event["event_id"] = json!(format!("evt_{}", i + 1));
+    }
+    events
+}
+
+type Handler = dyn Fn(&ReceivedRequest, &str) -> MockResponse + Send + Sync;
+
+struct Shared {
+    requests: Mutex<Vec<ReceivedRequest>>,
+    script: Mutex<VecDeque<MockResponse>>,
+    handler: Option<Box<Handler>>,
+}
+
+/// A local HTTP server bound to an ephemeral port on 127.0.0.1.
+///
+/// Responses come either from a FIFO script filled with
+/// [`enqueue()`](Self::enqueue), or from a handler closure passed to
+/// [`start_with()`](Self::start_with). Once the script is exhausted, requests
+/// are answered with HTTP 500.
+///
+/// Each connection serves one request. The server task is aborted when this
+/// value is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    task: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockServer")
+            .field("addr", &self.addr)
+            .field("requests", &lock(&self.shared.requests).len())
+            .field("scripted", &lock(&self.shared.script).len())
+            .finish()
+    }
+}
+
+impl MockServer {
+    /// Starts a server that answers requests from the script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub async fn start() -> Self {
+        Self::spawn(None).await
+    }
+
+    /// Starts a server that answers every request with `handler(request, base_url)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub async fn start_with<F>(handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest, &str) -> MockResponse + Send + Sync + 'static,
+    {
+        Self::spawn(Some(Box::new(handler))).await
+    }
+
+    async fn spawn(handler: Option<Box<Handler>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        let base_url = format!("http://{addr}");
+        let shared = Arc::new(Shared {
+            requests: Mutex::new(Vec::new()),
+            script: Mutex::new(VecDeque::new()),
+            handler,
+        });
+
+        let state = shared.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let state = state.clone();
+                let base_url = base_url.clone();
+                tokio::spawn(async move {
+                    serve_connection(socket, &base_url, &state).await;
+                });
+            }
+        });
+
+        Self { addr, shared, task }
+    }
+
+    /// The base URL to pass to `ClientBuilder::with_base_url()`.
+    #[must_use]
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A client builder pointed at this server with a placeholder API key.
+    #[must_use]
+    pub fn client_builder(&self) -> ClientBuilder {
+        Client::builder("test-api-key".to_string()).with_base_url(self.base_url())
+    }
+
+    /// A client pointed at this server with a placeholder API key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client cannot be built.
+    #[must_use]
+    pub fn client(&self) -> Client {
+        self.client_builder().build().expect("mock client builds")
+    }
+
+    /// Appends a response to the script. Requests consume scripted responses
+    /// in order.
+    pub fn enqueue(&self, response: MockResponse) -> &Self {
+        lock(&self.shared.script).push_back(response);
+        self
+    }
+
+    /// All requests received so far, in arrival order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        lock(&self.shared.requests).clone()
+    }
+
+    /// Asserts that exactly `expected` requests were received.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the received methods and paths if the count differs.
+    #[track_caller]
+    pub fn assert_request_count(&self, expected: usize) {
+        let requests = self.requests();
+        assert_eq!(
+            requests.len(),
+            expected,
+            "expected {expected} requests, received: {:?}",
+            requests
+                .iter()
+                .map(|r| format!("{} {}", r.method, r.path))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    /// Asserts that every scripted response has been served.
+    ///
+    /// # Panics
+    ///
+    /// Panics if responses remain in the script.
+    #[track_caller]
+    pub fn assert_script_consumed(&self) {
+        let remaining = lock(&self.shared.script).len();
+        assert_eq!(
+            remaining, 0,
+            "{remaining} scripted responses were not served"
+        );
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+async fn serve_connection(mut socket: TcpStream, base_url: &str, shared: &Shared) {
+    let Some(request) = read_request(&mut socket).await else {
+        return;
+    };
+    let response = match &shared.handler {
+        Some(handler) => handler(&request, base_url),
+        None => lock(&shared.script).pop_front().unwrap_or_else(|| {
+            MockResponse::error(
+                500,
+                &format!(
+                    "MockServer script exhausted: no response for {} {}",
+                    request.method, request.path
+                ),
+            )
+        }),
+    };
+    lock(&shared.requests).push(request);
+
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    if socket.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+
+    let chunks = response.chunks();
+    let limit = response
+        .disconnect_after_events
+        .or(response.stall_after_events)
+        .unwrap_or(chunks.len());
+    for chunk in chunks.into_iter().take(limit) {
+        if let Some(delay) = response.event_delay {
+            tokio::time::sleep(delay).await;
+        }
+        if socket.write_all(chunk).await.is_err() {
+            return;
+        }
+        let _ = socket.flush().await;
+    }
+    if response.stall_after_events.is_some() {
+        // Hold the connection open until the client gives up on it
+        let mut buf = [0u8; 64];
+        while matches!(socket.read(&mut buf).await, Ok(n) if n > 0) {}
+    }
+    let _ = socket.shutdown().await;
+}
+
+async fn read_request(socket: &mut TcpStream) -> Option<ReceivedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    // Read until the end of the header block
+    let header_end = loop {
+        if let Some(pos) = find(&buffer, b"\r\n\r\n") {
+            break pos;
+        }
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.clone())
+    };
+
+    if let Some(len) = header("content-length").and_then(|v| v.parse::<usize>().ok()) {
+        while body.len() < len {
+            let n = socket.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+    } else if header("transfer-encoding").is_some_and(|v| v.contains("chunked")) {
+        while find(&body, b"0\r\n\r\n").is_none() {
+            let n = socket.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        body = decode_chunked(&body);
+    }
+
+    Some(ReceivedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+fn decode_chunked(mut data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    while let Some(line_end) = find(data, b"\r\n") {
+        let size_str = String::from_utf8_lossy(&data[..line_end]);
+        let Ok(size) = usize::from_str_radix(size_str.trim(), 16) else {
+            break;
+        };
+        if size == 0 {
+            break;
+        }
+        let start = line_end + 2;
+        let end = (start + size).min(data.len());
+        out.extend_from_slice(&data[start..end]);
+        data = &data[(end + 2).min(data.len())..];
+    }
+    out
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_chunks_split_per_event() {
+        let response = MockResponse::sse("data: a\n\ndata: b\n\npartial");
+        assert_eq!(
+            response.chunks(),
+            [&b"data: a\n\n"[..], &b"data: b\n\n"[..], &b"partial"[..]]
+        );
+        assert_eq!(MockResponse::json(200, "{}\n\n{}").chunks().len(), 1);
+    }
+
+    #[test]
+    fn test_text_stream_events() {
+        let response = MockResponse::text_stream("int-1", &["Hel", "lo"]);
+        let body = String::from_utf8(response.body).unwrap();
+        let events: Vec<Value> = body
+            .split("\n\n")
+            .filter_map(|frame| frame.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["event_type"], "interaction.start");
+        assert_eq!(events[1]["delta"]["text"], "Hel");
+        assert_eq!(events[3]["event_type"], "interaction.complete");
+        assert_eq!(events[3]["event_id"], "evt_4");
+    }
+}
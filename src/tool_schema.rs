@@ -0,0 +1,198 @@
+//! JSON schemas for `#[tool]` parameter types.
+//!
+//! The `#[tool]` macro describes `String`, numbers, `bool`, `Vec`s of those,
+//! and `serde_json::Value` on its own. Any other parameter type must
+//! implement [`ToolSchema`], which supplies the schema the model sees.
+//! `#[derive(ToolSchema)]` from `genai-rs-macros` implements it for:
+//!
+//! - Structs with named fields: an `object` with one property per field.
+//!   `Option` fields and `#[serde(default)]` fields are not required.
+//! - Enums whose variants are all units: a `string` with `enum` values.
+//!
+//! Doc comments on the type and its fields become `description`s, and
+//! `#[serde(rename = "...")]`, `#[serde(rename_all = "...")]` and
+//! `#[serde(flatten)]` are honored so the schema matches what `serde`
+//! deserializes. In a self-referential type such as
+//! `struct Node { children: Vec<Node> }`, the nested occurrence is described
+//! as a plain `object`.
+//!
+//! # Example
+//!
+//! ```
+//! use genai_rs::{CallableFunction, ToolSchema};
+//! use genai_rs_macros::{ToolSchema, tool};
+//! use serde::Deserialize;
+//!
+//! /// How urgently the ticket needs attention
+//! #[derive(Deserialize, ToolSchema)]
+//! #[serde(rename_all = "lowercase")]
+//! enum Priority {
+//!     Low,
+//!     High,
+//! }
+//!
+//! #[derive(Deserialize, ToolSchema)]
+//! struct Ticket {
+//!     /// One-line summary
+//!     title: String,
+//!     priority: Priority,
+//!     /// Labels to apply, if any
+//!     labels: Option<Vec<String>>,
+//! }
+//!
+//! /// Files a support ticket
+//! #[tool(ticket(description = "The ticket to file"))]
+//! fn file_ticket(ticket: Ticket) -> String {
+//!     format!("Filed: {}", ticket.title)
+//! }
+//!
+//! let declaration = FileTicketCallable.declaration();
+//! let ticket = &declaration.parameters().properties()["ticket"];
+//! assert_eq!(ticket["properties"], Ticket::tool_schema()["properties"]);
+//! assert_eq!(ticket["description"], "The ticket to file");
+//! ```
+
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A type whose JSON schema can appear in a function declaration.
+///
+/// Implemented for primitives, `String`, `Option<T>`, `Vec<T>`, `Box<T>`,
+/// string-keyed `HashMap`s and `BTreeMap`s, and `serde_json::Value`. Derive it
+/// for your own structs and unit enums with `#[derive(ToolSchema)]`; see the
+/// [module documentation](self).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no schema for use as a `#[tool]` parameter",
+    note = "derive it with `#[derive(ToolSchema)]` from `genai_rs_macros`, or take a `serde_json::Value`"
+)]
+pub trait ToolSchema {
+    /// Returns the JSON schema describing this type.
+    ///
+    /// For `Option<T>` this is the schema of `T`; optionality is expressed by
+    /// leaving the field out of the parent's `required` list.
+    fn tool_schema() -> Value;
+}
+
+macro_rules! impl_tool_schema {
+    ($schema_type:literal: $($ty:ty),+) => {
+        $(
+            impl ToolSchema for $ty {
+                fn tool_schema() -> Value {
+                    json!({"type": $schema_type})
+                }
+            }
+        )+
+    };
+}
+
+impl_tool_schema!("string": String, char);
+impl_tool_schema!("integer": i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_tool_schema!("number": f32, f64);
+impl_tool_schema!("boolean": bool);
+impl_tool_schema!("object": Value);
+
+thread_local! {
+    /// Derived types whose schema is being built on this thread.
+    static BUILDING: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// Builds a derived type's schema, or `{"type": "object"}` if that type's
+/// schema is already being built further up the stack.
+///
+/// Used by `#[derive(ToolSchema)]` so that self-referential types terminate.
+#[doc(hidden)]
+pub fn guard_recursion(type_name: &'static str, build: impl FnOnce() -> Value) -> Value {
+    struct Building(&'static str);
+
+    impl Drop for Building {
+        fn drop(&mut self) {
+            BUILDING.with(|types| types.borrow_mut().remove(self.0));
+        }
+    }
+
+    if !BUILDING.with(|types| types.borrow_mut().insert(type_name)) {
+        return json!({"type": "object"});
+    }
+    let _building = Building(type_name);
+    build()
+}
+
+impl<T: ToolSchema> ToolSchema for Option<T> {
+    fn tool_schema() -> Value {
+        T::tool_schema()
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for Box<T> {
+    fn tool_schema() -> Value {
+        T::tool_schema()
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for Vec<T> {
+    fn tool_schema() -> Value {
+        json!({"type": "array", "items": T::tool_schema()})
+    }
+}
+
+impl<T: ToolSchema, S> ToolSchema for HashMap<String, T, S> {
+    fn tool_schema() -> Value {
+        json!({"type": "object", "additionalProperties": T::tool_schema()})
+    }
+}
+
+impl<T: ToolSchema> ToolSchema for BTreeMap<String, T> {
+    fn tool_schema() -> Value {
+        json!({"type": "object", "additionalProperties": T::tool_schema()})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitive_schemas() {
+        assert_eq!(String::tool_schema(), json!({"type": "string"}));
+        assert_eq!(u8::tool_schema(), json!({"type": "integer"}));
+        assert_eq!(f32::tool_schema(), json!({"type": "number"}));
+        assert_eq!(bool::tool_schema(), json!({"type": "boolean"}));
+        assert_eq!(Value::tool_schema(), json!({"type": "object"}));
+    }
+
+    #[test]
+    fn test_wrapper_schemas() {
+        assert_eq!(Option::<i64>::tool_schema(), json!({"type": "integer"}));
+        assert_eq!(Box::<bool>::tool_schema(), json!({"type": "boolean"}));
+        assert_eq!(
+            Vec::<Vec<String>>::tool_schema(),
+            json!({
+                "type": "array",
+                "items": {"type": "array", "items": {"type": "string"}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_guard_recursion_stops_at_reentry() {
+        let schema = guard_recursion(
+            "Node",
+            || json!({"items": guard_recursion("Node", || unreachable!())}),
+        );
+        assert_eq!(schema, json!({"items": {"type": "object"}}));
+
+        // The type is released once its schema is built
+        assert_eq!(guard_recursion("Node", || json!(1)), json!(1));
+    }
+
+    #[test]
+    fn test_map_schemas() {
+        let expected = json!({
+            "type": "object",
+            "additionalProperties": {"type": "number"}
+        });
+        assert_eq!(HashMap::<String, f64>::tool_schema(), expected);
+        assert_eq!(BTreeMap::<String, f64>::tool_schema(), expected);
+    }
+}
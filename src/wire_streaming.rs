@@ -1,9 +1,10 @@
 //! Streaming types for SSE responses.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use crate::content::Content;
-use crate::response::{InteractionResponse, InteractionStatus};
+use crate::response::{InteractionResponse, InteractionStatus, UsageMetadata};
 
 /// A chunk from the streaming API
 ///
@@ -527,15 +528,33 @@ pub struct StreamEvent {
     ///
     /// Pass this to `last_event_id` when calling `get_interaction_stream()` to resume
     /// the stream from this point. Events are ordered, so resuming from an event_id
-    /// will replay all subsequent events.
+    /// will replay all subsequent events. Taken from the payload's `event_id`, or
+    /// from the SSE `id` field when the payload has none.
     pub event_id: Option<String>,
+
+    /// Output position for `Delta` chunks, as sent by the API.
+    ///
+    /// Deltas with the same index belong to the same output; see
+    /// [`StreamAccumulator`] for folding them together.
+    pub index: Option<usize>,
 }
 
 impl StreamEvent {
     /// Creates a new StreamEvent with the given chunk and event_id.
     #[must_use]
     pub fn new(chunk: StreamChunk, event_id: Option<String>) -> Self {
-        Self { chunk, event_id }
+        Self {
+            chunk,
+            event_id,
+            index: None,
+        }
+    }
+
+    /// Sets the output position of a `Delta` chunk.
+    #[must_use]
+    pub fn with_index(mut self, index: Option<usize>) -> Self {
+        self.index = index;
+        self
     }
 
     /// Returns `true` if the chunk is a Delta variant.
@@ -550,6 +569,15 @@ impl StreamEvent {
         matches!(self.chunk, StreamChunk::Complete(_))
     }
 
+    /// Returns the content if the chunk is a Delta variant.
+    #[must_use]
+    pub const fn delta(&self) -> Option<&Content> {
+        match &self.chunk {
+            StreamChunk::Delta(content) => Some(content),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if the chunk is an Unknown variant.
     #[must_use]
     pub const fn is_unknown(&self) -> bool {
@@ -601,6 +629,10 @@ impl Serialize for StreamEvent {
             map.serialize_entry("event_id", event_id)?;
         }
 
+        if let Some(index) = self.index {
+            map.serialize_entry("index", &index)?;
+        }
+
         map.end()
     }
 }
@@ -618,10 +650,264 @@ impl<'de> Deserialize<'de> for StreamEvent {
             .and_then(|v| v.as_str())
             .map(String::from);
 
+        let index = value
+            .get("index")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|i| usize::try_from(i).ok());
+
         // Deserialize the chunk from the same value
         let chunk: StreamChunk = serde_json::from_value(value).map_err(serde::de::Error::custom)?;
 
-        Ok(Self { chunk, event_id })
+        Ok(Self {
+            chunk,
+            event_id,
+            index,
+        })
+    }
+}
+
+/// Folds [`StreamEvent`]s into an [`InteractionResponse`].
+///
+/// Deltas are merged by output index with the same rules the client uses to
+/// build the final `Complete` response: text is concatenated, function call
+/// names and string arguments are appended, and other content at the same
+/// index is replaced. Status, usage and interaction metadata are taken from
+/// `Start`, `StatusUpdate` and `Complete` events as they arrive.
+///
+/// This is useful when consuming raw events yourself, e.g. from a stream
+/// resumed with [`Client::get_interaction_stream()`](crate::Client::get_interaction_stream).
+/// [`snapshot()`](Self::snapshot) can be called at any point; if the stream
+/// ends early, it returns the partial response received so far.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use genai_rs::{Client, StreamAccumulator};
+///
+/// # async fn example() -> Result<(), genai_rs::GenaiError> {
+/// let client = Client::new("your-api-key".to_string());
+/// let mut stream = client.get_interaction_stream("interaction-id", None);
+///
+/// let mut accumulator = StreamAccumulator::new();
+/// while let Some(event) = stream.next().await {
+///     accumulator.push(&event?);
+///     // Render the response so far
+///     println!("{:?}", accumulator.snapshot().as_text());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StreamAccumulator {
+    /// Metadata from the latest `Start` or `Complete` event
+    interaction: Option<InteractionResponse>,
+    interaction_id: Option<String>,
+    status: Option<InteractionStatus>,
+    usage: Option<UsageMetadata>,
+    outputs: BTreeMap<usize, Content>,
+    /// Index opened by the last `ContentStart`, for deltas without an index
+    open_index: Option<usize>,
+    last_event_id: Option<String>,
+    complete: bool,
+}
+
+impl StreamAccumulator {
+    /// Creates a new empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds an event to the accumulator.
+    #[allow(unreachable_patterns)] // Handle future variants from #[non_exhaustive] enum
+    pub fn push(&mut self, event: &StreamEvent) {
+        if event.event_id.is_some() {
+            self.last_event_id.clone_from(&event.event_id);
+        }
+        match &event.chunk {
+            StreamChunk::Start { interaction } => self.update_interaction(interaction),
+            StreamChunk::StatusUpdate {
+                interaction_id,
+                status,
+            } => {
+                self.interaction_id = Some(interaction_id.clone());
+                self.status = Some(status.clone());
+            }
+            StreamChunk::ContentStart { index, .. } => self.open_index = Some(*index),
+            StreamChunk::Delta(delta) => {
+                let index = event
+                    .index
+                    .or(self.open_index)
+                    .unwrap_or_else(|| self.fallback_index(delta));
+                merge_content_at_index(&mut self.outputs, index, delta.clone());
+            }
+            StreamChunk::ContentStop { .. } => self.open_index = None,
+            StreamChunk::Complete(response) => {
+                self.update_interaction(response);
+                self.complete = true;
+            }
+            StreamChunk::Error { .. } => {
+                self.status = Some(InteractionStatus::Failed);
+                self.complete = true;
+            }
+            StreamChunk::Unknown { .. } | _ => {}
+        }
+    }
+
+    /// Returns the response accumulated so far.
+    ///
+    /// Before a `Complete` event arrives, the status is the latest reported one
+    /// (`InProgress` if none was reported) and `outputs` holds the merged deltas
+    /// received so far.
+    #[must_use]
+    pub fn snapshot(&self) -> InteractionResponse {
+        let mut response = self
+            .interaction
+            .clone()
+            .unwrap_or_else(|| empty_response(self.interaction_id.clone()));
+        if response.id.is_none() {
+            response.id.clone_from(&self.interaction_id);
+        }
+        if let Some(status) = &self.status {
+            response.status = status.clone();
+        }
+        if response.usage.is_none() {
+            response.usage.clone_from(&self.usage);
+        }
+        // Streaming `Complete` events carry empty outputs; prefer merged deltas
+        if !self.outputs.is_empty() {
+            response.outputs = self.outputs.values().cloned().collect();
+        }
+        response
+    }
+
+    /// Returns `true` once a `Complete` or `Error` event has been pushed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns the interaction ID, once an event carrying it has been pushed.
+    #[must_use]
+    pub fn interaction_id(&self) -> Option<&str> {
+        self.interaction
+            .as_ref()
+            .and_then(|i| i.id.as_deref())
+            .or(self.interaction_id.as_deref())
+    }
+
+    /// Returns the `event_id` of the last pushed event that had one, for
+    /// resuming with [`Client::get_interaction_stream()`](crate::Client::get_interaction_stream).
+    #[must_use]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    fn update_interaction(&mut self, interaction: &InteractionResponse) {
+        if interaction.usage.is_some() {
+            self.usage.clone_from(&interaction.usage);
+        }
+        self.status = Some(interaction.status.clone());
+        self.interaction = Some(interaction.clone());
+    }
+
+    /// Picks an index for a delta that arrived without one: the last output if
+    /// it has the same content type, otherwise the next position.
+    fn fallback_index(&self, delta: &Content) -> usize {
+        match self.outputs.last_key_value() {
+            Some((&index, last))
+                if std::mem::discriminant(last) == std::mem::discriminant(delta) =>
+            {
+                index
+            }
+            Some((&index, _)) => index + 1,
+            None => 0,
+        }
+    }
+}
+
+/// Merges a delta Content into the accumulated content at a given index.
+///
+/// For text content, this concatenates the text. For function calls, arguments
+/// are merged as JSON string chunks. For other content types, the delta replaces
+/// any existing content.
+pub(crate) fn merge_content_at_index(
+    content_map: &mut BTreeMap<usize, Content>,
+    index: usize,
+    delta: Content,
+) {
+    match content_map.get_mut(&index) {
+        Some(existing) => {
+            // Merge based on content type
+            match (existing, &delta) {
+                // Text content: concatenate the text
+                (
+                    Content::Text {
+                        text: Some(existing_text),
+                        ..
+                    },
+                    Content::Text {
+                        text: Some(delta_text),
+                        ..
+                    },
+                ) => {
+                    existing_text.push_str(delta_text);
+                }
+                // FunctionCall: merge name and arguments as they stream in
+                (
+                    Content::FunctionCall {
+                        name: existing_name,
+                        args: existing_args,
+                        ..
+                    },
+                    Content::FunctionCall {
+                        name: delta_name,
+                        args: delta_args,
+                        ..
+                    },
+                ) => {
+                    // Name is a String, concatenate if delta has content
+                    if !delta_name.is_empty() {
+                        existing_name.push_str(delta_name);
+                    }
+                    // Args: if both are strings, concatenate the JSON string chunks
+                    if let (serde_json::Value::String(es), serde_json::Value::String(ds)) =
+                        (existing_args, delta_args)
+                    {
+                        es.push_str(ds);
+                    }
+                    // If delta args isn't a string, just keep existing
+                }
+                // For other types, just replace
+                _ => {
+                    content_map.insert(index, delta);
+                }
+            }
+        }
+        None => {
+            // No existing content at this index, just insert
+            content_map.insert(index, delta);
+        }
+    }
+}
+
+/// Creates a response with no metadata, for snapshots taken before `Start`.
+fn empty_response(id: Option<String>) -> InteractionResponse {
+    InteractionResponse {
+        id,
+        model: None,
+        agent: None,
+        input: Vec::new(),
+        outputs: Vec::new(),
+        status: InteractionStatus::InProgress,
+        usage: None,
+        tools: None,
+        grounding_metadata: None,
+        url_context_metadata: None,
+        previous_interaction_id: None,
+        created: None,
+        updated: None,
     }
 }
 
@@ -639,6 +925,9 @@ impl<'de> Deserialize<'de> for StreamEvent {
 #[serde(rename_all = "snake_case")]
 pub struct InteractionStreamEvent {
     /// Event type (e.g., "content.delta", "interaction.complete")
+    ///
+    /// Falls back to the SSE `event` field when the payload omits it.
+    #[serde(default)]
     pub event_type: String,
 
     /// The full interaction data (present in "interaction.start" and "interaction.complete")
@@ -1125,4 +1414,165 @@ mod tests {
         assert_eq!(event.event_id.as_deref(), Some("evt_resume_token_123"));
         assert!(event.delta.is_some());
     }
+
+    fn text(text: &str) -> Content {
+        Content::Text {
+            text: Some(text.to_string()),
+            annotations: None,
+        }
+    }
+
+    fn delta(content: Content, index: Option<usize>) -> StreamEvent {
+        StreamEvent::new(StreamChunk::Delta(content), None).with_index(index)
+    }
+
+    fn interaction(status: InteractionStatus) -> InteractionResponse {
+        InteractionResponse {
+            model: Some("gemini-3-flash-preview".to_string()),
+            status,
+            ..empty_response(Some("interaction-1".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_stream_event_index_roundtrip() {
+        let event = delta(text("Hi"), Some(2));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["index"], 2);
+
+        let deserialized: StreamEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.index, Some(2));
+        assert!(deserialized.is_delta());
+    }
+
+    #[test]
+    fn test_accumulator_merges_deltas_by_index() {
+        let mut acc = StreamAccumulator::new();
+        acc.push(&StreamEvent::new(
+            StreamChunk::Start {
+                interaction: interaction(InteractionStatus::InProgress),
+            },
+            Some("evt_1".to_string()),
+        ));
+        acc.push(&delta(text("Hello"), Some(0)));
+        acc.push(&delta(
+            Content::FunctionCall {
+                id: Some("call-1".to_string()),
+                name: "get_".to_string(),
+                args: serde_json::json!("{\"city\":"),
+            },
+            Some(1),
+        ));
+        acc.push(&delta(text(", world"), Some(0)));
+        acc.push(&delta(
+            Content::FunctionCall {
+                id: None,
+                name: "weather".to_string(),
+                args: serde_json::json!("\"Paris\"}"),
+            },
+            Some(1),
+        ));
+
+        let snapshot = acc.snapshot();
+        assert_eq!(snapshot.id.as_deref(), Some("interaction-1"));
+        assert_eq!(snapshot.model.as_deref(), Some("gemini-3-flash-preview"));
+        assert_eq!(snapshot.status, InteractionStatus::InProgress);
+        assert_eq!(snapshot.outputs.len(), 2);
+        assert_eq!(snapshot.as_text(), Some("Hello, world"));
+        match &snapshot.outputs[1] {
+            Content::FunctionCall { name, args, .. } => {
+                assert_eq!(name, "get_weather");
+                assert_eq!(args, &serde_json::json!("{\"city\":\"Paris\"}"));
+            }
+            other => panic!("expected function call, got {other:?}"),
+        }
+        assert!(!acc.is_complete());
+        assert_eq!(acc.last_event_id(), Some("evt_1"));
+    }
+
+    #[test]
+    fn test_accumulator_complete_keeps_merged_outputs_and_usage() {
+        let mut acc = StreamAccumulator::new();
+        acc.push(&delta(text("Partial"), Some(0)));
+        assert_eq!(acc.snapshot().status, InteractionStatus::InProgress);
+        assert_eq!(acc.snapshot().as_text(), Some("Partial"));
+
+        let mut complete = interaction(InteractionStatus::Completed);
+        complete.usage = Some(UsageMetadata {
+            total_tokens: Some(42),
+            ..UsageMetadata::default()
+        });
+        acc.push(&StreamEvent::new(StreamChunk::Complete(complete), None));
+
+        let snapshot = acc.snapshot();
+        assert!(acc.is_complete());
+        assert_eq!(snapshot.status, InteractionStatus::Completed);
+        assert_eq!(snapshot.as_text(), Some("Partial"));
+        assert_eq!(snapshot.usage.and_then(|u| u.total_tokens), Some(42));
+        assert_eq!(acc.interaction_id(), Some("interaction-1"));
+    }
+
+    #[test]
+    fn test_accumulator_uses_complete_outputs_without_deltas() {
+        let mut complete = interaction(InteractionStatus::Completed);
+        complete.outputs = vec![text("Final")];
+        let mut acc = StreamAccumulator::new();
+        acc.push(&StreamEvent::new(StreamChunk::Complete(complete), None));
+        assert_eq!(acc.snapshot().as_text(), Some("Final"));
+    }
+
+    #[test]
+    fn test_accumulator_deltas_without_index() {
+        let mut acc = StreamAccumulator::new();
+        // ContentStart supplies the index for following deltas
+        acc.push(&StreamEvent::new(
+            StreamChunk::ContentStart {
+                index: 3,
+                content_type: Some("text".to_string()),
+            },
+            None,
+        ));
+        acc.push(&delta(text("a"), None));
+        acc.push(&delta(text("b"), None));
+        acc.push(&StreamEvent::new(
+            StreamChunk::ContentStop { index: 3 },
+            None,
+        ));
+        // Otherwise same-type deltas extend the last output and others start a new one
+        acc.push(&delta(text("c"), None));
+        acc.push(&delta(
+            Content::ThoughtSignature {
+                signature: "sig".to_string(),
+            },
+            None,
+        ));
+
+        let outputs = acc.snapshot().outputs;
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].as_text(), Some("abc"));
+        assert!(matches!(outputs[1], Content::ThoughtSignature { .. }));
+    }
+
+    #[test]
+    fn test_accumulator_status_updates_and_errors() {
+        let mut acc = StreamAccumulator::new();
+        acc.push(&StreamEvent::new(
+            StreamChunk::StatusUpdate {
+                interaction_id: "interaction-9".to_string(),
+                status: InteractionStatus::InProgress,
+            },
+            None,
+        ));
+        assert_eq!(acc.snapshot().id.as_deref(), Some("interaction-9"));
+
+        acc.push(&StreamEvent::new(
+            StreamChunk::Error {
+                message: "boom".to_string(),
+                code: None,
+            },
+            None,
+        ));
+        assert!(acc.is_complete());
+        assert_eq!(acc.snapshot().status, InteractionStatus::Failed);
+    }
 }
@@ -0,0 +1,138 @@
+//! API version selection tests
+//!
+//! Verifies that `ClientBuilder::with_api_version()` scopes interaction and
+//! files endpoints under the chosen version, and that clients with different
+//! versions can be used side by side. These tests run offline against a local
+//! stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test api_version_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer, ReceivedRequest};
+use genai_rs::{ApiVersion, Client};
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello"}],
+    "status": "completed"
+}"#;
+
+const FILE_JSON: &str = r#"{
+    "name": "files/abc123",
+    "mimeType": "text/plain",
+    "uri": "http://localhost/files/abc123",
+    "state": "ACTIVE"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_2\"}\n\n",
+);
+
+fn route(request: &ReceivedRequest, base_url: &str) -> MockResponse {
+    let path = request.path.as_str();
+    if path.contains("alt=sse") {
+        MockResponse::sse(SSE_BODY)
+    } else if path.starts_with("/upload/") {
+        MockResponse::json(200, "{}")
+            .with_header("x-goog-upload-url", format!("{base_url}/resumable/1"))
+    } else if path.starts_with("/resumable/") {
+        MockResponse::json(200, format!(r#"{{"file": {FILE_JSON}}}"#))
+    } else if path.contains("/files") {
+        MockResponse::json(200, FILE_JSON)
+    } else {
+        MockResponse::json(200, INTERACTION_JSON)
+    }
+}
+
+fn client(server: &MockServer, version: ApiVersion) -> Client {
+    Client::builder("test-key".to_string())
+        .with_api_version(version)
+        .with_base_url(server.base_url())
+        .build()
+        .expect("client builds")
+}
+
+fn paths(server: &MockServer) -> Vec<String> {
+    server.requests().into_iter().map(|r| r.path).collect()
+}
+
+#[tokio::test]
+async fn test_default_version_is_v1beta() {
+    let server = MockServer::start_with(route).await;
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+
+    assert_eq!(paths(&server), ["/v1beta/interactions/interaction-123"]);
+}
+
+#[tokio::test]
+async fn test_version_applies_to_interactions_and_files() {
+    let server = MockServer::start_with(route).await;
+    let client = client(&server, ApiVersion::V1Alpha);
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+    assert!(events.iter().all(Result::is_ok));
+    client.cancel_interaction("interaction-123").await.unwrap();
+    let file = client
+        .upload_file_bytes(b"hello".to_vec(), "text/plain", None)
+        .await
+        .unwrap();
+    client.get_file(&file.name).await.unwrap();
+    client.list_files(None, None).await.unwrap();
+
+    assert_eq!(
+        paths(&server),
+        [
+            "/v1alpha/interactions",
+            "/v1alpha/interactions?alt=sse",
+            "/v1alpha/interactions/interaction-123/cancel",
+            "/upload/v1alpha/files",
+            "/resumable/1",
+            "/v1alpha/files/abc123",
+            "/v1alpha/files",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_clients_with_different_versions_side_by_side() {
+    let server = MockServer::start_with(route).await;
+    let stable = client(&server, ApiVersion::V1);
+    let custom = client(&server, ApiVersion::custom("v2beta"));
+
+    stable.get_interaction("interaction-123").await.unwrap();
+    custom.get_interaction("interaction-123").await.unwrap();
+    stable.get_interaction("interaction-123").await.unwrap();
+
+    assert_eq!(
+        paths(&server),
+        [
+            "/v1/interactions/interaction-123",
+            "/v2beta/interactions/interaction-123",
+            "/v1/interactions/interaction-123",
+        ]
+    );
+}
@@ -0,0 +1,118 @@
+//! Streaming audio output tests
+//!
+//! Verifies `AudioStreamWriter` against audio deltas from `create_stream()`:
+//! WAV output has a correct header once finished, and PCM bytes reach the sink
+//! as each delta arrives rather than when the interaction completes. These
+//! tests run offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test audio_stream_tests
+//! ```
+
+use base64::Engine;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{AudioStreamWriter, Client, PcmFormat};
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+const MIME_TYPE: &str = "audio/L16;codec=pcm;rate=24000";
+
+fn audio_stream(chunks: &[&[u8]]) -> MockResponse {
+    let mut events = vec![json!({
+        "event_type": "interaction.start",
+        "interaction": {"id": "int-1", "status": "in_progress"},
+    })];
+    events.extend(chunks.iter().map(|chunk| {
+        json!({
+            "event_type": "content.delta",
+            "index": 0,
+            "delta": {
+                "type": "audio",
+                "data": base64::engine::general_purpose::STANDARD.encode(chunk),
+                "mime_type": MIME_TYPE,
+            },
+        })
+    }));
+    events.push(json!({
+        "event_type": "interaction.complete",
+        "interaction": {"id": "int-1", "status": "completed"},
+    }));
+    MockResponse::sse_events(events)
+}
+
+fn tts_stream(
+    client: &Client,
+) -> futures_util::stream::BoxStream<'_, Result<genai_rs::StreamEvent, genai_rs::GenaiError>> {
+    client
+        .interaction()
+        .with_model("gemini-2.5-flash-preview-tts")
+        .with_text("Say hello")
+        .with_audio_output()
+        .create_stream()
+}
+
+#[tokio::test]
+async fn test_stream_to_wav_file() {
+    let server = MockServer::start().await;
+    server.enqueue(audio_stream(&[&[1, 0, 2, 0], &[3, 0]]));
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hello.wav");
+
+    let client = server.client();
+    let mut stream = tts_stream(&client);
+    let mut audio = AudioStreamWriter::wav(tokio::fs::File::create(&path).await.unwrap());
+    while let Some(event) = stream.next().await {
+        audio.write_event(&event.unwrap()).await.unwrap();
+    }
+    assert_eq!(audio.format(), PcmFormat::from_mime_type(MIME_TYPE));
+    assert_eq!(audio.bytes_written(), 6);
+    audio.finish().await.unwrap();
+
+    let wav = std::fs::read(&path).unwrap();
+    assert_eq!(&wav[..4], b"RIFF");
+    assert_eq!(&wav[4..8], &(36u32 + 6).to_le_bytes());
+    assert_eq!(&wav[8..16], b"WAVEfmt ");
+    assert_eq!(&wav[24..28], &24_000u32.to_le_bytes());
+    assert_eq!(&wav[40..44], &6u32.to_le_bytes());
+    assert_eq!(&wav[44..], &[1, 0, 2, 0, 3, 0]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_pcm_bytes_arrive_before_stream_completes() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        audio_stream(&[&[1, 0], &[2, 0], &[3, 0]]).with_event_delay(Duration::from_secs(1)),
+    );
+    let (sink, mut playback) = tokio::io::duplex(64);
+
+    let client = server.client();
+    let writer = async {
+        let mut stream = tts_stream(&client);
+        let mut audio = AudioStreamWriter::pcm(sink);
+        while let Some(event) = stream.next().await {
+            audio.write_event(&event.unwrap()).await.unwrap();
+        }
+        audio.finish().await.unwrap();
+    };
+    let reader = async {
+        let mut first = [0; 2];
+        playback.read_exact(&mut first).await.unwrap();
+        let first_audio_at = tokio::time::Instant::now();
+        let mut rest = Vec::new();
+        playback.read_to_end(&mut rest).await.unwrap();
+        (first, first_audio_at, rest)
+    };
+
+    let started = tokio::time::Instant::now();
+    let ((), (first, first_audio_at, rest)) = tokio::join!(writer, reader);
+
+    assert_eq!(first, [1, 0]);
+    assert_eq!(rest, [2, 0, 3, 0]);
+    // Five events a second apart: the first audio is written after two of them
+    assert!(first_audio_at - started < Duration::from_secs(3));
+    assert!(started.elapsed() >= Duration::from_secs(5));
+}
@@ -0,0 +1,361 @@
+//! Automatic stream resumption tests
+//!
+//! Verifies `InteractionBuilder::with_auto_resume()`: a stream whose connection
+//! drops mid-generation reconnects via the GET stream endpoint from the last
+//! received event, yields every event exactly once, and still produces a
+//! complete final response. Covers `create_stream()` and
+//! `create_stream_with_auto_functions()`, plus resuming by hand with
+//! `StreamAccumulator`. These tests run offline against
+//! `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test auto_resume_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    AutoFunctionStreamChunk, CallableFunction, FunctionDeclaration, GenaiError, InteractionStatus,
+    RetryPolicy, StreamAccumulator, StreamChunk, StreamEvent,
+};
+use genai_rs_macros::tool;
+use serde_json::json;
+
+/// Reports the weather for a city
+#[allow(dead_code)]
+#[tool(city(description = "City name"))]
+fn resume_weather(city: String) -> String {
+    format!("Sunny in {city}")
+}
+
+fn resume_policy() -> RetryPolicy {
+    RetryPolicy::new().with_max_attempts(3)
+}
+
+async fn collect(server: &MockServer) -> Vec<Result<StreamEvent, GenaiError>> {
+    let client = server.client();
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .with_auto_resume(resume_policy())
+        .create_stream()
+        .collect()
+        .await
+}
+
+fn text_of(events: &[StreamEvent]) -> String {
+    events
+        .iter()
+        .filter_map(|e| match &e.chunk {
+            StreamChunk::Delta(delta) => delta.as_text().map(str::to_string),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_resumes_from_last_event_id() {
+    let server = MockServer::start().await;
+    let chunks = ["Hel", "lo", " world"];
+    server
+        .enqueue(MockResponse::text_stream("int-1", &chunks).disconnect_after_events(3))
+        // The server replays the whole stream; already-seen events must be dropped
+        .enqueue(MockResponse::text_stream("int-1", &chunks));
+
+    let events: Vec<_> = collect(&server)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    let ids: Vec<_> = events
+        .iter()
+        .filter_map(|e| e.event_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["evt_1", "evt_2", "evt_3", "evt_4", "evt_5"]);
+    assert_eq!(text_of(&events), "Hello world");
+    let StreamChunk::Complete(response) = &events.last().unwrap().chunk else {
+        panic!("stream should end with Complete");
+    };
+    assert_eq!(response.as_text(), Some("Hello world"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "GET");
+    assert!(requests[1].path.starts_with("/v1beta/interactions/int-1?"));
+    assert!(requests[1].path.contains("last_event_id=evt_3"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_resumes_without_event_ids() {
+    let events = [
+        json!({"event_type": "interaction.start", "interaction": {"id": "int-1", "status": "in_progress"}}),
+        json!({"event_type": "content.delta", "index": 0, "delta": {"type": "text", "text": "Hel"}}),
+        json!({"event_type": "content.delta", "index": 0, "delta": {"type": "text", "text": "lo"}}),
+        json!({"event_type": "interaction.complete", "interaction": {"id": "int-1", "status": "completed"}}),
+    ];
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::sse_events(events.clone()).disconnect_after_events(2))
+        .enqueue(MockResponse::sse_events(events));
+
+    let events: Vec<_> = collect(&server)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(text_of(&events), "Hello");
+    assert_eq!(events.len(), 4);
+    assert!(!server.requests()[1].path.contains("last_event_id"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_resumes_using_sse_id_and_retry_fields() {
+    // Event IDs, types and the reconnection delay come only from SSE fields
+    let body = concat!(
+        "retry: 5000\n",
+        "event: interaction.start\nid: evt_1\n",
+        "data: {\"interaction\": {\"id\": \"int-1\", \"status\": \"in_progress\"}}\n\n",
+        "event: content.delta\nid: evt_2\n",
+        "data: {\"index\": 0,\n",
+        "data:  \"delta\": {\"type\": \"text\", \"text\": \"Hel\"}}\n\n",
+        "event: content.delta\nid: evt_3\n",
+        "data: {\"index\": 0, \"delta\": {\"type\": \"text\", \"text\": \"lo\"}}\n\n",
+        "event: interaction.complete\nid: evt_4\n",
+        "data: {\"interaction\": {\"id\": \"int-1\", \"status\": \"completed\"}}\n\n",
+    );
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::sse(body).disconnect_after_events(2))
+        .enqueue(MockResponse::sse(body));
+
+    let started = tokio::time::Instant::now();
+    let events: Vec<_> = collect(&server)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    let ids: Vec<_> = events
+        .iter()
+        .filter_map(|e| e.event_id.as_deref())
+        .collect();
+    assert_eq!(ids, ["evt_1", "evt_2", "evt_3", "evt_4"]);
+    assert_eq!(text_of(&events), "Hello");
+    assert!(started.elapsed() >= std::time::Duration::from_secs(5));
+    assert!(server.requests()[1].path.contains("last_event_id=evt_2"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_resume_honors_cleared_sse_id() {
+    // An empty `id` field clears the last event ID, so the resume starts over
+    let body = concat!(
+        "event: interaction.start\nid: evt_1\n",
+        "data: {\"interaction\": {\"id\": \"int-1\", \"status\": \"in_progress\"}}\n\n",
+        "event: content.delta\nid\n",
+        "data: {\"index\": 0, \"delta\": {\"type\": \"text\", \"text\": \"Hel\"}}\n\n",
+        "event: content.delta\n",
+        "data: {\"index\": 0, \"delta\": {\"type\": \"text\", \"text\": \"lo\"}}\n\n",
+        "event: interaction.complete\n",
+        "data: {\"interaction\": {\"id\": \"int-1\", \"status\": \"completed\"}}\n\n",
+    );
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::sse(body).disconnect_after_events(2))
+        .enqueue(MockResponse::sse(body));
+
+    let events: Vec<_> = collect(&server)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(text_of(&events), "Hello");
+    assert_eq!(events.len(), 4);
+    assert!(!server.requests()[1].path.contains("last_event_id"));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_resume_gives_up_after_policy_attempts() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::text_stream("int-1", &["Hel", "lo"]).disconnect_after_events(2))
+        .enqueue(MockResponse::error(503, "Unavailable"))
+        .enqueue(MockResponse::error(503, "Unavailable"));
+
+    let results = collect(&server).await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[..2].iter().all(Result::is_ok));
+    assert!(matches!(
+        results[2],
+        Err(GenaiError::Api {
+            status_code: 503,
+            ..
+        })
+    ));
+    server.assert_request_count(3);
+}
+
+#[tokio::test]
+async fn test_disconnect_before_interaction_id_is_not_resumed() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::sse_events([
+            json!({"event_type": "content.delta", "index": 0, "delta": {"type": "text", "text": "Hel"}}),
+            json!({"event_type": "content.delta", "index": 0, "delta": {"type": "text", "text": "lo"}}),
+        ])
+        .disconnect_after_events(1),
+    );
+
+    let results = collect(&server).await;
+
+    assert!(results[0].is_ok());
+    assert!(matches!(results.last(), Some(Err(GenaiError::Http(_)))));
+    server.assert_request_count(1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_auto_function_stream_resumes_without_duplicate_calls() {
+    let server = MockServer::start().await;
+    let call = || {
+        MockResponse::function_call_stream(
+            "int-1",
+            "call-1",
+            "resume_weather",
+            json!({"city": "Paris"}),
+        )
+    };
+    server
+        .enqueue(call().disconnect_after_events(2))
+        .enqueue(call())
+        .enqueue(MockResponse::text_stream("int-2", &["Sunny", " today"]));
+
+    let declaration = FunctionDeclaration::builder("resume_weather")
+        .description("Reports the weather for a city")
+        .parameter("city", json!({"type": "string"}))
+        .required(vec!["city".to_string()])
+        .build();
+    let client = server.client();
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris?")
+        .add_function(declaration)
+        .with_auto_resume(resume_policy())
+        .create_stream_with_auto_functions()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let executions: Vec<_> = events
+        .iter()
+        .filter_map(|e| match &e.chunk {
+            AutoFunctionStreamChunk::FunctionResults(results) => Some(results),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(executions.len(), 1);
+    assert_eq!(executions[0].result, json!({"result": "Sunny in Paris"}));
+    let AutoFunctionStreamChunk::Complete(response) = &events.last().unwrap().chunk else {
+        panic!("stream should end with Complete");
+    };
+    assert_eq!(response.as_text(), Some("Sunny today"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1].path.contains("last_event_id=evt_2"));
+    assert_eq!(
+        requests[2].json().unwrap()["previousInteractionId"],
+        "int-1"
+    );
+}
+
+#[tokio::test]
+async fn test_accumulator_folds_manually_resumed_stream() {
+    let server = MockServer::start().await;
+    let chunks = ["Hel", "lo", " world"];
+    server
+        .enqueue(MockResponse::text_stream("int-1", &chunks).disconnect_after_events(3))
+        .enqueue(MockResponse::sse(
+            [
+                r#"data: {"event_type":"content.delta","index":0,"delta":{"type":"text","text":" world"},"event_id":"evt_4"}"#,
+                r#"data: {"event_type":"interaction.complete","interaction":{"id":"int-1","status":"completed"},"event_id":"evt_5"}"#,
+            ]
+            .map(|line| format!("{line}\n\n"))
+            .concat(),
+        ));
+    let client = server.client();
+
+    let mut accumulator = StreamAccumulator::new();
+    let mut stream = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .create_stream();
+    while let Some(Ok(event)) = stream.next().await {
+        accumulator.push(&event);
+    }
+    drop(stream);
+    let partial = accumulator.snapshot();
+    assert_eq!(partial.as_text(), Some("Hello"));
+    assert_eq!(partial.status, InteractionStatus::InProgress);
+    assert!(!accumulator.is_complete());
+
+    let interaction_id = accumulator.interaction_id().unwrap().to_string();
+    let last_event_id = accumulator.last_event_id().map(str::to_string);
+    let mut resumed = client.get_interaction_stream(&interaction_id, last_event_id.as_deref());
+    while let Some(event) = resumed.next().await {
+        accumulator.push(&event.unwrap());
+    }
+
+    let response = accumulator.snapshot();
+    assert!(accumulator.is_complete());
+    assert_eq!(response.status, InteractionStatus::Completed);
+    assert_eq!(response.as_text(), Some("Hello world"));
+    assert!(server.requests()[1].path.contains("last_event_id=evt_3"));
+}
+
+#[tokio::test]
+async fn test_complete_outputs_match_accumulator_for_unindexed_deltas() {
+    let events = [
+        json!({"event_type": "interaction.start", "interaction": {"id": "int-1", "status": "in_progress"}}),
+        json!({"event_type": "content.delta", "delta": {"type": "thought", "signature": "sig-1"}}),
+        json!({"event_type": "content.delta", "delta": {"type": "text", "text": "Hel"}}),
+        json!({"event_type": "content.delta", "delta": {"type": "text", "text": "lo"}}),
+        json!({"event_type": "interaction.complete", "interaction": {"id": "int-1", "status": "completed"}}),
+    ];
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::sse_events(events.clone()))
+        .enqueue(MockResponse::sse_events(events));
+    let client = server.client();
+
+    let created: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .create_stream()
+        .collect()
+        .await;
+    let fetched: Vec<_> = client.get_interaction_stream("int-1", None).collect().await;
+
+    for events in [created, fetched] {
+        let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+        let mut accumulator = StreamAccumulator::new();
+        for event in &events {
+            accumulator.push(event);
+        }
+        let StreamChunk::Complete(response) = &events.last().unwrap().chunk else {
+            panic!("stream should end with Complete");
+        };
+        assert_eq!(response.outputs.len(), 2);
+        assert_eq!(response.outputs, accumulator.snapshot().outputs);
+        assert_eq!(response.as_text(), Some("Hello"));
+    }
+}
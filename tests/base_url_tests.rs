@@ -0,0 +1,256 @@
+//! Custom base URL tests
+//!
+//! Verifies that `ClientBuilder::with_base_url()` is honoured by every
+//! Interactions API and Files API endpoint. These tests run offline against
+//! a local stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test base_url_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer, ReceivedRequest};
+use genai_rs::{Client, InteractionStatus, StreamChunk};
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello from the stand-in"}],
+    "status": "completed"
+}"#;
+
+const FILE_JSON: &str = r#"{
+    "name": "files/abc123",
+    "mimeType": "text/plain",
+    "uri": "http://localhost/v1beta/files/abc123",
+    "state": "ACTIVE"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"interaction.start\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"in_progress\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_2\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_3\"}\n\n",
+);
+
+/// Routes requests the way the real API would, for the endpoints under test.
+fn route(request: &ReceivedRequest, base_url: &str) -> MockResponse {
+    let path = request.path.as_str();
+    match request.method.as_str() {
+        _ if path.contains("alt=sse") => MockResponse::sse(SSE_BODY),
+        "POST" if path.starts_with("/gemini/upload/v1beta/files") => MockResponse::json(200, "{}")
+            .with_header(
+                "x-goog-upload-url",
+                format!("{base_url}/resumable/session-1"),
+            ),
+        "POST" if path.starts_with("/resumable/") => {
+            MockResponse::json(200, format!(r#"{{"file": {FILE_JSON}}}"#))
+        }
+        "GET" if path.starts_with("/gemini/v1beta/files?") || path == "/gemini/v1beta/files" => {
+            MockResponse::json(200, format!(r#"{{"files": [{FILE_JSON}]}}"#))
+        }
+        "GET" if path.starts_with("/gemini/v1beta/files/") => MockResponse::json(200, FILE_JSON),
+        "DELETE" => MockResponse::json(200, "{}"),
+        _ if path.starts_with("/gemini/v1beta/interactions") => {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+        _ => MockResponse::json(404, r#"{"error": "not found"}"#),
+    }
+}
+
+async fn start_server() -> (MockServer, Client) {
+    let server = MockServer::start_with(route).await;
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(format!("{}/gemini/", server.base_url()))
+        .build()
+        .expect("client builds");
+    (server, client)
+}
+
+fn assert_single_request(server: &MockServer, method: &str, path: &str) {
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1, "expected one request, got {requests:?}");
+    assert_eq!(requests[0].method, method);
+    assert_eq!(requests[0].path, path);
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-key"));
+}
+
+#[tokio::test]
+async fn test_create_interaction_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let response = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .expect("create succeeds");
+
+    assert_eq!(response.as_text(), Some("Hello from the stand-in"));
+    assert_single_request(&server, "POST", "/gemini/v1beta/interactions");
+}
+
+#[tokio::test]
+async fn test_create_stream_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+
+    let events: Vec<_> = events.into_iter().map(|e| e.expect("event ok")).collect();
+    assert!(matches!(
+        events.last().unwrap().chunk,
+        StreamChunk::Complete(_)
+    ));
+    assert_single_request(&server, "POST", "/gemini/v1beta/interactions?alt=sse");
+}
+
+#[tokio::test]
+async fn test_get_interaction_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let response = client
+        .get_interaction("interaction-123")
+        .await
+        .expect("get succeeds");
+
+    assert_eq!(response.status, InteractionStatus::Completed);
+    assert_single_request(
+        &server,
+        "GET",
+        "/gemini/v1beta/interactions/interaction-123",
+    );
+}
+
+#[tokio::test]
+async fn test_get_interaction_stream_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let events: Vec<_> = client
+        .get_interaction_stream("interaction-123", Some("evt_1"))
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    assert_single_request(
+        &server,
+        "GET",
+        "/gemini/v1beta/interactions/interaction-123?alt=sse&last_event_id=evt_1",
+    );
+}
+
+#[tokio::test]
+async fn test_delete_interaction_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    client
+        .delete_interaction("interaction-123")
+        .await
+        .expect("delete succeeds");
+
+    assert_single_request(
+        &server,
+        "DELETE",
+        "/gemini/v1beta/interactions/interaction-123",
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_interaction_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    client
+        .cancel_interaction("interaction-123")
+        .await
+        .expect("cancel succeeds");
+
+    assert_single_request(
+        &server,
+        "POST",
+        "/gemini/v1beta/interactions/interaction-123/cancel",
+    );
+}
+
+#[tokio::test]
+async fn test_upload_file_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let file = client
+        .upload_file_bytes(b"hello".to_vec(), "text/plain", Some("hello.txt"))
+        .await
+        .expect("upload succeeds");
+
+    assert_eq!(file.name, "files/abc123");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path, "/gemini/upload/v1beta/files");
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-key"));
+    assert_eq!(requests[1].path, "/resumable/session-1");
+    assert_eq!(requests[1].body, b"hello");
+}
+
+#[tokio::test]
+async fn test_upload_file_chunked_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    std::fs::write(&path, b"chunked contents").unwrap();
+
+    let (file, _upload) = client
+        .upload_file_chunked(&path)
+        .await
+        .expect("chunked upload succeeds");
+
+    assert_eq!(file.name, "files/abc123");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path, "/gemini/upload/v1beta/files");
+    assert_eq!(requests[1].body, b"chunked contents");
+}
+
+#[tokio::test]
+async fn test_get_file_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let file = client.get_file("files/abc123").await.expect("get succeeds");
+
+    assert!(file.is_active());
+    assert_single_request(&server, "GET", "/gemini/v1beta/files/abc123");
+}
+
+#[tokio::test]
+async fn test_list_files_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    let response = client
+        .list_files(Some(5), Some("page-2"))
+        .await
+        .expect("list succeeds");
+
+    assert_eq!(response.files.len(), 1);
+    assert_single_request(
+        &server,
+        "GET",
+        "/gemini/v1beta/files?pageSize=5&pageToken=page-2",
+    );
+}
+
+#[tokio::test]
+async fn test_delete_file_uses_base_url() {
+    let (server, client) = start_server().await;
+
+    client
+        .delete_file("files/abc123")
+        .await
+        .expect("delete succeeds");
+
+    assert_single_request(&server, "DELETE", "/gemini/v1beta/files/abc123");
+}
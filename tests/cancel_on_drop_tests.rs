@@ -0,0 +1,176 @@
+//! Cancel-on-drop tests
+//!
+//! Verifies `InteractionBuilder::with_cancel_on_drop()`, `CancelOnDrop` and
+//! `CancelGuard`: dropping a stream or guard before the interaction finishes
+//! sends a cancel request for the interaction ID, while finished or disarmed
+//! interactions are left alone. These tests run offline against
+//! `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test cancel_on_drop_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{CancelGuard, CancelOnDrop, InteractionStatus};
+use serde_json::json;
+use std::time::Duration;
+
+fn cancelled() -> MockResponse {
+    MockResponse::json(
+        200,
+        json!({"id": "int-1", "status": "cancelled"}).to_string(),
+    )
+}
+
+/// Waits for the spawned cancel request to reach the server.
+async fn wait_for_requests(server: &MockServer, count: usize) {
+    for _ in 0..100 {
+        if server.requests().len() >= count {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_dropping_stream_early_cancels_interaction() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::text_stream("int-1", &["Hel", "lo"]).stall_after_events(2))
+        .enqueue(cancelled());
+    let client = server.client();
+
+    let mut stream = client
+        .interaction()
+        .with_agent("deep-research-pro-preview-12-2025")
+        .with_text("Research")
+        .with_background(true)
+        .with_cancel_on_drop()
+        .create_stream();
+    stream.next().await.unwrap().unwrap();
+    stream.next().await.unwrap().unwrap();
+    drop(stream);
+
+    wait_for_requests(&server, 2).await;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].path, "/v1beta/interactions/int-1/cancel");
+}
+
+#[tokio::test]
+async fn test_completed_stream_is_not_cancelled() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text_stream("int-1", &["Hi"]));
+    let client = server.client();
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .with_cancel_on_drop()
+        .create_stream()
+        .collect()
+        .await;
+
+    assert!(events.last().unwrap().as_ref().unwrap().is_complete());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server.assert_request_count(1);
+}
+
+#[tokio::test]
+async fn test_resumed_stream_cancels_known_interaction() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(
+            MockResponse::sse_events([json!({
+                "event_type": "content.delta",
+                "index": 0,
+                "delta": {"type": "text", "text": "Hel"},
+                "event_id": "evt_4",
+            })])
+            .stall_after_events(1),
+        )
+        .enqueue(cancelled());
+    let client = server.client();
+
+    let mut stream = CancelOnDrop::new(
+        &client,
+        client.get_interaction_stream("int-1", Some("evt_3")),
+    )
+    .with_interaction_id("int-1");
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.interaction_id(), Some("int-1"));
+    drop(stream);
+
+    wait_for_requests(&server, 2).await;
+    assert_eq!(
+        server.requests()[1].path,
+        "/v1beta/interactions/int-1/cancel"
+    );
+}
+
+#[tokio::test]
+async fn test_disarmed_stream_keeps_interaction_running() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text_stream("int-1", &["Hel", "lo"]).stall_after_events(2));
+    let client = server.client();
+
+    let mut stream = CancelOnDrop::new(
+        &client,
+        client
+            .interaction()
+            .with_agent("deep-research-pro-preview-12-2025")
+            .with_text("Research")
+            .with_background(true)
+            .create_stream(),
+    );
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.interaction_id(), Some("int-1"));
+    stream.disarm();
+    stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.interaction_id(), None);
+    drop(stream);
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server.assert_request_count(1);
+}
+
+#[tokio::test]
+async fn test_guard_cancels_on_drop_unless_disarmed() {
+    let server = MockServer::start().await;
+    server.enqueue(cancelled());
+    let client = server.client();
+
+    let mut kept = CancelGuard::new(&client, "int-2");
+    kept.disarm();
+    drop(kept);
+    drop(CancelGuard::new(&client, "int-1"));
+
+    wait_for_requests(&server, 1).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/v1beta/interactions/int-1/cancel");
+}
+
+#[tokio::test]
+async fn test_guard_cancel_waits_for_result() {
+    let server = MockServer::start().await;
+    server.enqueue(cancelled());
+    let client = server.client();
+
+    let response = CancelGuard::new(&client, "int-1")
+        .cancel()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.status, InteractionStatus::Cancelled);
+
+    let unarmed = CancelGuard::unarmed(&client).cancel().await.unwrap();
+    assert!(unarmed.is_none());
+    server.assert_request_count(1);
+}
@@ -0,0 +1,371 @@
+//! Cassette record/replay tests
+//!
+//! Records traffic against a local stand-in server with `Cassette::record`,
+//! then replays it with `Cassette::replay` against an unreachable base URL to
+//! show that unary calls, SSE streams, stream resumption, auto-function loops
+//! and the Files API replay without the network.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test cassette_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer, ReceivedRequest};
+use genai_rs::{
+    CallableFunction, Cassette, CassetteMode, Client, FunctionDeclaration, GenaiError,
+    InteractionRequest, Middleware, StreamChunk,
+};
+use genai_rs_macros::tool;
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Nothing listens here, so any request that escapes the cassette fails.
+const OFFLINE_BASE_URL: &str = "http://127.0.0.1:9";
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello from the recording"}],
+    "status": "completed"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"interaction.start\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"in_progress\"},\"event_id\":\"evt_0\"}\n\n",
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\" again\"},\"event_id\":\"evt_2\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_3\"}\n\n",
+);
+
+const FILE_JSON: &str = r#"{
+    "name": "files/abc123",
+    "mimeType": "text/plain",
+    "uri": "http://localhost/files/abc123",
+    "state": "ACTIVE"
+}"#;
+
+/// Looks up a canned fact
+#[allow(dead_code)]
+#[tool(topic(description = "What to look up"))]
+fn cassette_lookup(topic: String) -> String {
+    format!("{topic} is 42")
+}
+
+fn client(base_url: &str, cassette: &Cassette) -> Client {
+    Client::builder("secret-key".to_string())
+        .with_base_url(base_url)
+        .with_cassette(cassette.clone())
+        .build()
+        .expect("client builds")
+}
+
+fn cassette_path(dir: &tempfile::TempDir) -> std::path::PathBuf {
+    dir.path().join("cassettes").join("test.json")
+}
+
+fn replay(path: &Path) -> Cassette {
+    let cassette = Cassette::replay(path).expect("cassette loads");
+    assert_eq!(cassette.mode(), CassetteMode::Replay);
+    cassette
+}
+
+async fn collect_text(client: &Client, prompt: &str) -> Result<String, GenaiError> {
+    let mut stream = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text(prompt)
+        .create_stream();
+    let mut text = String::new();
+    while let Some(event) = stream.next().await {
+        if let StreamChunk::Delta(delta) = event?.chunk
+            && let Some(t) = delta.as_text()
+        {
+            text.push_str(t);
+        }
+    }
+    Ok(text)
+}
+
+#[tokio::test]
+async fn test_unary_and_stream_replay_offline() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|request: &ReceivedRequest, _: &str| {
+        if request.path.contains("alt=sse") {
+            MockResponse::sse(SSE_BODY)
+        } else {
+            MockResponse::json(200, INTERACTION_JSON).with_header("x-goog-request-id", "req-1")
+        }
+    })
+    .await;
+
+    let recorder = Cassette::record(&path);
+    let live = client(&server.base_url(), &recorder);
+    let recorded = live
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+    let recorded_stream = collect_text(&live, "Stream it").await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+    recorder.save().unwrap();
+
+    let recording = std::fs::read_to_string(&path).unwrap();
+    assert!(recording.contains("x-goog-request-id"));
+    assert!(recording.contains("event_type"));
+    assert!(
+        !recording.contains("secret-key"),
+        "credentials must not be recorded"
+    );
+
+    let cassette = replay(&path);
+    let offline = client(OFFLINE_BASE_URL, &cassette);
+    let replayed = offline
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+    assert_eq!(replayed.as_text(), recorded.as_text());
+    assert_eq!(replayed.id, recorded.id);
+    assert_eq!(
+        collect_text(&offline, "Stream it").await.unwrap(),
+        recorded_stream
+    );
+    assert_eq!(recorded_stream, "Hello again");
+    assert_eq!(cassette.remaining(), 0);
+    assert_eq!(
+        server.requests().len(),
+        2,
+        "replay must not hit the network"
+    );
+}
+
+#[tokio::test]
+async fn test_unmatched_request_is_cassette_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+
+    let live = client(&server.base_url(), &Cassette::record(&path));
+    live.get_interaction("interaction-123").await.unwrap();
+    drop(live);
+
+    let cassette = replay(&path);
+    let offline = client(OFFLINE_BASE_URL, &cassette);
+    let result = offline
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Not recorded")
+        .create()
+        .await;
+    assert!(matches!(result, Err(GenaiError::Cassette(_))));
+
+    // Each recorded exchange is served once
+    offline.get_interaction("interaction-123").await.unwrap();
+    let again = offline.get_interaction("interaction-123").await;
+    assert!(matches!(again, Err(GenaiError::Cassette(_))));
+}
+
+#[tokio::test]
+async fn test_auto_function_loop_replays() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(
+                200,
+                r#"{
+                    "id": "interaction-1",
+                    "status": "requires_action",
+                    "outputs": [{"type": "function_call", "id": "call-1", "name": "cassette_lookup", "arguments": {"topic": "the answer"}}]
+                }"#,
+            )
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await;
+
+    let lookup = FunctionDeclaration::builder("cassette_lookup")
+        .description("Looks up a canned fact")
+        .parameter("topic", json!({"type": "string"}))
+        .required(vec!["topic".to_string()])
+        .build();
+    let run = |client: Client, lookup: FunctionDeclaration| async move {
+        client
+            .interaction()
+            .with_model("gemini-3-flash-preview")
+            .with_text("What is the answer?")
+            .add_function(lookup)
+            .create_with_auto_functions()
+            .await
+    };
+
+    let recorded = run(
+        client(&server.base_url(), &Cassette::record(&path)),
+        lookup.clone(),
+    )
+    .await
+    .unwrap();
+
+    let cassette = replay(&path);
+    let replayed = run(client(OFFLINE_BASE_URL, &cassette), lookup)
+        .await
+        .unwrap();
+
+    assert_eq!(replayed.executions.len(), 1);
+    assert_eq!(replayed.executions[0].result, recorded.executions[0].result);
+    assert_eq!(replayed.response.as_text(), recorded.response.as_text());
+    assert_eq!(cassette.remaining(), 0);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_stream_resume_replays() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|_, _| MockResponse::sse(SSE_BODY)).await;
+
+    let resume = |client: Client| async move {
+        let events: Vec<_> = client
+            .get_interaction_stream("interaction-123", Some("evt_1"))
+            .collect()
+            .await;
+        events
+            .into_iter()
+            .map(|e| e.map(|e| e.event_id))
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let recorded = resume(client(&server.base_url(), &Cassette::record(&path)))
+        .await
+        .unwrap();
+    assert!(server.requests()[0].path.contains("last_event_id=evt_1"));
+
+    let cassette = replay(&path);
+    let replayed = resume(client(OFFLINE_BASE_URL, &cassette)).await.unwrap();
+    assert_eq!(replayed, recorded);
+    assert_eq!(cassette.remaining(), 0);
+}
+
+#[tokio::test]
+async fn test_files_api_replays() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|request: &ReceivedRequest, base_url: &str| {
+        let path = request.path.as_str();
+        if path.starts_with("/upload/") {
+            MockResponse::json(200, "{}")
+                .with_header("x-goog-upload-url", format!("{base_url}/resumable/1"))
+        } else if path.starts_with("/resumable/") {
+            MockResponse::json(200, format!(r#"{{"file": {FILE_JSON}}}"#))
+        } else if request.method == "DELETE" {
+            MockResponse::json(200, "{}")
+        } else {
+            MockResponse::json(200, FILE_JSON)
+        }
+    })
+    .await;
+
+    let run = |client: Client| async move {
+        let file = client
+            .upload_file_bytes(b"hello".to_vec(), "text/plain", Some("greeting"))
+            .await?;
+        let fetched = client.get_file(&file.name).await?;
+        client.delete_file(&file.name).await?;
+        Ok::<_, GenaiError>(fetched)
+    };
+
+    let recorded = run(client(&server.base_url(), &Cassette::record(&path)))
+        .await
+        .unwrap();
+
+    let cassette = replay(&path);
+    let replayed = run(client(OFFLINE_BASE_URL, &cassette)).await.unwrap();
+    assert_eq!(replayed.name, recorded.name);
+    assert_eq!(replayed.uri, recorded.uri);
+    assert_eq!(cassette.remaining(), 0);
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_replay_or_record_switches_on_file_presence() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+
+    let first = Cassette::replay_or_record(&path).unwrap();
+    assert_eq!(first.mode(), CassetteMode::Record);
+    client(&server.base_url(), &first)
+        .get_interaction("interaction-123")
+        .await
+        .unwrap();
+    drop(first);
+
+    let second = Cassette::replay_or_record(&path).unwrap();
+    assert_eq!(second.mode(), CassetteMode::Replay);
+    assert_eq!(second.remaining(), 1);
+}
+
+/// Adds a per-tenant secret to every interaction request.
+#[derive(Debug)]
+struct TenantToken;
+
+#[async_trait]
+impl Middleware for TenantToken {
+    async fn on_request(
+        &self,
+        _request: &mut InteractionRequest,
+        headers: &mut HeaderMap,
+    ) -> Result<(), GenaiError> {
+        headers.insert("x-tenant-token", HeaderValue::from_static("tenant-secret"));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_configured_headers_are_not_recorded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = cassette_path(&dir);
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+
+    let recorder = Cassette::record(&path).with_redacted_header("X-Tenant-Token");
+    let live = Client::builder("secret-key".to_string())
+        .with_base_url(server.base_url())
+        .with_default_header("x-goog-user-project", "billing-project")
+        .with_default_header("x-gateway-key", "gateway-secret")
+        .with_middleware(TenantToken)
+        .with_cassette(recorder.clone())
+        .build()
+        .unwrap();
+    live.interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+    let sent = &server.requests()[0];
+    assert_eq!(sent.header("x-gateway-key"), Some("gateway-secret"));
+    assert_eq!(sent.header("x-tenant-token"), Some("tenant-secret"));
+
+    // Nothing is written until the recording is saved
+    assert!(!path.exists());
+    recorder.save().unwrap();
+
+    let recording = std::fs::read_to_string(&path).unwrap();
+    assert!(recording.contains("interaction-123"));
+    assert!(!recording.contains("gateway-secret"));
+    assert!(!recording.contains("billing-project"));
+    assert!(!recording.contains("tenant-secret"));
+    assert!(!recording.contains("secret-key"));
+}
@@ -0,0 +1,149 @@
+//! Credential provider tests
+//!
+//! Verifies that a `CredentialProvider` is consulted for every request
+//! (including retries and Files API calls), that its credential kind decides
+//! the auth header, and that provider failures abort the request before
+//! anything is sent. These tests run offline against a local stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test credential_provider_tests
+//! ```
+
+use async_trait::async_trait;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{Client, Credential, CredentialProvider, GenaiError, RetryPolicy};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello"}],
+    "status": "completed"
+}"#;
+
+/// Issues a new bearer token on every call, like a provider refreshing a
+/// short-lived token.
+#[derive(Debug, Default)]
+struct RotatingTokens {
+    issued: AtomicUsize,
+}
+
+#[async_trait]
+impl CredentialProvider for RotatingTokens {
+    async fn credential(&self) -> Result<Credential, GenaiError> {
+        let n = self.issued.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(Credential::BearerToken(format!("token-{n}")))
+    }
+}
+
+#[derive(Debug)]
+struct Unavailable;
+
+#[async_trait]
+impl CredentialProvider for Unavailable {
+    async fn credential(&self) -> Result<Credential, GenaiError> {
+        Err(GenaiError::Credential(
+            "secret store unreachable".to_string(),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_provider_is_consulted_per_request() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let provider = Arc::new(RotatingTokens::default());
+    let client = Client::builder_with_credentials(provider.clone())
+        .with_base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+
+    let auth: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| r.header("authorization").map(str::to_string))
+        .collect();
+    assert_eq!(
+        auth,
+        [
+            Some("Bearer token-1".to_string()),
+            Some("Bearer token-2".to_string())
+        ]
+    );
+    assert!(
+        server
+            .requests()
+            .iter()
+            .all(|r| r.header("x-goog-api-key").is_none())
+    );
+}
+
+#[tokio::test]
+async fn test_retries_fetch_a_fresh_credential() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(503, r#"{"error": {"message": "unavailable"}}"#)
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await;
+    let client = Client::builder_with_credentials(RotatingTokens::default())
+        .with_base_url(server.base_url())
+        .with_retry_policy(
+            RetryPolicy::new()
+                .with_max_attempts(2)
+                .with_initial_backoff(Duration::from_millis(1)),
+        )
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("authorization"), Some("Bearer token-1"));
+    assert_eq!(requests[1].header("authorization"), Some("Bearer token-2"));
+}
+
+#[tokio::test]
+async fn test_provider_error_aborts_request() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let client = Client::builder_with_credentials(Unavailable)
+        .with_base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let error = client.get_file("files/abc123").await.unwrap_err();
+
+    assert!(matches!(error, GenaiError::Credential(ref msg) if msg.contains("unreachable")));
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_static_key_convenience_is_unchanged() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-key"));
+    assert_eq!(requests[0].header("authorization"), None);
+}
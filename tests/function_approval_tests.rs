@@ -0,0 +1,273 @@
+//! Function call approval tests
+//!
+//! Verifies `InteractionBuilder::with_approval_policy()`: approved calls run,
+//! denied calls are skipped and reported to the model as errors, modified
+//! arguments reach the function, and the streaming loop yields an
+//! `AwaitingApproval` event before each review so a consumer can answer a
+//! prompt. These tests run offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test function_approval_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    ApprovalDecision, ApprovalPolicy, AutoFunctionStreamChunk, CallableFunction,
+    FunctionDeclaration, FunctionError, PendingFunctionCall, ToolService,
+};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Records the arguments of every call it receives.
+#[derive(Default)]
+struct Recorder {
+    calls: Mutex<Vec<Value>>,
+}
+
+#[async_trait]
+impl CallableFunction for Recorder {
+    fn declaration(&self) -> FunctionDeclaration {
+        FunctionDeclaration::builder("delete_record")
+            .description("Deletes a record")
+            .parameter("id", json!({"type": "integer"}))
+            .build()
+    }
+
+    async fn call(&self, args: Value) -> Result<Value, FunctionError> {
+        self.calls.lock().unwrap().push(args.clone());
+        Ok(json!({"deleted": args["id"]}))
+    }
+}
+
+struct RecorderService(Arc<Recorder>);
+
+impl ToolService for RecorderService {
+    fn tools(&self) -> Vec<Arc<dyn CallableFunction>> {
+        vec![self.0.clone()]
+    }
+}
+
+/// Approves even IDs, denies odd ones, and rewrites ID 0 to ID 100.
+#[derive(Debug)]
+struct EvenOnly;
+
+#[async_trait]
+impl ApprovalPolicy for EvenOnly {
+    async fn review(&self, call: &PendingFunctionCall) -> ApprovalDecision {
+        match call.args["id"].as_u64().unwrap() {
+            0 => ApprovalDecision::ModifyArgs(json!({"id": 100})),
+            id if id % 2 == 0 => ApprovalDecision::Approve,
+            _ => ApprovalDecision::deny("odd IDs are protected"),
+        }
+    }
+}
+
+/// Waits for each decision on a channel, as a UI prompt would.
+#[derive(Debug)]
+struct Prompt(tokio::sync::Mutex<mpsc::UnboundedReceiver<ApprovalDecision>>);
+
+#[async_trait]
+impl ApprovalPolicy for Prompt {
+    async fn review(&self, _call: &PendingFunctionCall) -> ApprovalDecision {
+        self.0.lock().await.recv().await.unwrap()
+    }
+}
+
+fn deletes() -> [(&'static str, &'static str, Value); 3] {
+    [
+        ("call-1", "delete_record", json!({"id": 1})),
+        ("call-2", "delete_record", json!({"id": 2})),
+        ("call-3", "delete_record", json!({"id": 0})),
+    ]
+}
+
+#[tokio::test]
+async fn test_policy_decisions_are_applied_in_order() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls("int-1", deletes()))
+        .enqueue(MockResponse::text("int-2", "Deleted two records."));
+    let recorder = Arc::new(Recorder::default());
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Delete records 1, 2 and 0")
+        .with_tool_service(Arc::new(RecorderService(recorder.clone())))
+        .with_approval_policy(EvenOnly)
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    // The denied call never ran; the modified one ran with the new arguments
+    assert_eq!(
+        *recorder.calls.lock().unwrap(),
+        [json!({"id": 2}), json!({"id": 100})]
+    );
+
+    let [denied, approved, modified] = &result.executions[..] else {
+        panic!("expected three executions: {:?}", result.executions);
+    };
+    assert!(denied.denied);
+    assert_eq!(
+        denied.error_message(),
+        Some("Function 'delete_record' was denied: odd IDs are protected")
+    );
+    assert!(!approved.denied);
+    assert_eq!(approved.result, json!({"deleted": 2}));
+    assert_eq!(modified.args, json!({"id": 100}));
+    assert_eq!(modified.call_id, "call-3");
+
+    let input = server.requests()[1].json().unwrap()["input"].to_string();
+    assert!(
+        input.contains("odd IDs are protected"),
+        "missing denial: {input}"
+    );
+}
+
+#[tokio::test]
+async fn test_streaming_waits_for_each_approval() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls_stream(
+            "int-1",
+            [
+                ("call-1", "delete_record", json!({"id": 7})),
+                ("call-2", "delete_record", json!({"id": 8})),
+            ],
+        ))
+        .enqueue(MockResponse::text_stream("int-2", &["Done"]));
+    let recorder = Arc::new(Recorder::default());
+    let (decisions, receiver) = mpsc::unbounded_channel();
+    let client = server.client();
+
+    let mut stream = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Delete records 7 and 8")
+        .with_tool_service(Arc::new(RecorderService(recorder.clone())))
+        .with_approval_policy(Prompt(tokio::sync::Mutex::new(receiver)))
+        .create_stream_with_auto_functions();
+
+    let mut prompted = Vec::new();
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        let event = event.unwrap();
+        if let AutoFunctionStreamChunk::AwaitingApproval(call) = &event.chunk {
+            // Nothing runs until the user answers
+            assert!(recorder.calls.lock().unwrap().is_empty());
+            prompted.push(call.call_id.clone());
+            let decision = if call.args["id"] == 7 {
+                ApprovalDecision::deny("user declined")
+            } else {
+                ApprovalDecision::Approve
+            };
+            decisions.send(decision).unwrap();
+        }
+        events.push(event.chunk);
+    }
+
+    assert_eq!(prompted, ["call-1", "call-2"]);
+    assert_eq!(*recorder.calls.lock().unwrap(), [json!({"id": 8})]);
+
+    // Only the approved call is announced; both results are reported
+    let pending = events
+        .iter()
+        .find_map(|chunk| match chunk {
+            AutoFunctionStreamChunk::ExecutingFunctions { pending_calls, .. } => {
+                Some(pending_calls)
+            }
+            _ => None,
+        })
+        .expect("stream should yield ExecutingFunctions");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].call_id, "call-2");
+    let results = events
+        .iter()
+        .find_map(|chunk| match chunk {
+            AutoFunctionStreamChunk::FunctionResults(results) => Some(results),
+            _ => None,
+        })
+        .expect("stream should yield FunctionResults");
+    assert!(results[0].denied);
+    assert!(!results[1].denied);
+    assert!(matches!(
+        events.last().unwrap(),
+        AutoFunctionStreamChunk::Complete(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_streaming_skips_executing_event_when_all_denied() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call_stream(
+            "int-1",
+            "call-1",
+            "delete_record",
+            json!({"id": 3}),
+        ))
+        .enqueue(MockResponse::text_stream("int-2", &["Understood"]));
+    let recorder = Arc::new(Recorder::default());
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Delete record 3")
+        .with_tool_service(Arc::new(RecorderService(recorder.clone())))
+        .with_approval_policy(EvenOnly)
+        .create_stream_with_auto_functions()
+        .map(|event| event.unwrap().chunk)
+        .collect()
+        .await;
+
+    assert!(recorder.calls.lock().unwrap().is_empty());
+    assert!(
+        !events
+            .iter()
+            .any(|chunk| matches!(chunk, AutoFunctionStreamChunk::ExecutingFunctions { .. }))
+    );
+    assert!(events.iter().any(|chunk| matches!(
+        chunk,
+        AutoFunctionStreamChunk::FunctionResults(results) if results[0].denied
+    )));
+}
+
+#[tokio::test]
+async fn test_no_awaiting_approval_without_policy() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call_stream(
+            "int-1",
+            "call-1",
+            "delete_record",
+            json!({"id": 3}),
+        ))
+        .enqueue(MockResponse::text_stream("int-2", &["Done"]));
+    let recorder = Arc::new(Recorder::default());
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Delete record 3")
+        .with_tool_service(Arc::new(RecorderService(recorder.clone())))
+        .create_stream_with_auto_functions()
+        .map(|event| event.unwrap().chunk)
+        .collect()
+        .await;
+
+    assert_eq!(*recorder.calls.lock().unwrap(), [json!({"id": 3})]);
+    assert!(
+        !events
+            .iter()
+            .any(|chunk| matches!(chunk, AutoFunctionStreamChunk::AwaitingApproval(_)))
+    );
+}
@@ -2604,7 +2604,7 @@ mod multiturn {
         //
         // - with_store_disabled() + with_previous_interaction() = error (chaining needs storage)
         // - with_store_disabled() + with_background(true) = error (background needs storage)
-        // - with_store_disabled() + create_with_auto_functions() = error (auto-functions need storage)
+        // - with_store_disabled() + create_with_auto_functions() = stateless loop (full history resent)
         //
         // See src/request_builder/tests.rs for validation tests
         println!("Storage constraints are enforced at runtime in build().");
@@ -0,0 +1,228 @@
+//! Function execution timeout and cancellation tests
+//!
+//! Verifies `InteractionBuilder::with_function_timeout()` and
+//! `CallableFunction::timeout()`: a call that runs too long is dropped, its
+//! `CancellationToken` is cancelled, the model receives a `timed_out` error,
+//! and the `FunctionExecutionResult` is marked as timed out. Dropping the
+//! auto-function loop cancels the tokens of calls still in flight. These tests
+//! run offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test function_timeout_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    AutoFunctionStreamChunk, CallableFunction, CancellationToken, FunctionDeclaration,
+    FunctionError, ToolService,
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Hangs far longer than any test, recording whether its token was cancelled.
+#[derive(Default)]
+struct Hang {
+    timeout: Option<Duration>,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl CallableFunction for Hang {
+    fn declaration(&self) -> FunctionDeclaration {
+        FunctionDeclaration::builder("hang")
+            .description("Never returns in time")
+            .build()
+    }
+
+    async fn call(&self, _args: Value) -> Result<Value, FunctionError> {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        Ok(json!({"done": true}))
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    async fn call_with_cancellation(
+        &self,
+        args: Value,
+        cancel: CancellationToken,
+    ) -> Result<Value, FunctionError> {
+        // Background work that outlives the call's future
+        let cancelled = self.cancelled.clone();
+        tokio::spawn(async move {
+            cancel.cancelled().await;
+            cancelled.store(true, Ordering::SeqCst);
+        });
+        self.call(args).await
+    }
+}
+
+struct HangService(Arc<Hang>);
+
+impl ToolService for HangService {
+    fn tools(&self) -> Vec<Arc<dyn CallableFunction>> {
+        vec![self.0.clone()]
+    }
+}
+
+fn hang_service(hang: &Arc<Hang>) -> Arc<dyn ToolService> {
+    Arc::new(HangService(hang.clone()))
+}
+
+/// Waits for a spawned task to observe cancellation.
+async fn wait_for(flag: &AtomicBool) -> bool {
+    for _ in 0..100 {
+        if flag.load(Ordering::SeqCst) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_timed_out_call_reports_error_to_model() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call(
+            "int-1",
+            "call-1",
+            "hang",
+            json!({}),
+        ))
+        .enqueue(MockResponse::text("int-2", "The lookup timed out."));
+    let hang = Arc::new(Hang::default());
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hang")
+        .with_tool_service(hang_service(&hang))
+        .with_function_timeout(Duration::from_millis(100))
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    let execution = &result.executions[0];
+    assert!(execution.timed_out);
+    assert!(execution.is_error());
+    assert_eq!(
+        execution.error_message(),
+        Some("Function 'hang' timed out after 100ms")
+    );
+    assert!(execution.duration < Duration::from_secs(5));
+    assert!(wait_for(&hang.cancelled).await, "token was not cancelled");
+
+    let input = server.requests()[1].json().unwrap()["input"].to_string();
+    assert!(
+        input.contains("timed_out"),
+        "missing timeout error: {input}"
+    );
+}
+
+#[tokio::test]
+async fn test_function_timeout_overrides_builder_timeout() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call(
+            "int-1",
+            "call-1",
+            "hang",
+            json!({}),
+        ))
+        .enqueue(MockResponse::text("int-2", "Done"));
+    let hang = Arc::new(Hang {
+        timeout: Some(Duration::from_millis(50)),
+        ..Hang::default()
+    });
+
+    let start = Instant::now();
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hang")
+        .with_tool_service(hang_service(&hang))
+        .with_function_timeout(Duration::from_secs(30))
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(result.executions[0].timed_out);
+}
+
+#[tokio::test]
+async fn test_streaming_marks_timed_out_results() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call_stream(
+            "int-1",
+            "call-1",
+            "hang",
+            json!({}),
+        ))
+        .enqueue(MockResponse::text_stream("int-2", &["Done"]));
+    let hang = Arc::new(Hang::default());
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hang")
+        .with_tool_service(hang_service(&hang))
+        .with_function_timeout(Duration::from_millis(100))
+        .create_stream_with_auto_functions()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let results = events
+        .iter()
+        .find_map(|event| match &event.chunk {
+            AutoFunctionStreamChunk::FunctionResults(results) => Some(results),
+            _ => None,
+        })
+        .expect("stream should yield FunctionResults");
+    assert!(results[0].timed_out);
+    assert!(matches!(
+        events.last().unwrap().chunk,
+        AutoFunctionStreamChunk::Complete(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_dropping_loop_cancels_running_functions() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::function_call(
+        "int-1",
+        "call-1",
+        "hang",
+        json!({}),
+    ));
+    let hang = Arc::new(Hang::default());
+    let client = server.client();
+
+    // No function timeout: the call only ends when the loop is dropped
+    let outcome = tokio::time::timeout(
+        Duration::from_millis(200),
+        client
+            .interaction()
+            .with_model("gemini-3-flash-preview")
+            .with_text("Hang")
+            .with_tool_service(hang_service(&hang))
+            .create_with_auto_functions(),
+    )
+    .await;
+
+    assert!(outcome.is_err());
+    assert!(wait_for(&hang.cancelled).await, "token was not cancelled");
+}
@@ -0,0 +1,126 @@
+//! HTTP client configuration tests
+//!
+//! Verifies that default headers, the user agent suffix, an injected
+//! `reqwest::Client` and a proxy configured on `ClientBuilder` all shape the
+//! requests that reach the server, including resumable upload sessions. These
+//! tests run offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test http_client_tests
+//! ```
+
+use genai_rs::Client;
+use genai_rs::testing::{MockResponse, MockServer, ReceivedRequest};
+use reqwest::header::{HeaderMap, HeaderValue};
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello"}],
+    "status": "completed"
+}"#;
+
+const FILE_JSON: &str = r#"{
+    "name": "files/abc123",
+    "mimeType": "text/plain",
+    "uri": "http://localhost/files/abc123",
+    "state": "ACTIVE"
+}"#;
+
+fn route(request: &ReceivedRequest, base_url: &str) -> MockResponse {
+    let path = request.path.as_str();
+    if path.starts_with("/upload/") {
+        MockResponse::json(200, "{}")
+            .with_header("x-goog-upload-url", format!("{base_url}/resumable/1"))
+    } else if path.starts_with("/resumable/") {
+        MockResponse::json(200, format!(r#"{{"file": {FILE_JSON}}}"#))
+    } else {
+        MockResponse::json(200, INTERACTION_JSON)
+    }
+}
+
+#[tokio::test]
+async fn test_default_headers_reach_every_request() {
+    let server = MockServer::start_with(route).await;
+    let client = server
+        .client_builder()
+        .with_default_header("x-goog-user-project", "billing-project")
+        .with_default_header("traceparent", "00-abc-def-01")
+        .with_user_agent_suffix("my-app/1.2.0")
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+    client
+        .upload_file_bytes(b"hello".to_vec(), "text/plain", None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    let user_agent = format!("genai-rs/{} my-app/1.2.0", env!("CARGO_PKG_VERSION"));
+    assert_eq!(requests.len(), 3);
+    for request in &requests {
+        assert_eq!(
+            request.header("x-goog-user-project"),
+            Some("billing-project"),
+            "missing on {}",
+            request.path
+        );
+        assert_eq!(request.header("traceparent"), Some("00-abc-def-01"));
+        assert_eq!(request.header("user-agent"), Some(user_agent.as_str()));
+    }
+    // The upload session URL carries its own authorization
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-api-key"));
+    assert_eq!(requests[2].path, "/resumable/1");
+    assert_eq!(requests[2].header("x-goog-api-key"), None);
+}
+
+#[tokio::test]
+async fn test_injected_http_client_is_used() {
+    let server = MockServer::start_with(route).await;
+    let mut headers = HeaderMap::new();
+    headers.insert("x-from-injected-client", HeaderValue::from_static("yes"));
+    let http_client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+    let client = server
+        .client_builder()
+        .with_http_client(http_client)
+        .with_default_header("x-goog-user-project", "billing-project")
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("x-from-injected-client"), Some("yes"));
+    assert_eq!(
+        request.header("x-goog-user-project"),
+        Some("billing-project")
+    );
+    assert_eq!(request.header("x-goog-api-key"), Some("test-api-key"));
+}
+
+#[tokio::test]
+async fn test_requests_route_through_proxy() {
+    let proxy = MockServer::start_with(route).await;
+    // The hostname does not resolve, so the request can only succeed via the proxy
+    let client = Client::builder("test-api-key".to_string())
+        .with_base_url("http://api.invalid")
+        .with_proxy(reqwest::Proxy::http(proxy.base_url()).unwrap())
+        .build()
+        .unwrap();
+
+    let response = client.get_interaction("interaction-123").await.unwrap();
+
+    assert_eq!(response.id.as_deref(), Some("interaction-123"));
+    let request = &proxy.requests()[0];
+    assert_eq!(
+        request.path,
+        "http://api.invalid/v1beta/interactions/interaction-123"
+    );
+    assert_eq!(request.header("host"), Some("api.invalid"));
+}
@@ -189,3 +189,267 @@ fn test_param_docs_not_allowed() {
     let params_json = serde_json::to_string(&decl.parameters()).unwrap();
     assert!(params_json.contains("This is the correct way to add param descriptions"));
 }
+
+#[test]
+fn test_timeout_secs_option() {
+    /// Fetches a page
+    #[tool(url(description = "URL to fetch"), timeout_secs = 10)]
+    fn test_with_timeout(url: String) -> String {
+        url
+    }
+
+    #[tool]
+    fn test_without_timeout(x: String) -> String {
+        x
+    }
+
+    let callable = TestWithTimeoutCallable;
+    assert_eq!(callable.timeout(), Some(std::time::Duration::from_secs(10)));
+    let decl = callable.declaration();
+    assert_eq!(decl.parameters().required(), vec!["url"]);
+    assert_eq!(decl.description(), "Fetches a page");
+
+    assert_eq!(TestWithoutTimeoutCallable.timeout(), None);
+}
+
+mod derived_schemas {
+    use genai_rs::{CallableFunction, ToolSchema};
+    use genai_rs_macros::{ToolSchema, tool};
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// How urgently the ticket needs attention
+    #[derive(Debug, Deserialize, PartialEq, ToolSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Priority {
+        Low,
+        VeryHigh,
+        #[serde(rename = "urgent")]
+        Critical,
+    }
+
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct Assignee {
+        /// Login name
+        pub login: String,
+        pub team: Option<String>,
+    }
+
+    /// A support ticket
+    #[derive(Debug, Deserialize, ToolSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Ticket {
+        /// One-line summary
+        pub title: String,
+        pub priority: Priority,
+        pub assignees: Vec<Assignee>,
+        pub due_date: Option<String>,
+        #[serde(default)]
+        pub custom_fields: HashMap<String, i64>,
+        #[serde(skip)]
+        pub internal_id: u64,
+    }
+
+    #[test]
+    fn test_derived_enum_schema() {
+        assert_eq!(
+            Priority::tool_schema(),
+            json!({
+                "type": "string",
+                "enum": ["low", "very_high", "urgent"],
+                "description": "How urgently the ticket needs attention",
+            })
+        );
+    }
+
+    #[test]
+    fn test_derived_struct_schema() {
+        assert_eq!(
+            Ticket::tool_schema(),
+            json!({
+                "type": "object",
+                "description": "A support ticket",
+                "properties": {
+                    "title": {"type": "string", "description": "One-line summary"},
+                    "priority": Priority::tool_schema(),
+                    "assignees": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "login": {"type": "string", "description": "Login name"},
+                                "team": {"type": "string"},
+                            },
+                            "required": ["login"],
+                        },
+                    },
+                    "dueDate": {"type": "string"},
+                    "customFields": {
+                        "type": "object",
+                        "additionalProperties": {"type": "integer"},
+                    },
+                },
+                "required": ["title", "priority", "assignees"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_tool_with_derived_parameters() {
+        /// Files a support ticket
+        #[tool(
+            ticket(description = "The ticket to file"),
+            level(enum_values = ["low", "very_high"])
+        )]
+        fn file_ticket(ticket: Ticket, level: Option<Priority>, note: String) -> String {
+            format!("{} {:?} {note}", ticket.title, level)
+        }
+
+        let decl = FileTicketCallable.declaration();
+        assert_eq!(decl.parameters().required(), vec!["ticket", "note"]);
+
+        let properties = decl.parameters().properties();
+        assert_eq!(properties["note"], json!({"type": "string"}));
+        assert_eq!(properties["ticket"]["description"], "The ticket to file");
+        assert_eq!(
+            properties["ticket"]["properties"],
+            Ticket::tool_schema()["properties"]
+        );
+        assert_eq!(properties["level"]["enum"], json!(["low", "very_high"]));
+    }
+
+    #[tokio::test]
+    async fn test_tool_deserializes_derived_arguments() {
+        #[tool]
+        fn count_assignees(ticket: Ticket) -> usize {
+            assert_eq!(ticket.priority, Priority::Critical);
+            ticket.assignees.len()
+        }
+
+        let result = CountAssigneesCallable
+            .call(json!({
+                "ticket": {
+                    "title": "Printer on fire",
+                    "priority": "urgent",
+                    "assignees": [{"login": "ada"}, {"login": "grace", "team": "ops"}],
+                }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"result": 2}));
+    }
+
+    /// A tree node
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct Node {
+        pub label: String,
+        pub children: Vec<Node>,
+    }
+
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct Expr {
+        pub op: String,
+        pub lhs: Option<Box<Expr>>,
+        pub args: Vec<Node>,
+    }
+
+    #[test]
+    fn test_recursive_schemas_terminate() {
+        assert_eq!(
+            Node::tool_schema(),
+            json!({
+                "type": "object",
+                "description": "A tree node",
+                "properties": {
+                    "label": {"type": "string"},
+                    "children": {
+                        "type": "array",
+                        "items": {"type": "object", "description": "A tree node"},
+                    },
+                },
+                "required": ["label", "children"],
+            })
+        );
+
+        // Other types nested inside a recursive one are still described in full
+        let expr = Expr::tool_schema();
+        assert_eq!(expr["properties"]["lhs"], json!({"type": "object"}));
+        assert_eq!(expr["properties"]["args"]["items"], Node::tool_schema());
+        assert_eq!(expr["required"], json!(["op", "args"]));
+    }
+
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct Page {
+        pub cursor: String,
+        pub size: Option<u32>,
+    }
+
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct Search {
+        pub query: String,
+        #[serde(flatten)]
+        pub page: Page,
+        #[serde(flatten)]
+        pub filters: HashMap<String, String>,
+    }
+
+    #[derive(Debug, Deserialize, ToolSchema)]
+    pub struct OptionalPage {
+        #[serde(flatten)]
+        pub page: Option<Page>,
+    }
+
+    #[test]
+    fn test_flattened_fields_merge_into_parent() {
+        assert_eq!(
+            Search::tool_schema(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string"},
+                    "cursor": {"type": "string"},
+                    "size": {"type": "integer"},
+                },
+                "required": ["query", "cursor"],
+                "additionalProperties": {"type": "string"},
+            })
+        );
+
+        let search: Search = serde_json::from_value(json!({
+            "query": "rust",
+            "cursor": "abc",
+            "lang": "en",
+        }))
+        .unwrap();
+        assert_eq!(search.page.cursor, "abc");
+        assert_eq!(search.filters["lang"], "en");
+
+        // An optional flattened struct contributes no required fields
+        let optional = OptionalPage::tool_schema();
+        assert_eq!(optional["properties"], Page::tool_schema()["properties"]);
+        assert!(optional.get("required").is_none());
+    }
+
+    #[test]
+    fn test_tool_with_nested_vec_parameter() {
+        #[tool]
+        fn sum_rows(rows: Vec<Vec<f64>>, weights: HashMap<String, f64>) -> f64 {
+            rows.iter().flatten().sum::<f64>() + weights.values().sum::<f64>()
+        }
+
+        let properties = SumRowsCallable
+            .declaration()
+            .parameters()
+            .properties()
+            .clone();
+        assert_eq!(
+            properties["rows"],
+            json!({"type": "array", "items": {"type": "array", "items": {"type": "number"}}})
+        );
+        assert_eq!(
+            properties["weights"]["additionalProperties"]["type"],
+            "number"
+        );
+    }
+}
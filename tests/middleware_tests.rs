@@ -0,0 +1,328 @@
+//! Middleware tests
+//!
+//! Verifies that middleware registered with `ClientBuilder::with_middleware()`
+//! can modify outgoing requests and headers, and observes response heads,
+//! parsed responses, stream events and errors for `create()`,
+//! `create_stream()` and auto-function loops. These tests run offline against
+//! a local stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test middleware_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    Client, FunctionDeclaration, GenaiError, InteractionInput, InteractionRequest,
+    InteractionResponse, Middleware, RetryPolicy, StreamEvent,
+};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello"}],
+    "status": "completed"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_2\"}\n\n",
+);
+
+/// Records every hook invocation as a short string.
+#[derive(Debug, Default, Clone)]
+struct Recorder {
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl Recorder {
+    fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn push(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[async_trait]
+impl Middleware for Recorder {
+    async fn on_request(
+        &self,
+        request: &mut InteractionRequest,
+        headers: &mut HeaderMap,
+    ) -> Result<(), GenaiError> {
+        self.push(format!(
+            "request:{}",
+            request.model.as_deref().unwrap_or("")
+        ));
+        headers.insert("x-audit-id", HeaderValue::from_static("audit-1"));
+        Ok(())
+    }
+
+    fn on_response_head(&self, status: u16, headers: &HeaderMap) {
+        let content_type = headers
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        self.push(format!("head:{status}:{content_type}"));
+    }
+
+    fn on_response(&self, response: &InteractionResponse) {
+        self.push(format!("response:{}", response.as_text().unwrap_or("")));
+    }
+
+    fn on_stream_event(&self, event: &StreamEvent) {
+        self.push(format!("event:{}", event.event_id.as_deref().unwrap_or("")));
+    }
+
+    fn on_error(&self, error: &GenaiError) {
+        let status = match error {
+            GenaiError::Api { status_code, .. } => status_code.to_string(),
+            _ => "other".to_string(),
+        };
+        self.push(format!("error:{status}"));
+    }
+}
+
+/// Replaces email addresses in text input.
+#[derive(Debug)]
+struct ScrubEmails;
+
+#[async_trait]
+impl Middleware for ScrubEmails {
+    async fn on_request(
+        &self,
+        request: &mut InteractionRequest,
+        _headers: &mut HeaderMap,
+    ) -> Result<(), GenaiError> {
+        if let InteractionInput::Text(text) = &mut request.input {
+            *text = text.replace("alice@example.com", "[EMAIL]");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Deny;
+
+#[async_trait]
+impl Middleware for Deny {
+    async fn on_request(
+        &self,
+        _request: &mut InteractionRequest,
+        _headers: &mut HeaderMap,
+    ) -> Result<(), GenaiError> {
+        Err(GenaiError::InvalidInput("blocked by policy".to_string()))
+    }
+}
+
+fn client_with(server: &MockServer, recorder: &Recorder) -> Client {
+    Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .with_middleware(recorder.clone())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_create_runs_hooks_and_adds_headers() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let recorder = Recorder::default();
+    let client = client_with(&server, &recorder);
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recorder.calls(),
+        [
+            "request:gemini-3-flash-preview",
+            "head:200:application/json",
+            "response:Hello",
+        ]
+    );
+    let requests = server.requests();
+    assert_eq!(requests[0].header("x-audit-id"), Some("audit-1"));
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-key"));
+}
+
+#[tokio::test]
+async fn test_request_can_be_modified() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .with_middleware(ScrubEmails)
+        .build()
+        .unwrap();
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Contact alice@example.com")
+        .create()
+        .await
+        .unwrap();
+
+    let body = server.requests()[0].body_text();
+    assert!(body.contains("Contact [EMAIL]"), "body: {body}");
+    assert!(!body.contains("alice@example.com"));
+}
+
+#[tokio::test]
+async fn test_stream_events_are_observed() {
+    let server = MockServer::start_with(|_, _| MockResponse::sse(SSE_BODY)).await;
+    let recorder = Recorder::default();
+    let client = client_with(&server, &recorder);
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok));
+    assert_eq!(
+        recorder.calls(),
+        [
+            "request:gemini-3-flash-preview",
+            "head:200:text/event-stream",
+            "event:evt_1",
+            "event:evt_2",
+        ]
+    );
+    assert_eq!(server.requests()[0].header("x-audit-id"), Some("audit-1"));
+}
+
+#[tokio::test]
+async fn test_errors_and_retries_are_observed() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(503, r#"{"error": {"message": "unavailable"}}"#)
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await;
+    let recorder = Recorder::default();
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .with_middleware(recorder.clone())
+        .with_retry_policy(
+            RetryPolicy::new()
+                .with_max_attempts(2)
+                .with_initial_backoff(Duration::from_millis(1)),
+        )
+        .build()
+        .unwrap();
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recorder.calls(),
+        [
+            "request:gemini-3-flash-preview",
+            "head:503:application/json",
+            "error:503",
+            "request:gemini-3-flash-preview",
+            "head:200:application/json",
+            "response:Hello",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_on_request_error_aborts_call() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let recorder = Recorder::default();
+    let client = Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .with_middleware(Deny)
+        .with_middleware(recorder.clone())
+        .build()
+        .unwrap();
+
+    let error = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, GenaiError::InvalidInput(_)));
+    assert!(server.requests().is_empty());
+    // Later middleware don't see the request, but do see the error
+    assert_eq!(recorder.calls(), ["error:other"]);
+}
+
+#[tokio::test]
+async fn test_auto_function_turns_are_observed() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(
+                200,
+                r#"{
+                    "id": "interaction-1",
+                    "status": "requires_action",
+                    "outputs": [{"type": "function_call", "id": "call-1", "name": "lookup", "arguments": {}}]
+                }"#,
+            )
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await;
+    let recorder = Recorder::default();
+    let client = client_with(&server, &recorder);
+
+    let lookup = FunctionDeclaration::builder("lookup")
+        .description("Looks something up")
+        .parameter("query", json!({"type": "string"}))
+        .build();
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Look it up")
+        .add_function(lookup)
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    let calls = recorder.calls();
+    assert_eq!(
+        calls.iter().filter(|c| c.starts_with("request:")).count(),
+        2
+    );
+    assert_eq!(calls.last().map(String::as_str), Some("response:Hello"));
+    assert!(
+        server
+            .requests()
+            .iter()
+            .all(|r| r.header("x-audit-id") == Some("audit-1"))
+    );
+}
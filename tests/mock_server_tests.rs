@@ -0,0 +1,239 @@
+//! MockServer tests
+//!
+//! Exercises `genai_rs::testing::MockServer` the way downstream test suites
+//! use it: scripted function-call turns, SSE streams, error statuses with
+//! `Retry-After`, mid-stream disconnects, and assertions on received requests.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test mock_server_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    CallableFunction, FunctionDeclaration, GenaiError, InteractionStatus, RetryPolicy, StreamChunk,
+};
+use genai_rs_macros::tool;
+use serde_json::json;
+use std::time::Duration;
+
+/// Reports the weather for a city
+#[allow(dead_code)]
+#[tool(city(description = "City name"))]
+fn mock_weather(city: String) -> String {
+    format!("Sunny in {city}")
+}
+
+fn weather_declaration() -> FunctionDeclaration {
+    FunctionDeclaration::builder("mock_weather")
+        .description("Reports the weather for a city")
+        .parameter("city", json!({"type": "string"}))
+        .required(vec!["city".to_string()])
+        .build()
+}
+
+#[tokio::test]
+async fn test_scripted_function_call_turns() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call(
+            "int-1",
+            "call-1",
+            "mock_weather",
+            json!({"city": "Paris"}),
+        ))
+        .enqueue(MockResponse::text("int-2", "It is sunny in Paris."));
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris?")
+        .add_function(weather_declaration())
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert_eq!(result.response.as_text(), Some("It is sunny in Paris."));
+    assert_eq!(
+        result.executions[0].result,
+        json!({"result": "Sunny in Paris"})
+    );
+    server.assert_request_count(2);
+    server.assert_script_consumed();
+
+    // The second turn sends the function result back on the first interaction
+    let second = server.requests()[1].json().unwrap();
+    assert_eq!(second["previousInteractionId"], "int-1");
+    let input = second["input"].to_string();
+    assert!(input.contains("call-1"), "function result missing: {input}");
+    assert!(input.contains("Sunny in Paris"));
+}
+
+#[tokio::test]
+async fn test_text_stream() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text_stream("int-1", &["Hel", "lo"]));
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .create_stream()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let text: String = events
+        .iter()
+        .filter_map(|e| match &e.chunk {
+            StreamChunk::Delta(delta) => delta.as_text().map(str::to_string),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Hello");
+    let StreamChunk::Complete(response) = &events.last().unwrap().chunk else {
+        panic!("stream should end with Complete");
+    };
+    assert_eq!(response.status, InteractionStatus::Completed);
+    assert_eq!(response.as_text(), Some("Hello"));
+    assert!(server.requests()[0].path.ends_with("?alt=sse"));
+}
+
+#[tokio::test]
+async fn test_function_call_stream() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::function_call_stream(
+        "int-1",
+        "call-1",
+        "mock_weather",
+        json!({"city": "Oslo"}),
+    ));
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Oslo?")
+        .create_stream()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let StreamChunk::Complete(response) = &events.last().unwrap().chunk else {
+        panic!("stream should end with Complete");
+    };
+    assert_eq!(response.status, InteractionStatus::RequiresAction);
+    let calls = response.function_calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].name, "mock_weather");
+    assert_eq!(calls[0].args, &json!({"city": "Oslo"}));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_error_with_retry_after_is_retried() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(
+            MockResponse::error(429, "Resource exhausted").with_retry_after(Duration::from_secs(2)),
+        )
+        .enqueue(MockResponse::text("int-1", "Recovered"));
+    let client = server
+        .client_builder()
+        .with_retry_policy(RetryPolicy::new().with_max_attempts(2))
+        .build()
+        .unwrap();
+
+    let started = tokio::time::Instant::now();
+    let response = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .create()
+        .await
+        .unwrap();
+
+    assert_eq!(response.as_text(), Some("Recovered"));
+    assert!(started.elapsed() >= Duration::from_secs(2));
+    server.assert_request_count(2);
+}
+
+#[tokio::test]
+async fn test_error_status_surfaces_as_api_error() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::error(429, "Resource exhausted").with_retry_after(Duration::from_secs(30)),
+    );
+
+    let error = server.client().get_interaction("int-1").await.unwrap_err();
+
+    match error {
+        GenaiError::Api {
+            status_code,
+            message,
+            retry_after,
+            ..
+        } => {
+            assert_eq!(status_code, 429);
+            assert!(message.contains("Resource exhausted"));
+            assert_eq!(retry_after, Some(Duration::from_secs(30)));
+        }
+        other => panic!("expected Api error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_mid_stream_disconnect() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::text_stream("int-1", &["Hel", "lo", " world"]).disconnect_after_events(2),
+    );
+
+    let client = server.client();
+    let mut stream = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .create_stream();
+
+    let mut event_ids = Vec::new();
+    let mut error = None;
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(event) => event_ids.push(event.event_id),
+            Err(e) => {
+                error = Some(e);
+                break;
+            }
+        }
+    }
+
+    assert_eq!(
+        event_ids,
+        [Some("evt_1".to_string()), Some("evt_2".to_string())]
+    );
+    assert!(error.is_some(), "a dropped stream should surface an error");
+}
+
+#[tokio::test]
+async fn test_exhausted_script_answers_500() {
+    let server = MockServer::start().await;
+
+    let error = server.client().get_interaction("int-1").await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        GenaiError::Api {
+            status_code: 500,
+            ..
+        }
+    ));
+    assert!(error.to_string().contains("script exhausted"));
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/v1beta/interactions/int-1");
+    assert_eq!(request.header("x-goog-api-key"), Some("test-api-key"));
+}
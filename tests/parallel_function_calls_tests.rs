@@ -0,0 +1,213 @@
+//! Parallel function execution tests
+//!
+//! Verifies that the function calls from one model turn run concurrently in
+//! `create_with_auto_functions()` and `create_stream_with_auto_functions()`,
+//! bounded by `with_max_parallel_function_calls()`, while results keep the
+//! order the model requested them in. These tests run offline against
+//! `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test parallel_function_calls_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    AutoFunctionStreamChunk, CallableFunction, FunctionDeclaration, FunctionError, ToolService,
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Sleeps for `delay_ms` and echoes `label`, recording peak concurrency.
+#[derive(Default)]
+struct SlowEcho {
+    active: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+#[async_trait]
+impl CallableFunction for SlowEcho {
+    fn declaration(&self) -> FunctionDeclaration {
+        FunctionDeclaration::builder("slow_echo")
+            .description("Echoes a label after a delay")
+            .parameter("label", json!({"type": "string"}))
+            .parameter("delay_ms", json!({"type": "integer"}))
+            .build()
+    }
+
+    async fn call(&self, args: Value) -> Result<Value, FunctionError> {
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(args["delay_ms"].as_u64().unwrap())).await;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        Ok(json!({"label": args["label"]}))
+    }
+}
+
+struct EchoService(Arc<SlowEcho>);
+
+impl ToolService for EchoService {
+    fn tools(&self) -> Vec<Arc<dyn CallableFunction>> {
+        vec![self.0.clone()]
+    }
+}
+
+/// Three calls whose delays make them finish in reverse order.
+fn calls() -> [(&'static str, &'static str, Value); 3] {
+    [
+        (
+            "call-a",
+            "slow_echo",
+            json!({"label": "a", "delay_ms": 300}),
+        ),
+        (
+            "call-b",
+            "slow_echo",
+            json!({"label": "b", "delay_ms": 200}),
+        ),
+        (
+            "call-c",
+            "slow_echo",
+            json!({"label": "c", "delay_ms": 100}),
+        ),
+    ]
+}
+
+fn labels(results: impl IntoIterator<Item = Value>) -> Vec<String> {
+    results
+        .into_iter()
+        .map(|result| result["label"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_calls_in_one_turn_run_concurrently_in_order() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls("int-1", calls()))
+        .enqueue(MockResponse::text("int-2", "Done"));
+    let echo = Arc::new(SlowEcho::default());
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Echo three labels")
+        .with_tool_service(Arc::new(EchoService(echo.clone())))
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    // All three calls were in flight at once
+    assert_eq!(echo.peak.load(Ordering::SeqCst), 3);
+    assert_eq!(
+        labels(result.executions.iter().map(|e| e.result.clone())),
+        ["a", "b", "c"]
+    );
+    let call_ids: Vec<_> = result
+        .executions
+        .iter()
+        .map(|e| e.call_id.as_str())
+        .collect();
+    assert_eq!(call_ids, ["call-a", "call-b", "call-c"]);
+    assert!(result.executions[0].duration >= Duration::from_millis(300));
+
+    // Results go back to the model in call order too
+    let input = server.requests()[1].json().unwrap()["input"].to_string();
+    let a = input.find("call-a").unwrap();
+    let b = input.find("call-b").unwrap();
+    let c = input.find("call-c").unwrap();
+    assert!(a < b && b < c, "results out of order: {input}");
+}
+
+#[tokio::test]
+async fn test_max_parallel_function_calls_limits_concurrency() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls("int-1", calls()))
+        .enqueue(MockResponse::text("int-2", "Done"));
+    let echo = Arc::new(SlowEcho::default());
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Echo three labels")
+        .with_tool_service(Arc::new(EchoService(echo.clone())))
+        .with_max_parallel_function_calls(2)
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert_eq!(echo.peak.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        labels(result.executions.iter().map(|e| e.result.clone())),
+        ["a", "b", "c"]
+    );
+}
+
+#[tokio::test]
+async fn test_one_parallel_call_executes_sequentially() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls("int-1", calls()))
+        .enqueue(MockResponse::text("int-2", "Done"));
+    let echo = Arc::new(SlowEcho::default());
+
+    let start = Instant::now();
+    server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Echo three labels")
+        .with_tool_service(Arc::new(EchoService(echo.clone())))
+        .with_max_parallel_function_calls(1)
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(600));
+    assert_eq!(echo.peak.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_streaming_calls_run_concurrently_in_order() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_calls_stream("int-1", calls()))
+        .enqueue(MockResponse::text_stream("int-2", &["Done"]));
+    let echo = Arc::new(SlowEcho::default());
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Echo three labels")
+        .with_tool_service(Arc::new(EchoService(echo.clone())))
+        .create_stream_with_auto_functions()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    assert_eq!(echo.peak.load(Ordering::SeqCst), 3);
+    let results = events
+        .iter()
+        .find_map(|event| match &event.chunk {
+            AutoFunctionStreamChunk::FunctionResults(results) => Some(results),
+            _ => None,
+        })
+        .expect("stream should yield FunctionResults");
+    assert_eq!(
+        labels(results.iter().map(|e| e.result.clone())),
+        ["a", "b", "c"]
+    );
+    assert!(matches!(
+        events.last().unwrap().chunk,
+        AutoFunctionStreamChunk::Complete(_)
+    ));
+}
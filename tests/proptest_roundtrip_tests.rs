@@ -19,7 +19,8 @@
 use chrono::{DateTime, TimeZone, Utc};
 use genai_rs::{
     Annotation, AutoFunctionResult, AutoFunctionStreamChunk, Content, FunctionExecutionResult,
-    InteractionResponse, InteractionStatus, ModalityTokens, PendingFunctionCall, UsageMetadata,
+    InteractionResponse, InteractionStatus, ModalityTokens, PendingFunctionCall, Turn,
+    UsageMetadata,
 };
 use proptest::prelude::*;
 
@@ -92,6 +93,16 @@ fn arb_function_execution_result() -> impl Strategy<Value = FunctionExecutionRes
         })
 }
 
+fn arb_turn() -> impl Strategy<Value = Turn> {
+    (any::<bool>(), arb_identifier()).prop_map(|(is_user, text)| {
+        if is_user {
+            Turn::user(text)
+        } else {
+            Turn::model(text)
+        }
+    })
+}
+
 fn arb_pending_function_call() -> impl Strategy<Value = PendingFunctionCall> {
     (arb_identifier(), arb_identifier(), arb_json_value())
         .prop_map(|(name, call_id, args)| PendingFunctionCall::new(&name, &call_id, args))
@@ -275,6 +286,8 @@ fn arb_auto_function_stream_chunk() -> impl Strategy<Value = AutoFunctionStreamC
     prop_oneof![
         // Delta variant
         arb_interaction_content().prop_map(AutoFunctionStreamChunk::Delta),
+        // AwaitingApproval variant
+        arb_pending_function_call().prop_map(AutoFunctionStreamChunk::AwaitingApproval),
         // ExecutingFunctions variant
         (
             arb_interaction_response(),
@@ -293,6 +306,8 @@ fn arb_auto_function_stream_chunk() -> impl Strategy<Value = AutoFunctionStreamC
         arb_interaction_response().prop_map(AutoFunctionStreamChunk::Complete),
         // MaxLoopsReached variant
         arb_interaction_response().prop_map(AutoFunctionStreamChunk::MaxLoopsReached),
+        // Transcript variant
+        prop::collection::vec(arb_turn(), 0..5).prop_map(AutoFunctionStreamChunk::Transcript),
         // Unknown variant for forward compatibility
         (arb_identifier(), arb_json_value()).prop_map(|(chunk_type, data)| {
             AutoFunctionStreamChunk::Unknown { chunk_type, data }
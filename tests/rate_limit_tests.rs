@@ -0,0 +1,149 @@
+//! Client-side rate limiter tests
+//!
+//! Verifies that a `RateLimiter` delays model calls once a model's budget is
+//! spent, that token usage from responses (unary and streaming) is debited,
+//! and that client clones share one budget. These tests run offline against a
+//! local stand-in server with Tokio's clock paused, so waits are instant.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test rate_limit_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{Client, RateLimit, RateLimiter};
+use std::time::Duration;
+use tokio::time::Instant;
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello"}],
+    "status": "completed",
+    "usage": {"total_tokens": 900}
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\",\"usage\":{\"total_tokens\":900}},\"event_id\":\"evt_2\"}\n\n",
+);
+
+async fn server() -> MockServer {
+    MockServer::start_with(|request, _| {
+        if request.path.contains("alt=sse") {
+            MockResponse::sse(SSE_BODY)
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await
+}
+
+fn client_with(server: &MockServer, limiter: RateLimiter) -> Client {
+    Client::builder("test-key".to_string())
+        .with_base_url(server.base_url())
+        .with_rate_limiter(limiter)
+        .build()
+        .unwrap()
+}
+
+async fn create(client: &Client, model: &str) {
+    client
+        .interaction()
+        .with_model(model)
+        .with_text("Hello")
+        .create()
+        .await
+        .expect("create succeeds");
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_requests_wait_for_rpm_capacity() {
+    let server = server().await;
+    let client = client_with(
+        &server,
+        RateLimiter::new(RateLimit::new().with_requests_per_minute(2)),
+    );
+    let start = Instant::now();
+
+    create(&client, "gemini-3-flash-preview").await;
+    create(&client, "gemini-3-flash-preview").await;
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    create(&client, "gemini-3-flash-preview").await;
+    assert!(start.elapsed() >= Duration::from_secs(30));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_usage_is_debited_from_tpm() {
+    let server = server().await;
+    let client = client_with(
+        &server,
+        RateLimiter::new(RateLimit::new().with_tokens_per_minute(600)),
+    );
+    let start = Instant::now();
+
+    // The response reports 900 tokens, leaving the bucket 300 in debt
+    create(&client, "gemini-3-flash-preview").await;
+    create(&client, "gemini-3-flash-preview").await;
+
+    assert!(start.elapsed() >= Duration::from_secs(30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_usage_is_debited_from_tpm() {
+    let server = server().await;
+    let client = client_with(
+        &server,
+        RateLimiter::new(RateLimit::new().with_tokens_per_minute(600)),
+    );
+    let start = Instant::now();
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+    assert!(events.iter().all(Result::is_ok));
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    create(&client, "gemini-3-flash-preview").await;
+    assert!(start.elapsed() >= Duration::from_secs(30));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_clones_share_one_budget() {
+    let server = server().await;
+    let limiter = RateLimiter::new(RateLimit::new().with_requests_per_minute(1));
+    let client = client_with(&server, limiter.clone());
+    let clone = client.clone();
+    let other_client = client_with(&server, limiter);
+    let start = Instant::now();
+
+    create(&client, "gemini-3-flash-preview").await;
+    create(&clone, "gemini-3-flash-preview").await;
+    assert!(start.elapsed() >= Duration::from_secs(60));
+
+    create(&other_client, "gemini-3-flash-preview").await;
+    assert!(start.elapsed() >= Duration::from_secs(120));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_limits_are_per_model() {
+    let server = server().await;
+    let client = client_with(
+        &server,
+        RateLimiter::new(RateLimit::new().with_requests_per_minute(1)),
+    );
+    let start = Instant::now();
+
+    create(&client, "gemini-3-flash-preview").await;
+    create(&client, "gemini-3-pro-preview").await;
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
@@ -0,0 +1,316 @@
+//! Built-in retry policy tests
+//!
+//! Verifies that `RetryPolicy` retries transient failures for unary calls, the
+//! initial connection of streams, and Files API operations, and that
+//! per-request overrides take precedence over the client policy. These tests
+//! run offline against a local stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test retry_policy_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{Client, GenaiError, RetryPolicy, StreamChunk};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Recovered"}],
+    "status": "completed"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Recovered\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_2\"}\n\n",
+);
+
+const UNAVAILABLE_JSON: &str = r#"{"error": {"code": 503, "message": "Service unavailable"}}"#;
+
+/// Starts a server that fails the first `failures` requests with `failure`,
+/// then answers with `success`.
+async fn flaky_server(failures: usize, failure: MockResponse, success: MockResponse) -> MockServer {
+    let count = Arc::new(AtomicUsize::new(0));
+    MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) < failures {
+            failure.clone()
+        } else {
+            success.clone()
+        }
+    })
+    .await
+}
+
+/// A fast policy so tests don't wait on real backoff delays.
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new()
+        .with_max_attempts(max_attempts)
+        .with_initial_backoff(Duration::from_millis(1))
+        .with_max_backoff(Duration::from_millis(5))
+}
+
+fn client_for(server: &MockServer, policy: Option<RetryPolicy>) -> Client {
+    let mut builder = Client::builder("test-key".to_string()).with_base_url(server.base_url());
+    if let Some(policy) = policy {
+        builder = builder.with_retry_policy(policy);
+    }
+    builder.build().expect("client builds")
+}
+
+#[tokio::test]
+async fn test_execute_retries_server_errors() {
+    let server = flaky_server(
+        2,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let response = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .expect("succeeds after retries");
+
+    assert_eq!(response.as_text(), Some("Recovered"));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_execute_gives_up_after_max_attempts() {
+    let server = flaky_server(
+        10,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(2)));
+
+    let error = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .expect_err("fails after exhausting attempts");
+
+    assert!(matches!(
+        error,
+        GenaiError::Api {
+            status_code: 503,
+            ..
+        }
+    ));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let server = flaky_server(
+        10,
+        MockResponse::json(400, r#"{"error": {"message": "Bad request"}}"#),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(5)));
+
+    let result = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_no_policy_means_single_attempt() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, None);
+
+    let result = client.get_interaction("interaction-123").await;
+
+    assert!(result.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_after_header_is_honored() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(429, r#"{"error": {"message": "Rate limited"}}"#)
+            .with_header("Retry-After", "1"),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(2)));
+
+    let start = std::time::Instant::now();
+    client
+        .get_interaction("interaction-123")
+        .await
+        .expect("succeeds after Retry-After");
+
+    assert!(
+        start.elapsed() >= Duration::from_secs(1),
+        "should wait for Retry-After, waited {:?}",
+        start.elapsed()
+    );
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_after_beyond_cap_fails_fast() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(429, r#"{"error": {"message": "Rate limited"}}"#)
+            .with_header("Retry-After", "3600"),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let error = client
+        .get_interaction("interaction-123")
+        .await
+        .expect_err("should not wait an hour");
+
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(3600)));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_stream_initial_connect_is_retried() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::sse(SSE_BODY),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+
+    let events: Vec<_> = events.into_iter().map(|e| e.expect("event ok")).collect();
+    assert!(matches!(
+        events.last().unwrap().chunk,
+        StreamChunk::Complete(_)
+    ));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_per_request_policy_overrides_client_policy() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let result = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .with_retry_policy(RetryPolicy::none())
+        .create()
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_per_request_policy_without_client_policy() {
+    let server = flaky_server(
+        1,
+        MockResponse::json(500, UNAVAILABLE_JSON),
+        MockResponse::json(200, INTERACTION_JSON),
+    )
+    .await;
+    let client = client_for(&server, None);
+
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .with_retry_policy(fast_policy(2))
+        .create()
+        .await
+        .expect("per-request policy retries");
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_file_operations_are_retried() {
+    let file_json =
+        r#"{"name": "files/abc123", "mimeType": "text/plain", "uri": "u", "state": "ACTIVE"}"#;
+    let server = flaky_server(
+        1,
+        MockResponse::json(503, UNAVAILABLE_JSON),
+        MockResponse::json(200, file_json),
+    )
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let file = client
+        .get_file("files/abc123")
+        .await
+        .expect("get_file retried");
+
+    assert!(file.is_active());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_upload_is_retried_from_the_start() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |request, base_url| {
+        if request.path.starts_with("/upload/") {
+            MockResponse::json(200, "{}")
+                .with_header("x-goog-upload-url", format!("{base_url}/session"))
+        } else if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(503, UNAVAILABLE_JSON)
+        } else {
+            MockResponse::json(
+                200,
+                r#"{"file": {"name": "files/abc123", "mimeType": "text/plain", "uri": "u"}}"#,
+            )
+        }
+    })
+    .await;
+    let client = client_for(&server, Some(fast_policy(3)));
+
+    let file = client
+        .upload_file_bytes(b"hello".to_vec(), "text/plain", None)
+        .await
+        .expect("upload retried");
+
+    assert_eq!(file.name, "files/abc123");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4, "start + failed upload, start + upload");
+    assert_eq!(requests[3].body, b"hello");
+}
@@ -0,0 +1,153 @@
+//! Stream stall detection tests
+//!
+//! Verifies `InteractionBuilder::with_stall_policy()`: a stream that goes quiet
+//! past the idle threshold for its phase is reconnected from the last event,
+//! probed with `get_interaction()`, or failed with `GenaiError::Timeout`, while
+//! events arriving within the threshold act as heartbeats. These tests run
+//! offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test stall_policy_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{GenaiError, RetryPolicy, StallAction, StallPolicy, StreamChunk, StreamEvent};
+use serde_json::json;
+use std::time::Duration;
+
+const IDLE: Duration = Duration::from_millis(200);
+
+fn stall_policy(action: StallAction) -> StallPolicy {
+    StallPolicy::new()
+        .with_status_idle(IDLE)
+        .with_content_idle(IDLE)
+        .with_action(action)
+}
+
+async fn collect(server: &MockServer, policy: StallPolicy) -> Vec<Result<StreamEvent, GenaiError>> {
+    let client = server.client();
+    client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hi")
+        .with_stall_policy(policy)
+        .with_auto_resume(RetryPolicy::new().with_initial_backoff(Duration::from_millis(10)))
+        .create_stream()
+        .collect()
+        .await
+}
+
+fn text_of(events: &[StreamEvent]) -> String {
+    events
+        .iter()
+        .filter_map(|e| match &e.chunk {
+            StreamChunk::Delta(delta) => delta.as_text().map(str::to_string),
+            _ => None,
+        })
+        .collect()
+}
+
+fn interaction(status: &str) -> MockResponse {
+    MockResponse::json(200, json!({"id": "int-1", "status": status}).to_string())
+}
+
+#[tokio::test]
+async fn test_stalled_stream_reconnects_from_last_event() {
+    let server = MockServer::start().await;
+    let chunks = ["Hel", "lo", " world"];
+    server
+        .enqueue(MockResponse::text_stream("int-1", &chunks).stall_after_events(2))
+        .enqueue(MockResponse::text_stream("int-1", &chunks));
+
+    let events: Vec<_> = collect(&server, stall_policy(StallAction::Reconnect))
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(text_of(&events), "Hello world");
+    assert_eq!(events.len(), 5);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "GET");
+    assert!(requests[1].path.contains("last_event_id=evt_2"));
+}
+
+#[tokio::test]
+async fn test_probe_keeps_waiting_while_in_progress() {
+    let server = MockServer::start().await;
+    let chunks = ["Hel", "lo"];
+    server
+        .enqueue(MockResponse::text_stream("int-1", &chunks).stall_after_events(1))
+        .enqueue(interaction("in_progress"))
+        .enqueue(interaction("completed"))
+        .enqueue(MockResponse::text_stream("int-1", &chunks));
+
+    let events: Vec<_> = collect(&server, stall_policy(StallAction::Probe))
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    assert_eq!(text_of(&events), "Hello");
+    let requests = server.requests();
+    let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths[1], "/v1beta/interactions/int-1");
+    assert_eq!(paths[2], "/v1beta/interactions/int-1");
+    assert!(paths[3].contains("alt=sse"));
+    assert!(paths[3].contains("last_event_id=evt_1"));
+    server.assert_script_consumed();
+}
+
+#[tokio::test]
+async fn test_fail_action_yields_timeout() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text_stream("int-1", &["Hel", "lo"]).stall_after_events(2));
+
+    let results = collect(&server, stall_policy(StallAction::Fail)).await;
+
+    assert_eq!(results.len(), 3);
+    assert!(results[..2].iter().all(Result::is_ok));
+    assert!(matches!(results[2], Err(GenaiError::Timeout(idle)) if idle == IDLE));
+    server.assert_request_count(1);
+}
+
+#[tokio::test]
+async fn test_stall_before_interaction_id_times_out() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text_stream("int-1", &["Hel"]).stall_after_events(0));
+
+    let results = collect(&server, stall_policy(StallAction::Reconnect)).await;
+
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], Err(GenaiError::Timeout(_))));
+    server.assert_request_count(1);
+}
+
+#[tokio::test]
+async fn test_status_updates_act_as_heartbeats() {
+    let status = |id: &str| json!({"event_type": "interaction.status_update", "interaction_id": "int-1", "status": "in_progress", "event_id": id});
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::sse_events([
+            json!({"event_type": "interaction.start", "interaction": {"id": "int-1", "status": "in_progress"}, "event_id": "evt_1"}),
+            status("evt_2"),
+            status("evt_3"),
+            status("evt_4"),
+            json!({"event_type": "interaction.complete", "interaction": {"id": "int-1", "status": "completed"}, "event_id": "evt_5"}),
+        ])
+        .with_event_delay(Duration::from_millis(100)),
+    );
+
+    // Five events 100ms apart exceed the idle threshold in total, but never
+    // between two events
+    let policy = stall_policy(StallAction::Fail).with_status_idle(Duration::from_millis(250));
+    let results = collect(&server, policy).await;
+
+    assert_eq!(results.len(), 5);
+    assert!(results.iter().all(Result::is_ok));
+    server.assert_request_count(1);
+}
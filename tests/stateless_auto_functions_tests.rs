@@ -0,0 +1,302 @@
+//! Stateless auto-function calling tests
+//!
+//! Verifies that `create_with_auto_functions()` and
+//! `create_stream_with_auto_functions()` work with `with_store_disabled()`:
+//! each round resends the full conversation as turns instead of chaining with
+//! `previous_interaction_id`, model turns keep their thought signatures, and
+//! the final transcript is returned for the caller to persist. These tests run
+//! offline against `genai_rs::testing::MockServer`.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test stateless_auto_functions_tests
+//! ```
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer};
+use genai_rs::{
+    AutoFunctionResultAccumulator, AutoFunctionStreamChunk, CallableFunction, Content,
+    FunctionDeclaration, FunctionError, ToolService, Turn,
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+struct Weather;
+
+#[async_trait]
+impl CallableFunction for Weather {
+    fn declaration(&self) -> FunctionDeclaration {
+        FunctionDeclaration::builder("get_weather")
+            .description("Gets the weather for a city")
+            .parameter("city", json!({"type": "string"}))
+            .build()
+    }
+
+    async fn call(&self, args: Value) -> Result<Value, FunctionError> {
+        Ok(json!({"city": args["city"], "temp": 18}))
+    }
+}
+
+struct WeatherService;
+
+impl ToolService for WeatherService {
+    fn tools(&self) -> Vec<Arc<dyn CallableFunction>> {
+        vec![Arc::new(Weather)]
+    }
+}
+
+/// A stateless response (no interaction ID) with a thought and a function call.
+fn thought_then_call(call_id: &str, city: &str) -> MockResponse {
+    MockResponse::json(
+        200,
+        json!({
+            "status": "requires_action",
+            "outputs": [
+                {"type": "thought", "signature": "sig-abc"},
+                {
+                    "type": "function_call",
+                    "id": call_id,
+                    "name": "get_weather",
+                    "arguments": {"city": city},
+                },
+            ],
+        })
+        .to_string(),
+    )
+}
+
+fn final_text(text: &str) -> MockResponse {
+    MockResponse::json(
+        200,
+        json!({
+            "status": "completed",
+            "outputs": [{"type": "text", "text": text}],
+        })
+        .to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_stateless_loop_resends_full_history() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(thought_then_call("call-1", "Paris"))
+        .enqueue(thought_then_call("call-2", "Lyon"))
+        .enqueue(final_text("Paris and Lyon are both 18°C."));
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris and Lyon?")
+        .with_tool_service(Arc::new(WeatherService))
+        .with_store_disabled()
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert_eq!(result.executions.len(), 2);
+    assert_eq!(
+        result.response.as_text(),
+        Some("Paris and Lyon are both 18°C.")
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    for request in &requests {
+        let body = request.json().unwrap();
+        assert_eq!(body["store"], false);
+        assert!(body.get("previousInteractionId").is_none(), "{body}");
+    }
+
+    // The third request carries every turn so far, thought signatures included
+    let input = &requests[2].json().unwrap()["input"];
+    let roles: Vec<_> = input
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|turn| turn["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "model", "user", "model", "user"]);
+    assert_eq!(input[0]["content"], "Weather in Paris and Lyon?");
+    assert_eq!(input[1]["content"][0]["signature"], "sig-abc");
+    assert_eq!(input[1]["content"][1]["type"], "function_call");
+    assert_eq!(input[2]["content"][0]["type"], "function_result");
+    assert_eq!(input[4]["content"][0]["call_id"], "call-2");
+
+    // The transcript ends with the final model turn
+    let transcript = result
+        .transcript
+        .expect("stateless result has a transcript");
+    assert_eq!(transcript.len(), 6);
+    assert!(transcript[5].is_model());
+    assert_eq!(
+        transcript[5].content().as_parts().unwrap()[0].as_text(),
+        Some("Paris and Lyon are both 18°C.")
+    );
+}
+
+#[tokio::test]
+async fn test_stateless_transcript_continues_history() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(thought_then_call("call-1", "Osaka"))
+        .enqueue(final_text("Osaka is 18°C."));
+    let history = vec![
+        Turn::user("Weather in Tokyo?"),
+        Turn::model("Tokyo is 20°C."),
+    ];
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_history(history.clone())
+        .with_text("And in Osaka?")
+        .with_tool_service(Arc::new(WeatherService))
+        .with_store_disabled()
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    let transcript = result.transcript.unwrap();
+    assert_eq!(&transcript[..2], &history[..]);
+    assert_eq!(transcript[2].as_text(), Some("And in Osaka?"));
+    assert_eq!(transcript.len(), 6);
+}
+
+#[tokio::test]
+async fn test_stored_loop_has_no_transcript() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call(
+            "int-1",
+            "call-1",
+            "get_weather",
+            json!({"city": "Paris"}),
+        ))
+        .enqueue(MockResponse::text("int-2", "18°C"));
+
+    let result = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris?")
+        .with_tool_service(Arc::new(WeatherService))
+        .create_with_auto_functions()
+        .await
+        .unwrap();
+
+    assert!(result.transcript.is_none());
+    let body = server.requests()[1].json().unwrap();
+    assert_eq!(body["previousInteractionId"], "int-1");
+}
+
+#[tokio::test]
+async fn test_stateless_stream_yields_transcript() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::function_call_stream(
+            "int-1",
+            "call-1",
+            "get_weather",
+            json!({"city": "Paris"}),
+        ))
+        .enqueue(MockResponse::text_stream("int-2", &["18°C"]));
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris?")
+        .with_tool_service(Arc::new(WeatherService))
+        .with_store_disabled()
+        .create_stream_with_auto_functions()
+        .map(|event| event.unwrap().chunk)
+        .collect()
+        .await;
+
+    let body = server.requests()[1].json().unwrap();
+    assert!(body.get("previousInteractionId").is_none(), "{body}");
+    assert_eq!(body["input"][1]["role"], "model");
+    assert_eq!(body["input"][1]["content"][0]["name"], "get_weather");
+    assert_eq!(body["input"][2]["content"][0]["call_id"], "call-1");
+
+    // Transcript comes right before Complete
+    let n = events.len();
+    assert!(matches!(
+        events[n - 2],
+        AutoFunctionStreamChunk::Transcript(_)
+    ));
+    assert!(matches!(
+        events[n - 1],
+        AutoFunctionStreamChunk::Complete(_)
+    ));
+
+    let mut accumulator = AutoFunctionResultAccumulator::new();
+    let result = events
+        .into_iter()
+        .find_map(|chunk| accumulator.push(chunk))
+        .unwrap();
+    let transcript = result.transcript.unwrap();
+    assert_eq!(transcript.len(), 4);
+    assert_eq!(
+        transcript[3].content().as_parts(),
+        Some(&[Content::text("18°C")][..])
+    );
+}
+
+#[tokio::test]
+async fn test_stateless_stream_keeps_unindexed_thought_signatures() {
+    let server = MockServer::start().await;
+    server
+        .enqueue(MockResponse::sse_events([
+            json!({"event_type": "interaction.start", "interaction": {"status": "in_progress"}}),
+            json!({"event_type": "content.delta", "delta": {"type": "thought", "signature": "sig-stream"}}),
+            json!({
+                "event_type": "content.delta",
+                "delta": {
+                    "type": "function_call",
+                    "id": "call-1",
+                    "name": "get_weather",
+                    "arguments": {"city": "Paris"},
+                },
+            }),
+            json!({"event_type": "interaction.complete", "interaction": {"status": "requires_action"}}),
+        ]))
+        .enqueue(MockResponse::text_stream("int-2", &["18°C"]));
+
+    let events: Vec<_> = server
+        .client()
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Weather in Paris?")
+        .with_tool_service(Arc::new(WeatherService))
+        .with_store_disabled()
+        .create_stream_with_auto_functions()
+        .map(|event| event.unwrap().chunk)
+        .collect()
+        .await;
+
+    // The resent model turn keeps the thought signature ahead of the call
+    let body = server.requests()[1].json().unwrap();
+    let model_turn = &body["input"][1];
+    assert_eq!(model_turn["role"], "model");
+    assert_eq!(model_turn["content"][0]["type"], "thought");
+    assert_eq!(model_turn["content"][0]["signature"], "sig-stream");
+    assert_eq!(model_turn["content"][1]["name"], "get_weather");
+    assert_eq!(body["input"][2]["content"][0]["call_id"], "call-1");
+
+    let mut accumulator = AutoFunctionResultAccumulator::new();
+    let result = events
+        .into_iter()
+        .find_map(|chunk| accumulator.push(chunk))
+        .unwrap();
+    let transcript = result.transcript.unwrap();
+    assert_eq!(
+        transcript[1].content().as_parts().unwrap()[0].thought_signature(),
+        Some("sig-stream")
+    );
+}
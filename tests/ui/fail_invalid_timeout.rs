@@ -0,0 +1,9 @@
+use genai_rs_macros::tool;
+
+// This should fail because timeout_secs must be a positive integer literal.
+#[tool(timeout_secs = "10")]
+fn test_invalid_timeout(name: String) -> String {
+    name
+}
+
+fn main() {}
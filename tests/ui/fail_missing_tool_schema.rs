@@ -0,0 +1,15 @@
+use genai_rs::CallableFunction;
+use genai_rs_macros::tool;
+
+#[derive(serde::Deserialize)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[tool]
+fn distance(point: Point) -> f64 {
+    (point.x * point.x + point.y * point.y).sqrt()
+}
+
+fn main() {}
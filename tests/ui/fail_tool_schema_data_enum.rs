@@ -0,0 +1,9 @@
+use genai_rs_macros::ToolSchema;
+
+#[derive(ToolSchema)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use genai_rs_macros::tool;
+
+// This should fail because "retries" is not a recognized option.
+// Valid options are: timeout_secs
+#[tool(retries = 3)]
+fn test_unknown_option(name: String) -> String {
+    name
+}
+
+fn main() {}
@@ -0,0 +1,224 @@
+//! Vertex AI backend tests
+//!
+//! Verifies that `Backend::Vertex` scopes every endpoint under
+//! `/v1beta1/projects/{project}/locations/{location}` and authenticates with an
+//! OAuth bearer token, while builders, streaming and auto-functions behave the
+//! same as on the Gemini API. These tests run offline against a local
+//! stand-in server.
+//!
+//! # Running Tests
+//!
+//! ```bash
+//! cargo test --test vertex_backend_tests
+//! ```
+
+use futures_util::StreamExt;
+use genai_rs::testing::{MockResponse, MockServer, ReceivedRequest};
+use genai_rs::{Backend, Client, FunctionDeclaration, StreamChunk};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const SCOPE: &str = "/v1beta1/projects/test-project/locations/us-central1";
+
+const INTERACTION_JSON: &str = r#"{
+    "id": "interaction-123",
+    "model": "gemini-3-flash-preview",
+    "outputs": [{"type": "text", "text": "Hello from Vertex"}],
+    "status": "completed"
+}"#;
+
+const FILE_JSON: &str = r#"{
+    "name": "files/abc123",
+    "mimeType": "text/plain",
+    "uri": "http://localhost/files/abc123",
+    "state": "ACTIVE"
+}"#;
+
+const SSE_BODY: &str = concat!(
+    "data: {\"event_type\":\"content.delta\",\"index\":0,\"delta\":{\"type\":\"text\",\"text\":\"Hello\"},\"event_id\":\"evt_1\"}\n\n",
+    "data: {\"event_type\":\"interaction.complete\",\"interaction\":{\"id\":\"interaction-123\",\"status\":\"completed\"},\"event_id\":\"evt_2\"}\n\n",
+);
+
+fn route(request: &ReceivedRequest, base_url: &str) -> MockResponse {
+    let path = request.path.as_str();
+    if path.contains("alt=sse") {
+        MockResponse::sse(SSE_BODY)
+    } else if path.starts_with(&format!("/upload{SCOPE}/files")) {
+        MockResponse::json(200, "{}")
+            .with_header("x-goog-upload-url", format!("{base_url}/resumable/1"))
+    } else if path.starts_with("/resumable/") {
+        MockResponse::json(200, format!(r#"{{"file": {FILE_JSON}}}"#))
+    } else if path.starts_with(&format!("{SCOPE}/files/")) {
+        MockResponse::json(200, FILE_JSON)
+    } else if path.starts_with(&format!("{SCOPE}/interactions")) {
+        MockResponse::json(200, INTERACTION_JSON)
+    } else {
+        MockResponse::json(404, r#"{"error": "not found"}"#)
+    }
+}
+
+fn vertex_client(server: &MockServer) -> Client {
+    Client::builder("ya29.test-token".to_string())
+        .with_backend(Backend::vertex("test-project", "us-central1"))
+        .with_base_url(server.base_url())
+        .build()
+        .expect("client builds")
+}
+
+fn assert_vertex_auth(request: &ReceivedRequest) {
+    assert_eq!(
+        request.header("authorization"),
+        Some("Bearer ya29.test-token")
+    );
+    assert_eq!(request.header("x-goog-api-key"), None);
+}
+
+#[tokio::test]
+async fn test_vertex_create_interaction() {
+    let server = MockServer::start_with(route).await;
+    let client = vertex_client(&server);
+
+    let response = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create()
+        .await
+        .expect("create succeeds");
+
+    assert_eq!(response.as_text(), Some("Hello from Vertex"));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, format!("{SCOPE}/interactions"));
+    assert_vertex_auth(&requests[0]);
+}
+
+#[tokio::test]
+async fn test_vertex_create_stream() {
+    let server = MockServer::start_with(route).await;
+    let client = vertex_client(&server);
+
+    let events: Vec<_> = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Hello")
+        .create_stream()
+        .collect()
+        .await;
+
+    let events: Vec<_> = events.into_iter().map(|e| e.expect("event ok")).collect();
+    assert!(matches!(
+        events.last().unwrap().chunk,
+        StreamChunk::Complete(_)
+    ));
+    let requests = server.requests();
+    assert_eq!(requests[0].path, format!("{SCOPE}/interactions?alt=sse"));
+    assert_vertex_auth(&requests[0]);
+}
+
+#[tokio::test]
+async fn test_vertex_interaction_lifecycle() {
+    let server = MockServer::start_with(route).await;
+    let client = vertex_client(&server);
+
+    client.get_interaction("interaction-123").await.unwrap();
+    client.cancel_interaction("interaction-123").await.unwrap();
+    client.delete_interaction("interaction-123").await.unwrap();
+
+    let requests = server.requests();
+    let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            format!("{SCOPE}/interactions/interaction-123"),
+            format!("{SCOPE}/interactions/interaction-123/cancel"),
+            format!("{SCOPE}/interactions/interaction-123"),
+        ]
+    );
+    requests.iter().for_each(assert_vertex_auth);
+}
+
+#[tokio::test]
+async fn test_vertex_files() {
+    let server = MockServer::start_with(route).await;
+    let client = vertex_client(&server);
+
+    let file = client
+        .upload_file_bytes(b"hello".to_vec(), "text/plain", None)
+        .await
+        .expect("upload succeeds");
+    client.get_file(&file.name).await.expect("get succeeds");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].path, format!("/upload{SCOPE}/files"));
+    assert_vertex_auth(&requests[0]);
+    // The session URL returned by the server carries its own authorization
+    assert_eq!(requests[1].path, "/resumable/1");
+    assert_eq!(requests[2].path, format!("{SCOPE}/files/abc123"));
+    assert_vertex_auth(&requests[2]);
+}
+
+#[tokio::test]
+async fn test_vertex_auto_functions() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let server = MockServer::start_with(move |_, _| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::json(
+                200,
+                r#"{
+                    "id": "interaction-1",
+                    "status": "requires_action",
+                    "outputs": [{"type": "function_call", "id": "call-1", "name": "lookup", "arguments": {}}]
+                }"#,
+            )
+        } else {
+            MockResponse::json(200, INTERACTION_JSON)
+        }
+    })
+    .await;
+    let client = vertex_client(&server);
+
+    let lookup = FunctionDeclaration::builder("lookup")
+        .description("Looks something up")
+        .parameter("query", json!({"type": "string"}))
+        .build();
+
+    // `lookup` is not registered, so the loop reports an error result and continues
+    let result = client
+        .interaction()
+        .with_model("gemini-3-flash-preview")
+        .with_text("Look it up")
+        .add_function(lookup)
+        .create_with_auto_functions()
+        .await
+        .expect("auto-function loop completes");
+
+    assert_eq!(result.response.as_text(), Some("Hello from Vertex"));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        assert_eq!(request.path, format!("{SCOPE}/interactions"));
+        assert_vertex_auth(request);
+    }
+    assert!(requests[1].body_text().contains("interaction-1"));
+}
+
+#[tokio::test]
+async fn test_gemini_backend_still_uses_api_key() {
+    let server = MockServer::start_with(|_, _| MockResponse::json(200, INTERACTION_JSON)).await;
+    let client = Client::builder("test-key".to_string())
+        .with_backend(Backend::GeminiApi)
+        .with_base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.get_interaction("interaction-123").await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/v1beta/interactions/interaction-123");
+    assert_eq!(requests[0].header("x-goog-api-key"), Some("test-key"));
+    assert_eq!(requests[0].header("authorization"), None);
+}